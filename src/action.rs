@@ -1,7 +1,7 @@
 use serde::{Deserialize, Serialize};
 use strum::Display;
 
-use crate::app::Mode;
+use crate::{app::Mode, crates_io_api_helper::Owner};
 
 #[derive(Debug, Display, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum Action {
@@ -10,28 +10,63 @@ pub enum Action {
     KeyRefresh,
     Resize(u16, u16),
     Suspend,
-    Resume,
     Quit,
-    Init { query: Option<String> },
+    ForceQuit,
+    Init {
+        query: Option<String>,
+    },
     Refresh,
+    ReloadConfig,
     NextTab,
     PreviousTab,
+    NextTheme,
+    PreviousTheme,
+    ToggleFooterHints,
     ShowErrorPopup(String),
+    /// Shows an error that can be retried by performing the action.
+    ShowRetryPopup(String, Box<Action>),
     ShowInfoPopup(String),
+    /// Shows a notice whose action is performed if the popup is cancelled.
+    ShowCancelPopup(String, Box<Action>),
     ClosePopup,
+    CancelPopup,
+    ConfirmPopup,
+    RetryPopup,
     Help,
+    StartHelpFilter,
     GetCrates,
+    CancelSearch,
     SwitchMode(Mode),
     SwitchToLastMode,
+    NavigateBack,
+    NavigateForward,
     IncrementPage,
     DecrementPage,
     NextSummaryMode,
     PreviousSummaryMode,
-    ToggleSortBy { reload: bool, forward: bool },
+    SummaryMoveLeft,
+    SummaryMoveRight,
+    SummaryMoveUp,
+    SummaryMoveDown,
+    SummarySelect,
+    RefreshSummary,
+    /// The registry has no summary endpoint, so the Summary tab is hidden.
+    HideSummary,
+    ToggleSortBy {
+        reload: bool,
+        forward: bool,
+    },
+    ToggleSortDirection,
     ScrollBottom,
     ScrollTop,
     ScrollDown,
     ScrollUp,
+    ScrollDownBy(usize),
+    ScrollUpBy(usize),
+    ScrollHalfPageDown,
+    ScrollHalfPageUp,
+    ScrollPageDown,
+    ScrollPageUp,
     ScrollCrateInfoDown,
     ScrollCrateInfoUp,
     ScrollSearchResultsDown,
@@ -39,14 +74,61 @@ pub enum Action {
     SubmitSearch,
     UpdateSearchTableResults,
     UpdateSummary,
+    UpdateBookmarks,
+    UpdateTrending,
+    UpdateCategories,
     UpdateCurrentSelectionCrateInfo,
     UpdateCurrentSelectionSummary,
     ReloadData,
+    /// A `ReloadData` asked for by the user, which also drops the cached
+    /// crate details.
+    RefreshData,
+    RefreshCrateDetails,
+    RefreshTrending,
     ToggleShowCrateInfo,
     StoreTotalNumberOfCrates(u64),
     ClearTaskDetailsHandle(String),
+    ToggleBookmark,
+    SearchCategory,
+    RefreshCategories,
+    SearchByOwner,
+    SetMinDownloads,
+    ToggleOnlyWithRepo,
+    FilterByLicense,
+    ShowOwnerCrates(Owner),
     CopyCargoAddCommandToClipboard,
+    AddToLocalCargoToml,
+    /// Reports the outcome of a `cargo add` run, with the error output if it
+    /// failed.
+    ShowCargoAddCompletionMessage {
+        name: String,
+        error: Option<String>,
+    },
+    CopyTomlDependencyToClipboard,
+    ToggleMark,
+    CopyMarkedCargoAdd,
+    CompareCrates,
+    RefreshComparison,
     OpenDocsUrlInBrowser,
     OpenCratesIOUrlInBrowser,
+    OpenRepositoryUrlInBrowser,
+    OpenHomepageUrlInBrowser,
+    CopyCratesIoUrlToClipboard,
+    CopyDocsUrlToClipboard,
+    ShowCrateQrCode,
+    ToggleFeature,
+    CopyCargoAddWithFeatures,
     ShowFullCrateInfo,
+    ExportResults,
+    ExportCrateMarkdown,
+    /// Exports the named crate, even if the selection has moved on since.
+    ExportNamedCrateMarkdown(String),
+}
+
+impl Action {
+    /// Whether the action is sent on a timer by the event loop. Periodic
+    /// actions only need a redraw when they change something visible.
+    pub fn is_periodic(&self) -> bool {
+        matches!(self, Action::Tick | Action::Render | Action::KeyRefresh)
+    }
 }
@@ -1,30 +1,57 @@
-use std::sync::{
-    Arc,
-    atomic::{AtomicBool, Ordering},
+use std::{
+    collections::BTreeSet,
+    sync::{
+        Arc,
+        atomic::{AtomicBool, Ordering},
+    },
+    time::{Duration, Instant},
 };
 
 use color_eyre::eyre::Result;
-use crossterm::event::{Event as CrosstermEvent, KeyEvent};
-use ratatui::{DefaultTerminal, prelude::*, widgets::*};
+use crossterm::event::{
+    Event as CrosstermEvent, KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEvent,
+    MouseEventKind,
+};
+use ratatui::{DefaultTerminal, layout::Flex, prelude::*, widgets::*};
 use serde::{Deserialize, Serialize};
 use strum::{Display, EnumIs};
 use tokio::sync::mpsc::{self, UnboundedReceiver, UnboundedSender};
-use tracing::{debug, error, info};
+use tracing::{debug, error, info, warn};
 
 use crate::{
     action::Action,
-    config,
+    bookmarks::Bookmarks,
+    browser, clipboard,
+    command::Command,
+    config::SortBy,
+    config::{self, Base16Palette},
+    crates_io_api_helper,
     events::{Event, Events},
+    export,
+    history::{NavigationHistory, NavigationSnapshot, SearchSnapshot},
+    local_deps, runtime,
     serde_helper::keybindings::key_event_to_string,
+    session::SessionState,
+    tui,
     widgets::{
+        bookmarks::{BookmarksPage, BookmarksPageWidget},
+        categories::{Categories, CategoriesWidget},
+        command_palette::{CommandPalette, CommandPaletteWidget},
+        compare::{Compare, CompareWidget},
+        crate_info_table,
+        footer_hints::FooterHintsWidget,
         help::{Help, HelpWidget},
+        lockfile::{Lockfile, LockfileWidget},
         popup_message::{PopupMessageState, PopupMessageWidget},
+        qr_code::QrCodeWidget,
         search_filter_prompt::SearchFilterPromptWidget,
         search_page::SearchPage,
         search_page::SearchPageWidget,
         status_bar::StatusBarWidget,
-        summary::{Summary, SummaryWidget},
+        summary::{Summary, SummaryItem, SummaryWidget},
         tabs::SelectedTab,
+        trending::{Trending, TrendingWidget},
+        which_key::WhichKeyWidget,
     },
 };
 
@@ -40,12 +67,28 @@ pub enum Mode {
     PickerHideCrateInfo,
     Search,
     Filter,
+    Bookmarks,
+    Categories,
+    Trending,
+    Compare,
+    Lockfile,
+    FeatureSelect,
+    CommandPalette,
     Popup,
     Help,
     Quit,
 }
 
 impl Mode {
+    /// Whether the mode is recorded in the navigation history.
+    pub fn is_navigable(&self) -> bool {
+        self.is_summary()
+            || self.is_picker()
+            || self.is_bookmarks()
+            || self.is_categories()
+            || self.is_trending()
+    }
+
     pub fn is_prompt(&self) -> bool {
         self.is_search() || self.is_filter()
     }
@@ -55,6 +98,12 @@ impl Mode {
     }
 }
 
+/// How long a key sequence is pending before its completions are listed.
+const WHICH_KEY_DELAY: Duration = Duration::from_millis(400);
+
+/// Two clicks on the same row within this interval count as a double click.
+const DOUBLE_CLICK_INTERVAL: Duration = Duration::from_millis(500);
+
 struct AppWidget;
 
 #[derive(Debug)]
@@ -71,6 +120,10 @@ pub struct App {
     /// allowing different parts of the app to know if it's in a loading state.
     loading_status: Arc<AtomicBool>,
 
+    /// Whether the summary is being loaded, tracked apart from
+    /// `loading_status` as it may load at the same time as a search.
+    summary_loading_status: Arc<AtomicBool>,
+
     /// The active mode of the application, which could change how user inputs
     /// and commands are interpreted.
     mode: Mode,
@@ -83,54 +136,180 @@ pub struct App {
     /// interpreting sequences of key presses.
     last_tick_key_events: Vec<KeyEvent>,
 
+    /// A vim-style count typed before a scroll command, e.g. the `5` in `5j`.
+    pending_count: Option<usize>,
+
+    /// The start of a crate name typed to jump to it in the results table.
+    type_ahead: String,
+
     /// frame counter
     frame_count: usize,
 
+    /// Whether anything changed since the last frame was drawn.
+    needs_redraw: bool,
+
+    /// Whether a request was in flight when the last frame was drawn, so
+    /// that the frame after it finishes shows the fetched data.
+    was_loading: bool,
+
     summary: Summary,
     search: SearchPage,
+    bookmarks: Bookmarks,
+    bookmarks_page: BookmarksPage,
+    categories: Categories,
+    trending: Trending,
+    compare: Compare,
+    lockfile: Lockfile,
+    command_palette: CommandPalette,
     popup: Option<(PopupMessageWidget, PopupMessageState)>,
+
+    /// The QR code shown in a popup by `ShowCrateQrCode`, closed with the
+    /// popup.
+    qr_code: Option<QrCodeWidget>,
+
     help: Help,
     selected_tab: SelectedTab,
+
+    /// Whether the registry has no summary endpoint, which hides the Summary
+    /// tab.
+    summary_hidden: bool,
+
+    /// The color palettes available for switching at runtime, the first entry
+    /// is the palette the app was started with.
+    themes: Vec<(String, Base16Palette)>,
+
+    /// Index into `themes` of the active palette.
+    theme_index: usize,
+
+    /// The time and row of the last left click, used to detect double clicks.
+    last_click: Option<(Instant, usize)>,
+
+    /// The places visited before and after the current one.
+    history: NavigationHistory<NavigationSnapshot>,
+
+    /// The last session, reopened on init when `restore_session` is set.
+    session: Option<SessionState>,
+
+    /// The crates the project in the current directory depends on, marked in
+    /// the search results when `detect_local_deps` is set.
+    local_deps: BTreeSet<String>,
+
+    /// Whether the key binding hints are shown below the status bar.
+    show_footer_hints: bool,
+
+    /// When the keys pressed so far started a longer key sequence that is
+    /// still incomplete.
+    pending_sequence_since: Option<Instant>,
 }
 
 impl App {
-    pub fn new(query: Option<String>) -> Self {
+    pub fn new(query: Option<String>, sort: Option<SortBy>) -> Self {
         let (tx, rx) = mpsc::unbounded_channel();
+        let session = (config::get().restore_session && query.is_none() && sort.is_none())
+            .then(SessionState::load)
+            .flatten();
         let _ = tx.send(Action::Init { query });
         let loading_status = Arc::new(AtomicBool::default());
-        let search = SearchPage::new(tx.clone(), loading_status.clone());
-        let summary = Summary::new(tx.clone(), loading_status.clone());
+        let mut search = SearchPage::new(tx.clone(), loading_status.clone());
+        if let Some(sort) = sort {
+            search.choose_sort(sort.into());
+        }
+        let summary_loading_status = Arc::new(AtomicBool::default());
+        let summary = Summary::new(tx.clone(), summary_loading_status.clone());
+        let bookmarks_page = BookmarksPage::new(tx.clone(), loading_status.clone());
+        let categories = Categories::new(tx.clone(), loading_status.clone());
+        let trending = Trending::new(tx.clone(), loading_status.clone());
+        let compare = Compare::new(tx.clone(), loading_status.clone());
+        let lockfile = Lockfile::new(tx.clone(), loading_status.clone());
+        let local_deps = if config::get().detect_local_deps {
+            std::env::current_dir()
+                .map(|directory| local_deps::load(&directory))
+                .unwrap_or_default()
+        } else {
+            BTreeSet::new()
+        };
+        let mut themes = vec![("default".to_string(), config::get().color)];
+        themes.extend(config::load_themes());
         Self {
             rx,
             tx,
             mode: Mode::default(),
             last_mode: Mode::default(),
             loading_status,
+            summary_loading_status,
             search,
             summary,
+            bookmarks: Bookmarks::load(),
+            bookmarks_page,
+            categories,
+            trending,
+            compare,
+            lockfile,
+            command_palette: CommandPalette::default(),
             popup: Default::default(),
+            qr_code: None,
             last_tick_key_events: Default::default(),
+            pending_count: None,
+            type_ahead: String::new(),
             frame_count: Default::default(),
+            needs_redraw: true,
+            was_loading: false,
             help: Default::default(),
             selected_tab: Default::default(),
+            summary_hidden: false,
+            themes,
+            theme_index: 0,
+            last_click: None,
+            history: Default::default(),
+            session,
+            local_deps,
+            show_footer_hints: config::get().show_footer_hints,
+            pending_sequence_since: None,
         }
     }
 
     /// Runs the main loop of the application, handling events and actions
-    #[tokio::main]
-    pub async fn run(&mut self, tui: &mut DefaultTerminal) -> Result<()> {
+    ///
+    /// Turns on the mouse capture and bracketed paste modes enabled in the
+    /// config for the duration of the loop; the terminal itself is set up by
+    /// the caller, e.g. with [`ratatui::run`].
+    pub fn run(&mut self, tui: &mut DefaultTerminal) -> Result<()> {
+        tui::enable_input_modes()?;
+        let result = runtime::build().and_then(|runtime| runtime.block_on(self.run_loop(tui)));
+        tui::disable_input_modes()?;
+        result
+    }
+
+    async fn run_loop(&mut self, tui: &mut DefaultTerminal) -> Result<()> {
         // uncomment to test error handling
         // panic!("test panic");
         // Err(color_eyre::eyre::eyre!("Error"))?;
         let mut events = Events::new();
         loop {
             if let Some(e) = events.next().await {
+                if matches!(e, Event::Crossterm(_)) {
+                    // input may change state without producing an action
+                    self.needs_redraw = true;
+                }
                 self.handle_event(e)?.map(|action| self.tx.send(action));
             }
             while let Ok(action) = self.rx.try_recv() {
                 self.handle_action(action.clone())?;
-                if matches!(action, Action::Resize(_, _) | Action::Render) {
-                    tui.draw(|frame| self.render(frame))?;
+                match action {
+                    Action::Resize(_, _) => self.draw(tui)?,
+                    Action::Render => {
+                        // keep animating the spinner while a request is in flight
+                        let loading = self.loading();
+                        if self.needs_redraw || loading || self.was_loading {
+                            self.draw(tui)?;
+                        }
+                        self.was_loading = loading;
+                    }
+                    Action::Suspend => {
+                        tui::suspend(tui)?;
+                        self.draw(tui)?;
+                    }
+                    _ => {}
                 }
             }
             if self.should_quit() {
@@ -152,28 +331,148 @@ impl App {
             Event::Tick => Some(Action::Tick),
             Event::KeyRefresh => Some(Action::KeyRefresh),
             Event::Render => Some(Action::Render),
+            Event::ConfigChanged => Some(Action::ReloadConfig),
             Event::Crossterm(CrosstermEvent::Resize(x, y)) => Some(Action::Resize(x, y)),
             Event::Crossterm(CrosstermEvent::Key(key)) => self.handle_key_event(key)?,
+            Event::Crossterm(CrosstermEvent::Mouse(mouse)) => self.handle_mouse_event(mouse),
+            Event::Crossterm(CrosstermEvent::Paste(text)) => self.handle_paste(&text),
             _ => None,
         };
         Ok(maybe_action)
     }
 
+    /// Inserts pasted text into the search or filter prompt.
+    fn handle_paste(&mut self, text: &str) -> Option<Action> {
+        match self.mode {
+            Mode::Search => self.search.handle_paste(text),
+            Mode::Filter => {
+                self.search.handle_paste(text);
+                self.search.handle_filter_prompt_change();
+            }
+            _ => {}
+        }
+        None
+    }
+
+    /// Selects the clicked row in the search results or bookmarks table.
+    ///
+    /// Clicking the selected row again within [`DOUBLE_CLICK_INTERVAL`]
+    /// toggles the crate info pane.
+    fn handle_mouse_event(&mut self, mouse: MouseEvent) -> Option<Action> {
+        if mouse.kind != MouseEventKind::Down(MouseButton::Left) {
+            return None;
+        }
+        let position = Position::new(mouse.column, mouse.row);
+        let index = if self.mode.is_bookmarks() {
+            self.bookmarks_page.results.select_at(position)?
+        } else if self.mode.is_trending() {
+            self.trending.results.select_at(position)?
+        } else if self.mode.is_picker() {
+            self.search.results.select_at(position)?
+        } else {
+            return None;
+        };
+        let now = Instant::now();
+        let double_click = self.last_click.is_some_and(|(time, last_index)| {
+            last_index == index && now.duration_since(time) < DOUBLE_CLICK_INTERVAL
+        });
+        if double_click {
+            self.last_click = None;
+            self.mode.is_picker().then_some(Action::ToggleShowCrateInfo)
+        } else {
+            self.last_click = Some((now, index));
+            self.mode
+                .is_picker()
+                .then_some(Action::UpdateCurrentSelectionCrateInfo)
+        }
+    }
+
     fn handle_key_event(&mut self, key: KeyEvent) -> Result<Option<Action>> {
         debug!("Received key {:?}", key);
+        if self.mode.is_picker_hide_crate_info() && self.handle_type_ahead(key) {
+            return Ok(None);
+        }
         match self.mode {
-            Mode::Search => {
-                self.search.handle_key(key);
+            Mode::Search | Mode::Filter => {
+                let readline = self.search.handle_key(key);
+                if self.mode.is_filter() {
+                    self.search.handle_filter_prompt_change();
+                }
+                if readline {
+                    return Ok(None);
+                }
             }
-            Mode::Filter => {
-                self.search.handle_key(key);
-                self.search.handle_filter_prompt_change();
+            Mode::CommandPalette => {
+                match key.code {
+                    KeyCode::Esc => self.switch_to_last_mode(),
+                    KeyCode::Enter => self.run_palette_command(),
+                    _ => self.command_palette.handle_key(key),
+                }
+                return Ok(None);
+            }
+            Mode::Help if self.help.filtering => {
+                self.help.handle_filter_key(key);
+                return Ok(None);
+            }
+            Mode::Help if key.code == KeyCode::Esc && !self.help.filter.is_empty() => {
+                self.help.clear_filter();
+                return Ok(None);
             }
             _ => (),
         };
         Ok(self.handle_key_events_from_config(key))
     }
 
+    /// Feeds printable keys into the type-ahead buffer and selects the first
+    /// crate whose name starts with it.
+    ///
+    /// Type-ahead starts with a key that isn't bound to a command; once it has
+    /// started every printable key extends it until the next key refresh.
+    /// Returns `true` if the key was consumed.
+    fn handle_type_ahead(&mut self, key: KeyEvent) -> bool {
+        if !(key.modifiers - KeyModifiers::SHIFT).is_empty() {
+            self.type_ahead.clear();
+            return false;
+        }
+        match key.code {
+            KeyCode::Backspace if !self.type_ahead.is_empty() => {
+                self.type_ahead.pop();
+                return true;
+            }
+            KeyCode::Char(c) if !self.type_ahead.is_empty() => self.type_ahead.push(c),
+            KeyCode::Char(c) if !c.is_ascii_digit() && !c.is_whitespace() => {
+                // leave keys that are bound, or start a bound sequence like `gg`,
+                // to the key bindings
+                let key_bindings = &config::get().key_bindings;
+                let is_bound = |mode| {
+                    key_bindings.event_to_command(mode, &[key]).is_some()
+                        || key_bindings.is_sequence_prefix(mode, &[key])
+                };
+                if is_bound(self.mode) || is_bound(Mode::Common) {
+                    return false;
+                }
+                self.type_ahead.push(c);
+            }
+            _ => {
+                self.type_ahead.clear();
+                return false;
+            }
+        }
+        let prefix = self.type_ahead.to_lowercase().replace('-', "_");
+        let index = self.search.results.crates.iter().position(|krate| {
+            krate
+                .name
+                .to_lowercase()
+                .replace('-', "_")
+                .starts_with(&prefix)
+        });
+        if let Some(index) = index {
+            self.search.results.select(Some(index));
+            let _ = self.tx.send(Action::UpdateCurrentSelectionCrateInfo);
+        }
+        true
+    }
+
     /// Evaluates a sequence of key events against user-configured key bindings
     /// to determine if an `Action` should be triggered.
     ///
@@ -181,9 +480,20 @@ impl App {
     /// events over time and then translating them into actions according to the
     /// current mode.
     fn handle_key_events_from_config(&mut self, key: KeyEvent) -> Option<Action> {
+        if key.code == KeyCode::Esc && self.pending_sequence_since.is_some() {
+            // abandon the pending sequence rather than quitting
+            self.key_refresh_tick();
+            return None;
+        }
         self.last_tick_key_events.push(key);
+        if let Some(digit) = self.count_digit(key) {
+            let count = self.pending_count.unwrap_or_default();
+            self.pending_count = Some(count.saturating_mul(10).saturating_add(digit));
+            return None;
+        }
+        let count = self.pending_count.take();
         let config = config::get();
-        config
+        let action = config
             .key_bindings
             .event_to_command(self.mode, &self.last_tick_key_events)
             .or_else(|| {
@@ -192,6 +502,69 @@ impl App {
                     .event_to_command(Mode::Common, &self.last_tick_key_events)
             })
             .map(|command| config.key_bindings.command_to_action(command))
+            .map(|action| match (action, count) {
+                (Action::ScrollDown, Some(count)) => Action::ScrollDownBy(count),
+                (Action::ScrollUp, Some(count)) => Action::ScrollUpBy(count),
+                (action, _) => action,
+            });
+        self.pending_sequence_since = match action {
+            None if !self.sequence_completions().is_empty() => {
+                self.pending_sequence_since.or_else(|| Some(Instant::now()))
+            }
+            _ => None,
+        };
+        action
+    }
+
+    /// Returns the keys that complete the pending key sequence, in the current
+    /// mode or every mode, with their commands.
+    fn sequence_completions(&self) -> Vec<(Vec<KeyEvent>, Command)> {
+        let key_bindings = &config::get().key_bindings;
+        let mut completions = key_bindings.completions(self.mode, &self.last_tick_key_events);
+        completions.extend(
+            key_bindings
+                .completions(Mode::Common, &self.last_tick_key_events)
+                .into_iter()
+                .filter(|(keys, _)| completions.iter().all(|(other, _)| other != keys))
+                .collect::<Vec<_>>(),
+        );
+        completions
+    }
+
+    /// Lists the completions of a pending key sequence once it has been
+    /// pending for `WHICH_KEY_DELAY`.
+    fn which_key_widget(&self) -> Option<WhichKeyWidget> {
+        self.pending_sequence_since
+            .filter(|since| since.elapsed() >= WHICH_KEY_DELAY)?;
+        let completions = self.sequence_completions();
+        let pending = self.last_tick_key_events.clone();
+        (!completions.is_empty()).then(|| WhichKeyWidget::new(pending, completions))
+    }
+
+    /// Returns the digit if `key` should be accumulated into a count prefix.
+    ///
+    /// Counts are only recognized outside of the prompt, and only for digits
+    /// that aren't bound to a command. A leading `0` is not a count.
+    fn count_digit(&self, key: KeyEvent) -> Option<usize> {
+        if self.mode.is_prompt() || !key.modifiers.is_empty() {
+            return None;
+        }
+        let digit = match key.code {
+            KeyCode::Char(c) => c.to_digit(10)? as usize,
+            _ => return None,
+        };
+        if digit == 0 && self.pending_count.is_none() {
+            return None;
+        }
+        let key_bindings = &config::get().key_bindings;
+        if key_bindings.event_to_command(self.mode, &[key]).is_some()
+            || key_bindings
+                .event_to_command(Mode::Common, &[key])
+                .is_some()
+        {
+            return None;
+        }
+        Some(digit)
     }
 
     /// Performs the `Action` by calling on a respective app method.
@@ -201,18 +574,50 @@ impl App {
     /// navigation within the application, are also handled. Certain actions generate a follow-up
     /// action which will be to be processed in the next iteration of the main event loop.
     fn handle_action(&mut self, action: Action) -> Result<()> {
-        if action != Action::Tick && action != Action::Render && action != Action::KeyRefresh {
+        if !action.is_periodic() {
             info!("{action:?}");
+            self.needs_redraw = true;
+        }
+        match action {
+            Action::SwitchMode(mode) if mode != self.mode && !mode.is_command_palette() => {
+                self.record_history()
+            }
+            Action::SearchCategory | Action::SummarySelect | Action::ShowOwnerCrates(_) => {
+                self.record_history()
+            }
+            _ => {}
         }
         match action {
-            Action::Quit => self.quit(),
+            Action::Quit => self.request_quit(),
+            Action::ForceQuit => self.quit(),
             Action::KeyRefresh => self.key_refresh_tick(),
+            Action::ReloadConfig => self.reload_config(),
             Action::Init { ref query } => self.init(query)?,
             Action::Tick => self.tick(),
             Action::StoreTotalNumberOfCrates(n) => self.store_total_number_of_crates(n),
-            Action::ScrollUp => self.scroll_up(),
-            Action::ScrollDown => self.scroll_down(),
-
+            Action::ScrollUp => self.scroll_up(1),
+            Action::ScrollDown => self.scroll_down(1),
+            Action::ScrollUpBy(count) => self.scroll_up(count),
+            Action::ScrollDownBy(count) => self.scroll_down(count),
+            Action::ScrollHalfPageUp => self.scroll_up(self.page_size().div_ceil(2)),
+            Action::ScrollHalfPageDown => self.scroll_down(self.page_size().div_ceil(2)),
+            Action::ScrollPageUp => self.scroll_up(self.page_size()),
+            Action::ScrollPageDown => self.scroll_down(self.page_size()),
+
+            Action::ScrollTop if self.mode.is_bookmarks() => {
+                self.bookmarks_page.results.scroll_to_top()
+            }
+            Action::ScrollBottom if self.mode.is_bookmarks() => {
+                self.bookmarks_page.results.scroll_to_bottom()
+            }
+            Action::ScrollTop if self.mode.is_trending() => self.trending.results.scroll_to_top(),
+            Action::ScrollBottom if self.mode.is_trending() => {
+                self.trending.results.scroll_to_bottom()
+            }
+            Action::ScrollTop if self.mode.is_categories() => self.categories.scroll_to_top(),
+            Action::ScrollBottom if self.mode.is_categories() => self.categories.scroll_to_bottom(),
+            Action::ScrollTop if self.mode.is_lockfile() => self.lockfile.scroll_to_top(),
+            Action::ScrollBottom if self.mode.is_lockfile() => self.lockfile.scroll_to_bottom(),
             Action::ScrollTop
             | Action::ScrollBottom
             | Action::ScrollSearchResultsUp
@@ -221,23 +626,76 @@ impl App {
             Action::ScrollCrateInfoUp => self.search.crate_info.scroll_previous(),
             Action::ScrollCrateInfoDown => self.search.crate_info.scroll_next(),
             Action::ReloadData => self.search.reload_data(),
+            Action::RefreshData => {
+                self.search.clear_details_cache();
+                self.search.reload_data();
+            }
+            Action::RefreshCrateDetails => self.search.refresh_crate_details(),
+            Action::RefreshTrending => self.trending.request(),
             Action::IncrementPage => self.search.increment_page(),
             Action::DecrementPage => self.search.decrement_page(),
             Action::NextSummaryMode => self.summary.next_mode(),
             Action::PreviousSummaryMode => self.summary.previous_mode(),
+            Action::SummaryMoveLeft => self.summary.move_left(),
+            Action::SummaryMoveRight => self.summary.move_right(),
+            Action::SummaryMoveUp => self.summary.move_up(),
+            Action::SummaryMoveDown => self.summary.move_down(),
+            Action::NextTheme => self.next_theme(),
+            Action::PreviousTheme => self.previous_theme(),
+            Action::ToggleFooterHints => self.show_footer_hints = !self.show_footer_hints,
             Action::NextTab => self.goto_next_tab(),
             Action::PreviousTab => self.goto_previous_tab(),
             Action::SwitchMode(mode) => self.switch_mode(mode),
             Action::SwitchToLastMode => self.switch_to_last_mode(),
+            Action::NavigateBack => self.navigate_back(),
+            Action::NavigateForward => self.navigate_forward(),
+            Action::StartHelpFilter => self.help.start_filter(),
             Action::SubmitSearch => self.search.submit_query(),
             Action::ToggleShowCrateInfo => self.search.toggle_show_crate_info(),
             Action::UpdateCurrentSelectionCrateInfo => self.update_current_selection_crate_info(),
-            Action::UpdateSearchTableResults => self.search.update_search_table_results(),
+            Action::UpdateSearchTableResults => {
+                self.search.update_search_table_results();
+                self.search.apply_pending_selection();
+            }
             Action::UpdateSummary => self.summary.update(),
+            Action::UpdateBookmarks => self.bookmarks_page.update(&self.bookmarks),
+            Action::UpdateTrending => self.trending.update(),
+            Action::UpdateCategories => self.categories.update(),
+            Action::SearchCategory => self.search_category(),
+            Action::RefreshCategories => self.categories.request(),
+            Action::SummarySelect => self.select_summary_item(),
+            Action::RefreshSummary => self.summary.refresh(),
+            Action::HideSummary => {
+                self.summary_hidden = true;
+                if self.mode.is_summary() {
+                    self.switch_mode(Mode::Search);
+                }
+            }
+            Action::SearchByOwner => self.search.request_owner_crates(),
+            Action::ToggleSortDirection => self.search.toggle_sort_direction(),
+            Action::SetMinDownloads => {
+                if let Err(err) = self.search.set_min_downloads_from_input() {
+                    let _ = self.tx.send(Action::ShowErrorPopup(err.to_string()));
+                }
+            }
+            Action::ToggleOnlyWithRepo => self.search.toggle_only_with_repo(),
+            Action::FilterByLicense => self.search.set_license_filter_from_input(),
+            Action::ShowOwnerCrates(ref owner) => self.search.search_by_owner(owner.clone()),
+            Action::ToggleBookmark => self.toggle_bookmark(),
             Action::ShowFullCrateInfo => self.show_full_crate_details(),
-            Action::ShowErrorPopup(ref err) => self.show_error_popup(err.clone()),
+            Action::ShowErrorPopup(ref err) => self.show_error_popup(err.clone(), None),
+            Action::ShowRetryPopup(ref err, ref retry) => {
+                self.show_error_popup(err.clone(), Some(*retry.clone()))
+            }
             Action::ShowInfoPopup(ref info) => self.show_info_popup(info.clone()),
+            Action::ShowCancelPopup(ref info, ref cancel) => {
+                self.show_cancel_popup(info.clone(), *cancel.clone())
+            }
             Action::ClosePopup => self.close_popup(),
+            Action::CancelPopup => self.cancel_popup(),
+            Action::CancelSearch => self.search.cancel_search(),
+            Action::ConfirmPopup => self.confirm_popup(),
+            Action::RetryPopup => self.retry_popup(),
             Action::ToggleSortBy { reload, forward } => {
                 self.search.toggle_sort_by(reload, forward)?
             }
@@ -249,11 +707,42 @@ impl App {
                 self.open_summary_url_in_browser()?
             }
             Action::OpenCratesIOUrlInBrowser => self.open_crates_io_url_in_browser()?,
+            Action::OpenRepositoryUrlInBrowser => self.open_repository_url_in_browser()?,
+            Action::OpenHomepageUrlInBrowser => self.open_homepage_url_in_browser()?,
+            Action::CopyCratesIoUrlToClipboard => self.copy_url_to_clipboard(self.crates_io_url()),
+            Action::CopyDocsUrlToClipboard => self.copy_url_to_clipboard(self.docs_url()),
+            Action::ShowCrateQrCode => self.show_crate_qr_code(),
+            Action::ToggleFeature => self.toggle_feature(),
+            Action::CopyCargoAddWithFeatures => self.copy_cargo_add_with_features_to_clipboard(),
             Action::CopyCargoAddCommandToClipboard => self.copy_cargo_add_command_to_clipboard()?,
+            Action::AddToLocalCargoToml => self.add_to_local_cargo_toml(),
+            Action::ShowCargoAddCompletionMessage {
+                ref name,
+                ref error,
+            } => self.show_cargo_add_completion_message(name.clone(), error.clone()),
+            Action::CopyTomlDependencyToClipboard => self.copy_toml_dependency_to_clipboard()?,
+            Action::ToggleMark => self.search.results.toggle_mark(),
+            Action::CopyMarkedCargoAdd => self.copy_marked_cargo_add_to_clipboard(),
+            Action::CompareCrates => self.compare_crates(),
+            Action::RefreshComparison => self.compare.refresh(),
+            Action::ExportResults => self.export_results(),
+            Action::ExportCrateMarkdown => self.export_crate_markdown(),
+            Action::ExportNamedCrateMarkdown(ref name) => {
+                self.export_named_crate_markdown(name.clone())
+            }
             _ => {}
         }
         match action {
-            Action::ScrollUp | Action::ScrollDown | Action::ScrollTop | Action::ScrollBottom
+            Action::ScrollUp
+            | Action::ScrollDown
+            | Action::ScrollUpBy(_)
+            | Action::ScrollDownBy(_)
+            | Action::ScrollHalfPageUp
+            | Action::ScrollHalfPageDown
+            | Action::ScrollPageUp
+            | Action::ScrollPageDown
+            | Action::ScrollTop
+            | Action::ScrollBottom
                 if self.mode.is_prompt() || self.mode.is_picker() =>
             {
                 let _ = self.tx.send(Action::UpdateCurrentSelectionCrateInfo);
@@ -267,8 +756,19 @@ impl App {
     }
 
     // Render the `AppWidget` as a stateful widget using `self` as the `State`
+    fn draw(&mut self, tui: &mut DefaultTerminal) -> Result<()> {
+        self.search.crate_info.clear_hyperlinks();
+        tui.draw(|frame| self.render(frame))?;
+        tui::write_hyperlinks(self.search.crate_info.hyperlinks())?;
+        self.needs_redraw = false;
+        Ok(())
+    }
+
     fn render(&mut self, frame: &mut Frame) {
         frame.render_stateful_widget(AppWidget, frame.area(), self);
+        self.search
+            .crate_info
+            .retain_visible_hyperlinks(frame.buffer_mut());
         self.update_frame_count(frame);
         self.update_cursor(frame);
     }
@@ -276,7 +776,27 @@ impl App {
 
 impl App {
     fn tick(&mut self) {
-        self.search.update_search_table_results();
+        self.search.tick();
+        if self.search.refresh_search_table_results() {
+            self.needs_redraw = true;
+        }
+        if self
+            .pending_sequence_since
+            .is_some_and(|since| since.elapsed() >= WHICH_KEY_DELAY)
+        {
+            // show the which-key popup
+            self.needs_redraw = true;
+        }
+        // a notice offering to cancel loading is moot once loading finishes
+        if !self.loading()
+            && self
+                .popup
+                .as_ref()
+                .is_some_and(|(popup, _)| popup.cancel_action().is_some())
+        {
+            self.close_popup();
+            self.needs_redraw = true;
+        }
     }
 
     fn init(&mut self, query: &Option<String>) -> Result<()> {
@@ -284,51 +804,249 @@ impl App {
             self.search.search = query.clone();
             let _ = self.tx.send(Action::SwitchMode(Mode::Search));
             let _ = self.tx.send(Action::SubmitSearch);
+        } else if let Some(session) = self.session.take() {
+            self.restore_session(session)?;
         } else {
             self.summary.request()?;
+            if config::get().prefetch_on_startup {
+                self.search.reload_data();
+            }
         }
+        self.show_config_warnings();
         Ok(())
     }
 
+    /// Shows the problems worked around while loading the config, which
+    /// would otherwise only be logged.
+    fn show_config_warnings(&self) {
+        let warnings = config::take_warnings();
+        if !warnings.is_empty() {
+            let _ = self.tx.send(Action::ShowErrorPopup(warnings.join("\n")));
+        }
+    }
+
+    /// Reopens a saved session, searching again rather than showing the
+    /// results from back then.
+    fn restore_session(&mut self, session: SessionState) -> Result<()> {
+        if !session.query.is_empty() || session.category.is_some() || session.owner.is_some() {
+            self.search.set_sort(session.sort.into());
+            self.search.sort_ascending = session.sort_ascending;
+            self.search.restore(SearchSnapshot {
+                query: session.query,
+                category: session.category,
+                owner: session.owner,
+                page: session.page,
+            });
+        }
+        if session.mode.is_picker() {
+            self.search.pending_selection = session.selection;
+        } else {
+            self.summary.request()?;
+        }
+        if session.mode.is_navigable() {
+            self.switch_mode(session.mode);
+        }
+        Ok(())
+    }
+
+    fn session_state(&self) -> SessionState {
+        let mode = if self.mode.is_navigable() {
+            self.mode
+        } else {
+            self.last_mode
+        };
+        SessionState {
+            mode,
+            query: self.search.search.clone(),
+            sort: SortBy::from(&self.search.sort),
+            sort_ascending: self.search.sort_ascending,
+            category: self.search.category.clone(),
+            owner: self.search.owner.clone(),
+            page: self.search.page,
+            selection: self.search.results.selected(),
+        }
+    }
+
     fn key_refresh_tick(&mut self) {
+        // the which-key popup and type-ahead buffer are shown while pending
+        if self.pending_sequence_since.is_some()
+            || self.pending_count.is_some()
+            || !self.type_ahead.is_empty()
+        {
+            self.needs_redraw = true;
+        }
         self.last_tick_key_events.drain(..);
+        self.pending_sequence_since = None;
+        self.pending_count = None;
+        self.type_ahead.clear();
     }
 
     fn should_quit(&self) -> bool {
         self.mode == Mode::Quit
     }
 
+    /// Quits, or asks for confirmation first if `confirm_quit` is set.
+    fn request_quit(&mut self) {
+        if config::get().confirm_quit && !self.mode.is_popup() {
+            self.popup = Some((
+                PopupMessageWidget::confirm(
+                    "Quit".into(),
+                    "Are you sure you want to quit?".into(),
+                    Action::ForceQuit,
+                ),
+                PopupMessageState::default(),
+            ));
+            self.switch_mode(Mode::Popup);
+        } else {
+            self.quit();
+        }
+    }
+
+    /// Aborts in-flight requests so that the runtime can shut down promptly.
     fn quit(&mut self) {
+        if config::get().restore_session
+            && let Err(err) = self.session_state().save()
+        {
+            warn!("Unable to save session: {err}");
+        }
+        self.search.abort_all_tasks();
+        self.summary.abort_request();
+        self.compare.abort_request();
         self.mode = Mode::Quit
     }
 
-    fn scroll_up(&mut self) {
+    fn scroll_up(&mut self, count: usize) {
         match self.mode {
             Mode::Popup => {
                 if let Some((_, popup_state)) = &mut self.popup {
-                    popup_state.scroll_up();
+                    (0..count).for_each(|_| popup_state.scroll_up());
+                }
+            }
+            Mode::Summary => (0..count).for_each(|_| self.summary.scroll_previous()),
+            Mode::Help => (0..count).for_each(|_| self.help.scroll_up()),
+            Mode::Bookmarks => self.bookmarks_page.scroll_up(count),
+            Mode::Trending => self.trending.scroll_up(count),
+            Mode::FeatureSelect => {
+                if let Some((name, _)) = self.selected_crate_features() {
+                    self.search.crate_info.previous_feature(&name, count);
                 }
             }
-            Mode::Summary => self.summary.scroll_previous(),
-            Mode::Help => self.help.scroll_up(),
-            _ => self.search.scroll_up(),
+            Mode::Categories => self.categories.scroll_previous(count),
+            Mode::Lockfile => self.lockfile.scroll_previous(count),
+            _ => self.search.scroll_up(count),
         }
     }
 
-    fn scroll_down(&mut self) {
+    fn scroll_down(&mut self, count: usize) {
         match self.mode {
             Mode::Popup => {
                 if let Some((_, popup_state)) = &mut self.popup {
-                    popup_state.scroll_down();
+                    (0..count).for_each(|_| popup_state.scroll_down());
                 }
             }
-            Mode::Summary => self.summary.scroll_next(),
-            Mode::Help => self.help.scroll_down(),
-            _ => self.search.scroll_down(),
+            Mode::Summary => (0..count).for_each(|_| self.summary.scroll_next()),
+            Mode::Help => (0..count).for_each(|_| self.help.scroll_down()),
+            Mode::Bookmarks => self.bookmarks_page.scroll_down(count),
+            Mode::Trending => self.trending.scroll_down(count),
+            Mode::FeatureSelect => {
+                if let Some((name, features)) = self.selected_crate_features() {
+                    let feature_count = features.len();
+                    self.search
+                        .crate_info
+                        .next_feature(&name, count, feature_count);
+                }
+            }
+            Mode::Categories => self.categories.scroll_next(count),
+            Mode::Lockfile => self.lockfile.scroll_next(count),
+            _ => self.search.scroll_down(count),
+        }
+    }
+
+    /// Returns the number of rows visible in the current mode's list at the
+    /// last render.
+    fn page_size(&self) -> usize {
+        match self.mode {
+            Mode::Summary => self.summary.page_size,
+            Mode::Help => self.help.page_size,
+            Mode::Bookmarks => self.bookmarks_page.results.page_size(),
+            Mode::Trending => self.trending.results.page_size(),
+            Mode::Categories => self.categories.page_size,
+            Mode::Lockfile => self.lockfile.page_size,
+            Mode::Popup => 1,
+            _ => self.search.results.page_size(),
+        }
+    }
+
+    fn snapshot(&self) -> NavigationSnapshot {
+        let selection = match self.mode {
+            Mode::PickerShowCrateInfo | Mode::PickerHideCrateInfo => self.search.results.selected(),
+            Mode::Bookmarks => self.bookmarks_page.results.selected(),
+            Mode::Trending => self.trending.results.selected(),
+            Mode::Categories => self.categories.table_state.selected(),
+            _ => None,
+        };
+        NavigationSnapshot {
+            mode: self.mode,
+            selection,
+            search: self.mode.is_picker().then(|| self.search.snapshot()),
+        }
+    }
+
+    /// Remembers the current place before navigating away from it.
+    fn record_history(&mut self) {
+        if self.mode.is_navigable() {
+            let snapshot = self.snapshot();
+            self.history
+                .push(snapshot, config::get().navigation_history_size);
+        }
+    }
+
+    fn navigate_back(&mut self) {
+        if self.mode.is_navigable()
+            && let Some(snapshot) = self.history.back(self.snapshot())
+        {
+            self.restore_snapshot(snapshot);
+        }
+    }
+
+    fn navigate_forward(&mut self) {
+        if self.mode.is_navigable()
+            && let Some(snapshot) = self.history.forward(self.snapshot())
+        {
+            self.restore_snapshot(snapshot);
+        }
+    }
+
+    /// Returns to a place from the navigation history, only searching again
+    /// if the results table shows a different search.
+    fn restore_snapshot(&mut self, snapshot: NavigationSnapshot) {
+        self.switch_mode(snapshot.mode);
+        match snapshot.search {
+            Some(search) if search != self.search.snapshot() => {
+                self.search.restore(search);
+                self.search.pending_selection = snapshot.selection;
+            }
+            Some(_) => {
+                self.search.results.select(snapshot.selection);
+                let _ = self.tx.send(Action::UpdateCurrentSelectionCrateInfo);
+            }
+            None if snapshot.mode.is_bookmarks() => {
+                self.bookmarks_page.results.select(snapshot.selection)
+            }
+            None if snapshot.mode.is_trending() => self.trending.results.select(snapshot.selection),
+            None if snapshot.mode.is_categories() => {
+                self.categories.table_state.select(snapshot.selection)
+            }
+            None => {}
         }
     }
 
     fn switch_mode(&mut self, mode: Mode) {
+        let mode = if mode.is_summary() && self.summary_hidden {
+            Mode::Search
+        } else {
+            mode
+        };
         self.last_mode = self.mode;
         self.mode = mode;
         self.search.mode = mode;
@@ -347,11 +1065,44 @@ impl App {
             }
             Mode::Help => {
                 self.search.enter_normal_mode();
+                self.help.clear_filter();
                 self.help.mode = Some(self.last_mode);
                 self.selected_tab.select(SelectedTab::None)
             }
+            Mode::Bookmarks => {
+                self.search.enter_normal_mode();
+                self.selected_tab.select(SelectedTab::Bookmarks);
+                self.bookmarks_page.request(&self.bookmarks);
+            }
+            Mode::Categories => {
+                self.search.enter_normal_mode();
+                self.selected_tab.select(SelectedTab::Categories);
+                if self.categories.is_empty() {
+                    self.categories.request();
+                }
+            }
+            Mode::Trending => {
+                self.search.enter_normal_mode();
+                self.selected_tab.select(SelectedTab::Trending);
+                if self.trending.is_empty() {
+                    self.trending.request();
+                }
+            }
+            Mode::CommandPalette => self.command_palette.open(self.last_mode),
+            Mode::Lockfile => {
+                self.search.enter_normal_mode();
+                self.selected_tab.select(SelectedTab::None);
+                self.lockfile.load();
+            }
             Mode::PickerShowCrateInfo | Mode::PickerHideCrateInfo => {
                 self.search.enter_normal_mode();
+                if self.last_mode.is_feature_select() {
+                    self.search.show_crate_info();
+                }
+                self.selected_tab.select(SelectedTab::Search)
+            }
+            Mode::FeatureSelect => {
+                self.search.show_crate_info();
                 self.selected_tab.select(SelectedTab::Search)
             }
             _ => {
@@ -365,42 +1116,188 @@ impl App {
         self.switch_mode(self.last_mode);
     }
 
+    /// Closes the command palette and runs the selected command in the mode
+    /// the palette was opened from.
+    fn run_palette_command(&mut self) {
+        let Some(command) = self.command_palette.selected_command() else {
+            return;
+        };
+        self.switch_to_last_mode();
+        let action = config::get().key_bindings.command_to_action(command);
+        let _ = self.tx.send(action);
+    }
+
     fn goto_next_tab(&mut self) {
-        match self.mode {
-            Mode::Summary => self.switch_mode(Mode::Search),
-            Mode::Search => self.switch_mode(Mode::Summary),
-            _ => self.switch_mode(Mode::Summary),
+        let mut tab = self.selected_tab.next();
+        if self.summary_hidden && tab == SelectedTab::Summary {
+            tab = tab.next();
         }
+        self.switch_mode(tab.mode());
     }
 
     fn goto_previous_tab(&mut self) {
-        match self.mode {
-            Mode::Summary => self.switch_mode(Mode::Search),
-            Mode::Search => self.switch_mode(Mode::Summary),
-            _ => self.switch_mode(Mode::Summary),
+        let mut tab = self.selected_tab.previous();
+        if self.summary_hidden && tab == SelectedTab::Summary {
+            tab = tab.previous();
+        }
+        self.switch_mode(tab.mode());
+    }
+
+    /// Opens the crate selected in the summary, or searches for the selected
+    /// keyword or category.
+    fn select_summary_item(&mut self) {
+        let summary_sort = config::get()
+            .summary_sort
+            .unwrap_or(config::get().default_sort);
+        match self.summary.selected_item() {
+            Some(SummaryItem::Crate(name)) => {
+                self.search.set_sort(summary_sort.into());
+                self.search.set_query(name);
+                self.switch_mode(Mode::PickerHideCrateInfo);
+                self.search.toggle_show_crate_info();
+                let _ = self.tx.send(Action::ReloadData);
+            }
+            Some(SummaryItem::Keyword(id)) => {
+                self.search.set_query(format!("keyword:{id}"));
+                self.switch_mode(Mode::Search);
+                self.search.submit_query();
+                // after submitting, which resets the sort of typed queries
+                self.search.set_sort(summary_sort.into());
+                let _ = self.tx.send(Action::ReloadData);
+            }
+            Some(SummaryItem::Category(slug)) => self.search.search_by_category(slug),
+            None => {
+                let _ = self
+                    .tx
+                    .send(Action::ShowErrorPopup("No summary item selected".into()));
+            }
+        }
+    }
+
+    fn search_category(&mut self) {
+        if let Some(slug) = self.categories.selected_slug() {
+            self.search.search_by_category(slug);
+        } else {
+            let _ = self
+                .tx
+                .send(Action::ShowErrorPopup("No category selected".into()));
+        }
+    }
+
+    fn toggle_bookmark(&mut self) {
+        let name = if self.mode.is_bookmarks() {
+            self.bookmarks_page.results.selected_crate_name()
+        } else if self.mode.is_trending() {
+            self.trending.results.selected_crate_name()
+        } else {
+            self.search.results.selected_crate_name()
+        };
+        let Some(name) = name else {
+            let _ = self.tx.send(Action::ShowErrorPopup(
+                "No selection made to bookmark".into(),
+            ));
+            return;
+        };
+        self.bookmarks.toggle(&name);
+        if let Err(err) = self.bookmarks.save() {
+            let _ = self.tx.send(Action::ShowErrorPopup(format!(
+                "Unable to save bookmarks: {err}"
+            )));
+        }
+        if self.mode.is_bookmarks() {
+            let _ = self.tx.send(Action::UpdateBookmarks);
+        }
+    }
+
+    fn reload_config(&mut self) {
+        match config::reload() {
+            Ok(()) => {
+                info!("Reloaded config from {:?}", config::get().config_file);
+                // the reloaded color file replaces the palette the app started with
+                if let Some((_, palette)) = self.themes.first_mut() {
+                    *palette = config::get().color;
+                }
+                self.theme_index = 0;
+                self.show_config_warnings();
+                let _ = self.tx.send(Action::Render);
+            }
+            Err(err) => {
+                let _ = self.tx.send(Action::ShowErrorPopup(format!(
+                    "Unable to reload config: {err}"
+                )));
+            }
         }
     }
 
-    fn show_error_popup(&mut self, message: String) {
+    fn next_theme(&mut self) {
+        self.select_theme(self.theme_index.saturating_add(1));
+    }
+
+    fn previous_theme(&mut self) {
+        self.select_theme(self.theme_index + self.themes.len().saturating_sub(1));
+    }
+
+    fn select_theme(&mut self, index: usize) {
+        if self.themes.is_empty() {
+            return;
+        }
+        self.theme_index = index % self.themes.len();
+        let (name, palette) = &self.themes[self.theme_index];
+        info!("Switching theme to {name}");
+        config::set_color(*palette);
+        let _ = self.tx.send(Action::Render);
+    }
+
+    /// Shows `message` in an error popup, offering to perform `retry` if given.
+    fn show_error_popup(&mut self, message: String, retry: Option<Action>) {
         error!("Error: {message}");
+        let popup = match retry {
+            Some(action) => PopupMessageWidget::retry("Error".into(), message, action),
+            None => PopupMessageWidget::new("Error".into(), message),
+        };
+        self.popup = Some((popup, PopupMessageState::default()));
+        self.switch_mode(Mode::Popup);
+    }
+
+    fn show_info_popup(&mut self, info: String) {
+        info!("Info: {info}");
         self.popup = Some((
-            PopupMessageWidget::new("Error".into(), message),
+            PopupMessageWidget::new("Info".into(), info),
             PopupMessageState::default(),
         ));
         self.switch_mode(Mode::Popup);
     }
 
-    fn show_info_popup(&mut self, info: String) {
+    fn show_cancel_popup(&mut self, info: String, cancel: Action) {
         info!("Info: {info}");
         self.popup = Some((
-            PopupMessageWidget::new("Info".into(), info),
+            PopupMessageWidget::cancel("Info".into(), info, cancel),
             PopupMessageState::default(),
         ));
         self.switch_mode(Mode::Popup);
     }
 
+    /// Shows a QR code of the crates.io URL of the selected crate.
+    fn show_crate_qr_code(&mut self) {
+        let Some(url) = self.crates_io_url() else {
+            let _ = self
+                .tx
+                .send(Action::ShowErrorPopup("No crate selected".into()));
+            return;
+        };
+        let Some(qr_code) = QrCodeWidget::new(url.clone()) else {
+            let _ = self.tx.send(Action::ShowErrorPopup(format!(
+                "Unable to show QR code: {url} is too long"
+            )));
+            return;
+        };
+        self.qr_code = Some(qr_code);
+        self.switch_mode(Mode::Popup);
+    }
+
     fn close_popup(&mut self) {
         self.popup = None;
+        self.qr_code = None;
         if self.last_mode.is_popup() {
             self.switch_mode(Mode::Search);
         } else {
@@ -408,6 +1305,43 @@ impl App {
         }
     }
 
+    /// Closes the popup and performs its confirm action, if it has one.
+    fn confirm_popup(&mut self) {
+        let action = self
+            .popup
+            .as_ref()
+            .and_then(|(popup, _)| popup.confirm_action().cloned());
+        self.close_popup();
+        if let Some(action) = action {
+            let _ = self.tx.send(action);
+        }
+    }
+
+    /// Closes the popup and performs its cancel action, if it has one.
+    fn cancel_popup(&mut self) {
+        let action = self
+            .popup
+            .as_ref()
+            .and_then(|(popup, _)| popup.cancel_action().cloned());
+        self.close_popup();
+        if let Some(action) = action {
+            let _ = self.tx.send(action);
+        }
+    }
+
+    /// Closes the popup and performs its retry action, if it has one.
+    fn retry_popup(&mut self) {
+        let Some(action) = self
+            .popup
+            .as_ref()
+            .and_then(|(popup, _)| popup.retry_action().cloned())
+        else {
+            return;
+        };
+        self.close_popup();
+        let _ = self.tx.send(action);
+    }
+
     fn update_current_selection_crate_info(&mut self) {
         self.search.clear_all_previous_task_details_handles();
         self.search.request_crate_details();
@@ -419,20 +1353,49 @@ impl App {
     }
 
     fn store_total_number_of_crates(&mut self, n: u64) {
-        self.search.total_num_crates = Some(n)
+        self.search.total_num_crates = Some(n);
+        // appended pages only announce themselves through the new total
+        self.search.mark_results_dirty();
+        self.search.prefetch_next_page();
+    }
+
+    /// Opens `url` in the browser, showing an error popup if it fails.
+    fn open_in_browser(&self, url: &str) {
+        if let Err(err) = browser::open(url) {
+            let _ = self.tx.send(Action::ShowErrorPopup(format!(
+                "Unable to open URL in browser: {err}"
+            )));
+        }
+    }
+
+    /// The docs.rs URL of the selected crate.
+    fn docs_url(&self) -> Option<String> {
+        let crate_response = self.search.crate_response.lock().unwrap();
+        let name = &crate_response.as_ref()?.crate_data.name;
+        Some(format!("https://docs.rs/{name}/latest"))
+    }
+
+    /// The crates.io URL of the selected summary entry, or of the selected
+    /// crate.
+    fn crates_io_url(&self) -> Option<String> {
+        if self.mode.is_summary() {
+            return self.summary.url();
+        }
+        let crate_response = self.search.crate_response.lock().unwrap();
+        let name = &crate_response.as_ref()?.crate_data.name;
+        Some(format!("https://crates.io/crates/{name}"))
     }
 
     fn open_docs_url_in_browser(&self) -> Result<()> {
-        if let Some(crate_response) = self.search.crate_response.lock().unwrap().clone() {
-            let name = crate_response.crate_data.name;
-            webbrowser::open(&format!("https://docs.rs/{name}/latest"))?;
+        if let Some(url) = self.docs_url() {
+            self.open_in_browser(&url);
         }
         Ok(())
     }
 
     fn open_summary_url_in_browser(&self) -> Result<()> {
         if let Some(url) = self.summary.url() {
-            webbrowser::open(&url)?;
+            self.open_in_browser(&url);
         } else {
             let _ = self.tx.send(Action::ShowErrorPopup(
                 "Unable to open URL in browser: No summary data loaded".into(),
@@ -442,42 +1405,275 @@ impl App {
     }
 
     fn open_crates_io_url_in_browser(&self) -> Result<()> {
+        if let Some(url) = self.crates_io_url() {
+            self.open_in_browser(&url);
+        }
+        Ok(())
+    }
+
+    fn open_repository_url_in_browser(&self) -> Result<()> {
         if let Some(crate_response) = self.search.crate_response.lock().unwrap().clone() {
-            let name = crate_response.crate_data.name;
-            webbrowser::open(&format!("https://crates.io/crates/{name}"))?;
+            if let Some(repository) = crate_response.crate_data.repository {
+                self.open_in_browser(&repository);
+            } else {
+                let _ = self
+                    .tx
+                    .send(Action::ShowErrorPopup("No repository URL available".into()));
+            }
+        }
+        Ok(())
+    }
+
+    fn open_homepage_url_in_browser(&self) -> Result<()> {
+        if let Some(crate_response) = self.search.crate_response.lock().unwrap().clone() {
+            if let Some(homepage) = crate_response.crate_data.homepage {
+                self.open_in_browser(&homepage);
+            } else {
+                let _ = self
+                    .tx
+                    .send(Action::ShowErrorPopup("No homepage URL available".into()));
+            }
         }
         Ok(())
     }
 
     fn copy_cargo_add_command_to_clipboard(&self) -> Result<()> {
-        use copypasta::ClipboardProvider;
-        match copypasta::ClipboardContext::new() {
-            Ok(mut ctx) => {
-                if let Some(crate_response) = self.search.crate_response.lock().unwrap().clone() {
-                    let msg = format!("cargo add {}", crate_response.crate_data.name);
-                    let _ = match ctx.set_contents(msg.clone()).ok() {
-                        Some(_) => self.tx.send(Action::ShowInfoPopup(format!(
-                            "Copied to clipboard: `{msg}`"
-                        ))),
-                        None => self.tx.send(Action::ShowErrorPopup(format!(
-                            "Unable to copied to clipboard: `{msg}`"
-                        ))),
-                    };
-                } else {
-                    let _ = self
-                        .tx
-                        .send(Action::ShowErrorPopup("No selection made to copy".into()));
-                }
+        if let Some(crate_response) = self.search.crate_response.lock().unwrap().clone() {
+            let msg = format!("cargo add {}", crate_response.crate_data.name);
+            self.copy_to_clipboard(msg);
+        } else {
+            let _ = self
+                .tx
+                .send(Action::ShowErrorPopup("No selection made to copy".into()));
+        }
+        Ok(())
+    }
+
+    /// The name and feature names of the crate shown in the crate info pane.
+    fn selected_crate_features(&self) -> Option<(String, Vec<String>)> {
+        let crate_response = self.search.crate_response.lock().unwrap();
+        let crate_response = crate_response.as_ref()?;
+        Some((
+            crate_response.crate_data.name.clone(),
+            crate_info_table::feature_names(crate_response),
+        ))
+    }
+
+    fn toggle_feature(&mut self) {
+        if let Some((name, features)) = self.selected_crate_features() {
+            self.search.crate_info.toggle_feature(&name, &features);
+        }
+    }
+
+    /// Copies `cargo add` for the selected crate with the picked features.
+    fn copy_cargo_add_with_features_to_clipboard(&mut self) {
+        let Some((name, _)) = self.selected_crate_features() else {
+            let _ = self
+                .tx
+                .send(Action::ShowErrorPopup("No selection made to copy".into()));
+            return;
+        };
+        let features = self.search.crate_info.selected_features(&name);
+        let msg = if features.is_empty() {
+            format!("cargo add {name}")
+        } else {
+            format!("cargo add {name} --features {}", features.join(","))
+        };
+        self.copy_to_clipboard(msg);
+    }
+
+    /// Runs `cargo add` for the selected crate in the current directory.
+    fn add_to_local_cargo_toml(&self) {
+        let Some(crate_response) = self.search.crate_response.lock().unwrap().clone() else {
+            let _ = self
+                .tx
+                .send(Action::ShowErrorPopup("No selection made to add".into()));
+            return;
+        };
+        let directory = match std::env::current_dir() {
+            Ok(directory) if directory.join("Cargo.toml").is_file() => directory,
+            _ => {
+                let _ = self.tx.send(Action::ShowErrorPopup(
+                    "No Cargo.toml found in the current directory".into(),
+                ));
+                return;
             }
-            Err(err) => {
-                let _ = self.tx.send(Action::ShowErrorPopup(format!(
-                    "Unable to create ClipboardContext: {err}"
-                )));
+        };
+        let name = crate_response.crate_data.name;
+        let tx = self.tx.clone();
+        tokio::spawn(async move {
+            let output = tokio::process::Command::new("cargo")
+                .arg("add")
+                .arg(&name)
+                .current_dir(directory)
+                .stdin(std::process::Stdio::null())
+                .output()
+                .await;
+            let error = match output {
+                Ok(output) if output.status.success() => None,
+                Ok(output) => Some(String::from_utf8_lossy(&output.stderr).trim().to_string()),
+                Err(err) => Some(format!("Unable to run cargo: {err}")),
+            };
+            let _ = tx.send(Action::ShowCargoAddCompletionMessage { name, error });
+        });
+    }
+
+    fn show_cargo_add_completion_message(&mut self, name: String, error: Option<String>) {
+        match error {
+            Some(error) => self.show_error_popup(
+                format!("Unable to add {name} to Cargo.toml:\n{error}"),
+                None,
+            ),
+            None => {
+                self.show_info_popup(format!("Added {name} to Cargo.toml"));
+                if config::get().detect_local_deps {
+                    self.local_deps.insert(name);
+                }
             }
         }
+    }
+
+    fn copy_toml_dependency_to_clipboard(&self) -> Result<()> {
+        if let Some(crate_response) = self.search.crate_response.lock().unwrap().clone() {
+            let crate_data = crate_response.crate_data;
+            let version = crate_data
+                .max_stable_version
+                .unwrap_or(crate_data.max_version);
+            let msg = format!("{} = \"{version}\"", crate_data.name);
+            self.copy_to_clipboard(msg);
+        } else {
+            let _ = self
+                .tx
+                .send(Action::ShowErrorPopup("No selection made to copy".into()));
+        }
         Ok(())
     }
 
+    /// Compares the two marked crates, or the marked crate with the selected
+    /// one.
+    fn compare_crates(&mut self) {
+        let mut names = self
+            .search
+            .results
+            .marked()
+            .iter()
+            .cloned()
+            .collect::<Vec<_>>();
+        if names.len() == 1
+            && let Some(selected) = self.search.results.selected_crate_name()
+            && selected != names[0]
+        {
+            names.push(selected);
+        }
+        let marked = names.len();
+        let Ok([first, second]) = <[String; 2]>::try_from(names) else {
+            let message = if marked > 2 {
+                format!("{marked} crates are marked; mark exactly two to compare them")
+            } else {
+                "Mark two crates, or one crate and select another, to compare them".into()
+            };
+            let _ = self.tx.send(Action::ShowErrorPopup(message));
+            return;
+        };
+        self.compare.request(first, second);
+        self.switch_mode(Mode::Compare);
+    }
+
+    /// Copies a single `cargo add` command adding every marked crate.
+    fn copy_marked_cargo_add_to_clipboard(&self) {
+        let marked = self.search.results.marked();
+        if marked.is_empty() {
+            let _ = self
+                .tx
+                .send(Action::ShowErrorPopup("No crates marked to copy".into()));
+            return;
+        }
+        self.copy_to_clipboard(format!(
+            "cargo add {}",
+            marked.iter().cloned().collect::<Vec<_>>().join(" ")
+        ));
+    }
+
+    fn copy_url_to_clipboard(&self, url: Option<String>) {
+        if let Some(url) = url {
+            self.copy_to_clipboard(url);
+        } else {
+            let _ = self
+                .tx
+                .send(Action::ShowErrorPopup("No URL available to copy".into()));
+        }
+    }
+
+    fn copy_to_clipboard(&self, msg: String) {
+        let _ = match clipboard::copy(&msg) {
+            Ok(()) => self.tx.send(Action::ShowInfoPopup(format!(
+                "Copied to clipboard: `{msg}`"
+            ))),
+            Err(err) => self.tx.send(Action::ShowErrorPopup(err.to_string())),
+        };
+    }
+
+    /// Writes the details of the selected crate to a Markdown file, fetching
+    /// them first if they haven't been loaded.
+    fn export_crate_markdown(&self) {
+        let Some(name) = self.search.results.selected_crate_name() else {
+            let _ = self
+                .tx
+                .send(Action::ShowErrorPopup("No crate selected to export".into()));
+            return;
+        };
+        self.export_named_crate_markdown(name);
+    }
+
+    /// Exports `name`, fetching its details first unless they are the ones
+    /// already loaded.
+    fn export_named_crate_markdown(&self, name: String) {
+        let full_crate_info = self.search.full_crate_info.lock().unwrap().clone();
+        if let Some(krate) = full_crate_info.filter(|krate| krate.name == name) {
+            let _ = self.tx.send(export_crate_markdown(&krate));
+            return;
+        }
+        let tx = self.tx.clone();
+        let loading_status = self.loading_status.clone();
+        tokio::spawn(async move {
+            loading_status.store(true, Ordering::SeqCst);
+            let result = crates_io_api_helper::request_full_crate(&name).await;
+            loading_status.store(false, Ordering::SeqCst);
+            match result {
+                Ok(krate) => {
+                    let _ = tx.send(export_crate_markdown(&krate));
+                }
+                Err(err) => crates_io_api_helper::report_error(
+                    &tx,
+                    &format!("loading details for `{name}`"),
+                    &err,
+                    Some(Action::ExportNamedCrateMarkdown(name.clone())),
+                ),
+            }
+        });
+    }
+
+    fn export_results(&self) {
+        // export the filtered view the user is looking at, not the full page
+        let crates = &self.search.results.crates;
+        if crates.is_empty() {
+            let _ = self
+                .tx
+                .send(Action::ShowErrorPopup("No results to export".into()));
+            return;
+        }
+        let _ = match export::export_crates(crates, config::get().export_format) {
+            Ok(path) => self.tx.send(Action::ShowInfoPopup(format!(
+                "Exported {} crates to {}",
+                crates.len(),
+                path.display()
+            ))),
+            Err(err) => self.tx.send(Action::ShowErrorPopup(format!(
+                "Unable to export results: {err}"
+            ))),
+        };
+    }
+
     // Sets the frame count
     fn update_frame_count(&mut self, frame: &mut Frame<'_>) {
         self.frame_count = frame.count();
@@ -485,6 +1681,11 @@ impl App {
 
     // Sets cursor for the prompt
     fn update_cursor(&mut self, frame: &mut Frame<'_>) {
+        if self.mode.is_command_palette()
+            && let Some(cursor_position) = self.command_palette.cursor_position()
+        {
+            frame.set_cursor_position(cursor_position);
+        }
         if self.mode.is_prompt()
             && let Some(cursor_position) = self.search.cursor_position()
         {
@@ -512,11 +1713,39 @@ impl App {
         )
     }
 
+    /// Whether the data of the visible mode is loading.
     fn loading(&self) -> bool {
-        self.loading_status.load(Ordering::SeqCst)
+        if self.visible_mode().is_summary() {
+            self.summary_loading_status.load(Ordering::SeqCst)
+        } else {
+            self.loading_status.load(Ordering::SeqCst)
+        }
+    }
+
+    /// The mode whose page is shown, i.e. the mode below an overlay.
+    fn visible_mode(&self) -> Mode {
+        if matches!(self.mode, Mode::Popup | Mode::Quit | Mode::CommandPalette) {
+            self.last_mode
+        } else {
+            self.mode
+        }
+    }
+}
+
+/// Writes `krate` to a Markdown file, returning the popup that tells how it
+/// went.
+fn export_crate_markdown(krate: &crates_io_api::FullCrate) -> Action {
+    let name = &krate.name;
+    match export::export_crate_markdown(krate) {
+        Ok(path) => Action::ShowInfoPopup(format!("Exported {name} to {}", path.display())),
+        Err(err) => Action::ShowErrorPopup(format!("Unable to export {name}: {err}")),
     }
 }
 
+/// The smallest terminal the layout can be drawn in without overlapping.
+const MIN_WIDTH: u16 = 40;
+const MIN_HEIGHT: u16 = 12;
+
 impl StatefulWidget for AppWidget {
     type State = App;
 
@@ -526,38 +1755,79 @@ impl StatefulWidget for AppWidget {
             .bg(config::get().color.base00)
             .render(area, buf);
 
+        if area.width < MIN_WIDTH || area.height < MIN_HEIGHT {
+            let [center] = Layout::vertical([Constraint::Length(2)])
+                .flex(Flex::Center)
+                .areas(area);
+            Paragraph::new(format!(
+                "Terminal too small (need {MIN_WIDTH}x{MIN_HEIGHT})"
+            ))
+            .fg(config::get().color.base08)
+            .centered()
+            .wrap(Wrap { trim: true })
+            .render(center, buf);
+            return;
+        }
+
         use Constraint::*;
-        let [header, main] = Layout::vertical([Length(1), Fill(1)]).areas(area);
+        let footer_height = if state.show_footer_hints { 1 } else { 0 };
+        let [header, main, footer] =
+            Layout::vertical([Length(1), Fill(1), Length(footer_height)]).areas(area);
         let [tabs, events] = Layout::horizontal([Min(15), Fill(1)]).areas(header);
 
         state.render_tabs(tabs, buf);
         state.events_widget().render(events, buf);
 
-        let mode = if matches!(state.mode, Mode::Popup | Mode::Quit) {
-            state.last_mode
-        } else {
-            state.mode
-        };
+        let mode = state.visible_mode();
         match mode {
             Mode::Summary => state.render_summary(main, buf),
             Mode::Help => state.render_help(main, buf),
 
             Mode::Search => state.render_search(main, buf),
             Mode::Filter => state.render_search(main, buf),
+            Mode::Bookmarks => state.render_bookmarks(main, buf),
+            Mode::Categories => state.render_categories(main, buf),
+            Mode::Trending => state.render_trending(main, buf),
+            Mode::Compare => state.render_compare(main, buf),
+            Mode::Lockfile => state.render_lockfile(main, buf),
             Mode::PickerShowCrateInfo => state.render_search(main, buf),
+            Mode::FeatureSelect => state.render_search(main, buf),
             Mode::PickerHideCrateInfo => state.render_search(main, buf),
 
             Mode::Common => {}
+            Mode::CommandPalette => {}
             Mode::Popup => {}
             Mode::Quit => {}
         };
 
         if state.loading() {
-            Line::from(state.spinner())
-                .right_aligned()
-                .render(main, buf);
+            let progress = match mode {
+                Mode::Search
+                | Mode::Filter
+                | Mode::PickerShowCrateInfo
+                | Mode::PickerHideCrateInfo => state.search.progress(),
+                _ => None,
+            };
+            if let Some((position, total)) = progress {
+                state.render_progress(main, buf, position, total);
+            } else {
+                Line::from(state.spinner())
+                    .right_aligned()
+                    .render(main, buf);
+            }
         }
 
+        FooterHintsWidget::new(state.mode).render(footer, buf);
+        if let Some(which_key) = state.which_key_widget() {
+            which_key.render(main, buf);
+        }
+        if state.mode.is_command_palette() {
+            CommandPaletteWidget.render(main, buf, &mut state.command_palette);
+        }
+
+        if let Some(qr_code) = &state.qr_code {
+            qr_code.render(area, buf);
+        }
         if let Some((popup, popup_state)) = &mut state.popup {
             popup.render(area, buf, popup_state);
         }
@@ -567,10 +1837,16 @@ impl StatefulWidget for AppWidget {
 impl App {
     fn render_tabs(&self, area: Rect, buf: &mut Buffer) {
         use strum::IntoEnumIterator;
-        let titles = SelectedTab::iter().map(|tab| tab.title());
+        let tabs = SelectedTab::iter()
+            .filter(|tab| !(self.summary_hidden && *tab == SelectedTab::Summary))
+            .collect::<Vec<_>>();
+        let titles = tabs.iter().map(SelectedTab::title);
         let highlight_style = SelectedTab::highlight_style();
 
-        let selected_tab_index = self.selected_tab as usize;
+        let selected_tab_index = tabs
+            .iter()
+            .position(|tab| *tab == self.selected_tab)
+            .unwrap_or_default();
         Tabs::new(titles)
             .highlight_style(highlight_style)
             .select(selected_tab_index)
@@ -595,7 +1871,7 @@ impl App {
 
     fn render_search(&mut self, area: Rect, buf: &mut Buffer) {
         let prompt_height = if self.mode.is_prompt() && self.search.is_prompt() {
-            5
+            self.prompt_widget().height()
         } else {
             0
         };
@@ -606,35 +1882,128 @@ impl App {
         ])
         .areas(area);
 
-        SearchPageWidget.render(main, buf, &mut self.search);
+        SearchPageWidget::new(&self.bookmarks, &self.local_deps).render(
+            main,
+            buf,
+            &mut self.search,
+        );
 
         self.render_prompt(prompt, buf);
         self.render_status_bar(status_bar, buf);
     }
 
-    fn render_prompt(&mut self, area: Rect, buf: &mut Buffer) {
-        let p = SearchFilterPromptWidget::new(
+    fn render_bookmarks(&mut self, area: Rect, buf: &mut Buffer) {
+        let [main, status_bar] =
+            Layout::vertical([Constraint::Fill(0), Constraint::Length(1)]).areas(area);
+        BookmarksPageWidget::new(&self.bookmarks).render(main, buf, &mut self.bookmarks_page);
+        self.render_status_bar(status_bar, buf);
+    }
+
+    fn render_categories(&mut self, area: Rect, buf: &mut Buffer) {
+        let [main, status_bar] =
+            Layout::vertical([Constraint::Fill(0), Constraint::Length(1)]).areas(area);
+        CategoriesWidget.render(main, buf, &mut self.categories);
+        self.render_status_bar(status_bar, buf);
+    }
+
+    fn render_trending(&mut self, area: Rect, buf: &mut Buffer) {
+        let [main, status_bar] =
+            Layout::vertical([Constraint::Fill(0), Constraint::Length(1)]).areas(area);
+        TrendingWidget::new(&self.bookmarks).render(main, buf, &mut self.trending);
+        self.render_status_bar(status_bar, buf);
+    }
+
+    fn render_compare(&mut self, area: Rect, buf: &mut Buffer) {
+        let [main, status_bar] =
+            Layout::vertical([Constraint::Fill(0), Constraint::Length(1)]).areas(area);
+        CompareWidget.render(main, buf, &mut self.compare);
+        self.render_status_bar(status_bar, buf);
+    }
+
+    fn render_lockfile(&mut self, area: Rect, buf: &mut Buffer) {
+        let [main, status_bar] =
+            Layout::vertical([Constraint::Fill(0), Constraint::Length(1)]).areas(area);
+        LockfileWidget.render(main, buf, &mut self.lockfile);
+        self.render_status_bar(status_bar, buf);
+    }
+
+    fn prompt_widget(&self) -> SearchFilterPromptWidget<'_> {
+        SearchFilterPromptWidget::new(
             self.mode,
             self.search.sort.clone(),
             &self.search.input,
             self.search.search_mode,
-        );
-        p.render(area, buf, &mut self.search.prompt);
+        )
+        .sort_ascending(self.search.sort_ascending)
+    }
+
+    fn render_prompt(&mut self, area: Rect, buf: &mut Buffer) {
+        let mut prompt = self.search.prompt;
+        self.prompt_widget().render(area, buf, &mut prompt);
+        self.search.prompt = prompt;
     }
 
     fn render_status_bar(&mut self, area: Rect, buf: &mut Buffer) {
         let s = StatusBarWidget::new(
             self.mode,
             self.search.sort.clone(),
-            self.search.input.value().to_string(),
+            if self.mode.is_help() {
+                self.help.filter_status()
+            } else if self.mode.is_summary() {
+                self.summary.age_description()
+            } else {
+                self.search.query_description()
+            },
+        )
+        .min_downloads(self.search.min_downloads)
+        .quick_filters(self.search.quick_filters())
+        .sort_ascending(self.search.sort_ascending)
+        .type_ahead(self.type_ahead.clone())
+        .request_stats(
+            config::get()
+                .show_request_stats
+                .then(crates_io_api_helper::request_stats),
         );
         s.render(area, buf);
     }
 
-    fn spinner(&self) -> String {
-        let spinner = ['⠋', '⠙', '⠹', '⠸', '⠼', '⠴', '⠦', '⠧', '⠇', '⠏'];
-        let index = self.frame_count % spinner.len();
-        let symbol = spinner[index];
-        symbol.into()
+    /// Renders how far through the result set the current page is, in place of
+    /// the spinner.
+    fn render_progress(&self, area: Rect, buf: &mut Buffer, position: u64, total: u64) {
+        let [_, area] =
+            Layout::horizontal([Constraint::Fill(1), Constraint::Length(30)]).areas(area);
+        LineGauge::default()
+            .ratio(position as f64 / total as f64)
+            .label(format!("{position}/{total}"))
+            .filled_style(config::get().color.base0d)
+            .unfilled_style(config::get().color.base03)
+            .render(Rect { height: 1, ..area }, buf);
+    }
+
+    fn spinner(&self) -> &'static str {
+        config::get().spinner_style.frame(self.frame_count)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_state_changes_need_a_redraw() {
+        config::init_for_tests();
+        let mut app = App::new(None, None);
+        app.needs_redraw = false;
+        app.handle_action(Action::Tick).unwrap();
+        app.handle_action(Action::KeyRefresh).unwrap();
+        assert!(!app.needs_redraw);
+
+        app.type_ahead.push('s');
+        app.handle_action(Action::KeyRefresh).unwrap();
+        assert!(app.needs_redraw);
+
+        app.needs_redraw = false;
+        app.handle_action(Action::ToggleFooterHints).unwrap();
+        assert!(app.needs_redraw);
     }
 }
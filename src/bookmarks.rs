@@ -0,0 +1,69 @@
+use std::{collections::BTreeSet, path::PathBuf};
+
+use color_eyre::eyre::Result;
+use serde::{Deserialize, Serialize};
+use tracing::warn;
+
+use crate::config;
+
+/// A set of crate names the user has starred, persisted across restarts.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Bookmarks {
+    crates: BTreeSet<String>,
+}
+
+impl Bookmarks {
+    /// Loads bookmarks from the data directory.
+    ///
+    /// A missing or malformed bookmarks file results in an empty set rather
+    /// than an error so that a bad file never prevents the app from starting.
+    pub fn load() -> Self {
+        let path = bookmarks_file();
+        let Ok(contents) = std::fs::read_to_string(&path) else {
+            return Self::default();
+        };
+        toml::from_str(&contents).unwrap_or_else(|err| {
+            warn!("Unable to parse bookmarks file {}: {err}", path.display());
+            Self::default()
+        })
+    }
+
+    /// Writes bookmarks to the data directory.
+    pub fn save(&self) -> Result<()> {
+        let path = bookmarks_file();
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(path, toml::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    /// Adds the crate if it isn't bookmarked, removes it otherwise.
+    ///
+    /// Returns `true` if the crate is bookmarked after toggling.
+    pub fn toggle(&mut self, name: &str) -> bool {
+        if self.crates.remove(name) {
+            false
+        } else {
+            self.crates.insert(name.to_string());
+            true
+        }
+    }
+
+    pub fn contains(&self, name: &str) -> bool {
+        self.crates.contains(name)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.crates.is_empty()
+    }
+
+    pub fn names(&self) -> Vec<String> {
+        self.crates.iter().cloned().collect()
+    }
+}
+
+/// Returns the path to the bookmarks file.
+fn bookmarks_file() -> PathBuf {
+    config::get().data_dir.join("bookmarks.toml")
+}
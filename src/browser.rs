@@ -0,0 +1,82 @@
+use std::process::{Command, Stdio};
+
+use color_eyre::eyre::{Result, eyre};
+
+use crate::config;
+
+/// Opens `url` with the configured `browser_command`, or the system's default
+/// browser when none is set.
+pub fn open(url: &str) -> Result<()> {
+    match &config::get().browser_command {
+        Some(command) => spawn(command, url),
+        None => Ok(webbrowser::open(url)?),
+    }
+}
+
+/// Spawns `command`, replacing `{url}` in its arguments with `url`, or
+/// appending `url` when the command doesn't mention it.
+fn spawn(command: &str, url: &str) -> Result<()> {
+    let mut args = command_args(command, url)
+        .ok_or_else(|| eyre!("browser_command `{command}` has unbalanced quotes"))?;
+    if args.is_empty() {
+        return Err(eyre!("browser_command is empty"));
+    }
+    let program = args.remove(0);
+    // detach the browser's output so that it doesn't draw over the TUI
+    Command::new(&program)
+        .args(args)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .map_err(|err| eyre!("Unable to run browser command `{program}`: {err}"))?;
+    Ok(())
+}
+
+/// Splits `command` into arguments the way a POSIX shell would, so that
+/// quoted paths with spaces stay whole. Returns `None` for unbalanced quotes.
+fn command_args(command: &str, url: &str) -> Option<Vec<String>> {
+    let mut args = shlex::split(command)?
+        .into_iter()
+        .map(|arg| arg.replace("{url}", url))
+        .collect::<Vec<_>>();
+    if !command.contains("{url}") {
+        args.push(url.to_string());
+    }
+    Some(args)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn substitutes_or_appends_the_url() {
+        let url = "https://crates.io/crates/ratatui";
+        assert_eq!(
+            command_args("wslview {url}", url).unwrap(),
+            ["wslview", url]
+        );
+        assert_eq!(
+            command_args("firefox --new-tab", url).unwrap(),
+            ["firefox", "--new-tab", url]
+        );
+    }
+
+    #[test]
+    fn keeps_quoted_arguments_whole() {
+        let url = "https://crates.io/crates/ratatui";
+        assert_eq!(
+            command_args(
+                r#""/Applications/Google Chrome.app/chrome" --app='{url}'"#,
+                url
+            )
+            .unwrap(),
+            [
+                "/Applications/Google Chrome.app/chrome",
+                &format!("--app={url}")
+            ]
+        );
+        assert_eq!(command_args("firefox 'unbalanced", url), None);
+    }
+}
@@ -0,0 +1,119 @@
+use std::{collections::VecDeque, path::PathBuf};
+
+use chrono::{DateTime, Utc};
+use color_eyre::eyre::Result;
+use serde::{Deserialize, Serialize};
+use tracing::warn;
+
+use crate::config;
+
+/// The crates.io summary along with when it was fetched, persisted so that
+/// it doesn't have to be fetched on every start.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CachedSummary {
+    pub fetched_at: DateTime<Utc>,
+    pub summary: crates_io_api::Summary,
+}
+
+impl CachedSummary {
+    pub fn new(summary: crates_io_api::Summary) -> Self {
+        Self {
+            fetched_at: Utc::now(),
+            summary,
+        }
+    }
+
+    /// Loads the cached summary from the data directory.
+    ///
+    /// A missing or malformed cache file results in `None` so that the
+    /// summary is fetched again.
+    pub fn load() -> Option<Self> {
+        let path = summary_cache_file();
+        let contents = std::fs::read_to_string(&path).ok()?;
+        serde_json::from_str(&contents)
+            .inspect_err(|err| warn!("Unable to parse summary cache {}: {err}", path.display()))
+            .ok()
+    }
+
+    /// Writes the summary to the data directory.
+    pub fn save(&self) -> Result<()> {
+        let path = summary_cache_file();
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(path, serde_json::to_string(self)?)?;
+        Ok(())
+    }
+
+    /// Whether the summary was fetched less than `summary_cache_ttl_secs` ago.
+    pub fn is_fresh(&self) -> bool {
+        let ttl = chrono::Duration::seconds(config::get().summary_cache_ttl_secs as i64);
+        Utc::now() - self.fetched_at < ttl
+    }
+}
+
+/// An in-memory cache keyed by name that evicts the least recently used
+/// entry once it holds `capacity` entries.
+#[derive(Debug)]
+pub struct LruCache<V> {
+    capacity: usize,
+    /// Ordered from least to most recently used.
+    entries: VecDeque<(String, V)>,
+}
+
+impl<V> LruCache<V> {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            entries: VecDeque::with_capacity(capacity),
+        }
+    }
+
+    /// Returns the entry for `key`, marking it as the most recently used.
+    pub fn get(&mut self, key: &str) -> Option<&V> {
+        let index = self.entries.iter().position(|(k, _)| k == key)?;
+        let entry = self.entries.remove(index)?;
+        self.entries.push_back(entry);
+        self.entries.back().map(|(_, value)| value)
+    }
+
+    pub fn insert(&mut self, key: String, value: V) {
+        self.entries.retain(|(k, _)| *k != key);
+        if self.entries.len() >= self.capacity {
+            self.entries.pop_front();
+        }
+        self.entries.push_back((key, value));
+    }
+
+    pub fn remove(&mut self, key: &str) {
+        self.entries.retain(|(k, _)| k != key);
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+}
+
+/// Returns the path to the summary cache file.
+fn summary_cache_file() -> PathBuf {
+    config::get().data_dir.join("cache").join("summary.json")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lru_cache_evicts_the_least_recently_used_entry() {
+        let mut cache = LruCache::new(2);
+        cache.insert("serde".into(), 1);
+        cache.insert("tokio".into(), 2);
+        assert_eq!(cache.get("serde"), Some(&1));
+        cache.insert("ratatui".into(), 3);
+        assert_eq!(cache.get("tokio"), None);
+        assert_eq!(cache.get("serde"), Some(&1));
+        assert_eq!(cache.get("ratatui"), Some(&3));
+        cache.remove("serde");
+        assert_eq!(cache.get("serde"), None);
+    }
+}
@@ -1,13 +1,16 @@
 use std::path::PathBuf;
 
 use clap::{
-    Parser,
+    Parser, ValueEnum,
     builder::{Styles, styling::AnsiColor},
 };
-use serde::Serialize;
+use ratatui::style::Color;
+use serde::{Deserialize, Serialize};
 use serde_with::{NoneAsEmptyString, serde_as, skip_serializing_none};
 use tracing::level_filters::LevelFilter;
 
+use crate::export::ExportFormat;
+
 pub fn version() -> String {
     let git_describe = if env!("VERGEN_GIT_DESCRIBE") != "VERGEN_IDEMPOTENT_OUTPUT" {
         format!("-{}", env!("VERGEN_GIT_DESCRIBE"))
@@ -42,7 +45,7 @@ const HELP_STYLES: Styles = Styles::styled()
 /// configuration.
 #[serde_as]
 #[skip_serializing_none]
-#[derive(Debug, Default, Parser, Serialize)]
+#[derive(Debug, Default, Clone, Parser, Serialize)]
 #[command(author, version = version(), about, long_about = None, styles = HELP_STYLES)]
 pub struct Cli {
     /// Initial Query
@@ -53,6 +56,27 @@ pub struct Cli {
     #[arg(long)]
     pub print_default_config: bool,
 
+    /// Print the first page of results for QUERY as JSON instead of starting
+    /// the TUI
+    #[arg(long, conflicts_with = "format")]
+    #[serde(skip)]
+    pub json: bool,
+
+    /// Print the first page of results for QUERY in this format instead of
+    /// starting the TUI
+    #[arg(long, value_name = "FORMAT")]
+    #[serde(skip)]
+    pub format: Option<ExportFormat>,
+
+    /// The number of crates requested per page of search results, at most 100
+    #[arg(long, value_name = "N")]
+    pub page_size: Option<u64>,
+
+    /// The order to list search results in
+    #[arg(long, value_name = "SORT")]
+    #[serde(skip)]
+    pub sort: Option<SortBy>,
+
     /// A path to a crates-tui configuration file.
     #[arg(
         short,
@@ -66,10 +90,36 @@ pub struct Cli {
     #[arg(long, value_name = "FILE", default_value = get_default_color_file())]
     pub color_file: Option<PathBuf>,
 
+    /// A hex color, e.g. `#ff8800`, used for highlights and titles instead of
+    /// the palette's
+    #[arg(long, value_name = "HEX", value_parser = parse_hex_color)]
+    #[serde(skip)]
+    pub accent: Option<Color>,
+
+    /// A built-in color theme, e.g. `rose-pine-dawn`, that the color file is
+    /// applied over
+    #[arg(long, value_name = "NAME")]
+    pub theme: Option<String>,
+
     /// Frame rate, i.e. number of frames per second
-    #[arg(short, long, value_name = "FLOAT", default_value_t = 15.0)]
+    #[arg(short, long, value_name = "FLOAT", default_value_t = 15.0, value_parser = parse_rate)]
     pub frame_rate: f64,
 
+    /// Tick rate, i.e. number of ticks per second
+    #[arg(long, value_name = "FLOAT", value_parser = parse_rate)]
+    pub tick_rate: Option<f64>,
+
+    /// The number of times per second a pending key sequence is reset
+    #[arg(long, value_name = "FLOAT", value_parser = parse_rate)]
+    pub key_refresh_rate: Option<f64>,
+
+    /// Run on a single thread instead of one per CPU core. The app mostly
+    /// waits on network requests, so this saves memory and threads without
+    /// slowing it down noticeably.
+    #[arg(long)]
+    #[serde(skip)]
+    pub single_thread: bool,
+
     /// The directory to use for storing application data.
     #[arg(long, value_name = "DIR", default_value = get_default_data_dir())]
     pub data_dir: Option<PathBuf>,
@@ -82,6 +132,51 @@ pub struct Cli {
     pub log_level: Option<LevelFilter>,
 }
 
+/// The sort orders supported by crates.io, in a form clap can parse.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SortBy {
+    Relevance,
+    Downloads,
+    RecentDownloads,
+    RecentUpdates,
+    NewlyAdded,
+    Alphabetical,
+}
+
+impl From<SortBy> for crates_io_api::Sort {
+    fn from(sort: SortBy) -> Self {
+        match sort {
+            SortBy::Relevance => Self::Relevance,
+            SortBy::Downloads => Self::Downloads,
+            SortBy::RecentDownloads => Self::RecentDownloads,
+            SortBy::RecentUpdates => Self::RecentUpdates,
+            SortBy::NewlyAdded => Self::NewlyAdded,
+            SortBy::Alphabetical => Self::Alphabetical,
+        }
+    }
+}
+
+impl From<&crates_io_api::Sort> for SortBy {
+    fn from(sort: &crates_io_api::Sort) -> Self {
+        match sort {
+            crates_io_api::Sort::Relevance => Self::Relevance,
+            crates_io_api::Sort::Downloads => Self::Downloads,
+            crates_io_api::Sort::RecentDownloads => Self::RecentDownloads,
+            crates_io_api::Sort::RecentUpdates => Self::RecentUpdates,
+            crates_io_api::Sort::NewlyAdded => Self::NewlyAdded,
+            crates_io_api::Sort::Alphabetical => Self::Alphabetical,
+        }
+    }
+}
+
+impl Cli {
+    /// Returns the format to print results in when running non-interactively.
+    pub fn output_format(&self) -> Option<ExportFormat> {
+        self.format.or(self.json.then_some(ExportFormat::Json))
+    }
+}
+
 fn get_default_config_path() -> String {
     crate::config::default_config_file()
         .to_string_lossy()
@@ -100,6 +195,55 @@ fn get_default_data_dir() -> String {
         .into_owned()
 }
 
+/// Parses a color written as `#rrggbb` or `rrggbb`.
+fn parse_hex_color(value: &str) -> Result<Color, String> {
+    let hex = value.strip_prefix('#').unwrap_or(value);
+    match u32::from_str_radix(hex, 16) {
+        Ok(rgb) if hex.len() == 6 && hex.chars().all(|c| c.is_ascii_hexdigit()) => {
+            Ok(Color::from_u32(rgb))
+        }
+        _ => Err(format!("`{value}` is not a hex color like #ff8800")),
+    }
+}
+
+/// Rates above this many times per second are clamped to it.
+pub const MAX_RATE: f64 = 120.0;
+
+/// Parses a rate per second, rejecting rates that would make the interval
+/// between events infinite.
+fn parse_rate(value: &str) -> Result<f64, String> {
+    let rate = value
+        .parse::<f64>()
+        .map_err(|err| format!("`{value}` is not a number: {err}"))?;
+    if !rate.is_finite() || rate <= 0.0 {
+        return Err(format!("`{value}` must be greater than 0"));
+    }
+    Ok(rate.min(MAX_RATE))
+}
+
 pub fn parse() -> Cli {
     Cli::parse()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rates_must_be_positive() {
+        assert!(Cli::try_parse_from(["crates-tui", "--tick-rate", "0"]).is_err());
+        assert!(Cli::try_parse_from(["crates-tui", "--key-refresh-rate", "-1"]).is_err());
+        let cli = Cli::try_parse_from(["crates-tui", "--tick-rate", "1000"]).unwrap();
+        assert_eq!(cli.tick_rate, Some(MAX_RATE));
+    }
+
+    #[test]
+    fn accent_must_be_a_hex_color() {
+        let cli = Cli::try_parse_from(["crates-tui", "--accent", "#ff8800"]).unwrap();
+        assert_eq!(cli.accent, Some(Color::Rgb(0xff, 0x88, 0x00)));
+        let cli = Cli::try_parse_from(["crates-tui", "--accent", "00aaff"]).unwrap();
+        assert_eq!(cli.accent, Some(Color::Rgb(0x00, 0xaa, 0xff)));
+        assert!(Cli::try_parse_from(["crates-tui", "--accent", "orange"]).is_err());
+        assert!(Cli::try_parse_from(["crates-tui", "--accent", "#f80"]).is_err());
+    }
+}
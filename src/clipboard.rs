@@ -0,0 +1,50 @@
+use std::io::stdout;
+
+use color_eyre::eyre::{Result, eyre};
+use crossterm::clipboard::CopyToClipboard;
+use serde::{Deserialize, Serialize};
+use strum::Display;
+use tracing::warn;
+
+use crate::config;
+
+/// How text is copied to the clipboard.
+#[derive(Debug, Default, Display, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum ClipboardBackend {
+    /// Use the system clipboard provider.
+    System,
+    /// Ask the terminal to set the clipboard with an OSC 52 escape sequence,
+    /// which also works over SSH.
+    Osc52,
+    /// Use the system clipboard, falling back to OSC 52 when no provider is
+    /// available.
+    #[default]
+    Auto,
+}
+
+/// Copies `text` to the clipboard using the configured backend.
+pub fn copy(text: &str) -> Result<()> {
+    match config::get().clipboard_backend {
+        ClipboardBackend::System => copy_system(text),
+        ClipboardBackend::Osc52 => copy_osc52(text),
+        ClipboardBackend::Auto => copy_system(text).or_else(|err| {
+            warn!("{err}, falling back to OSC 52");
+            copy_osc52(text)
+        }),
+    }
+}
+
+fn copy_system(text: &str) -> Result<()> {
+    use copypasta::ClipboardProvider;
+    let mut ctx = copypasta::ClipboardContext::new()
+        .map_err(|err| eyre!("Unable to create ClipboardContext: {err}"))?;
+    ctx.set_contents(text.to_string())
+        .map_err(|err| eyre!("Unable to copy to clipboard: {err}"))
+}
+
+fn copy_osc52(text: &str) -> Result<()> {
+    crossterm::execute!(stdout(), CopyToClipboard::to_clipboard_from(text))?;
+    Ok(())
+}
@@ -6,40 +6,111 @@ use crate::app::Mode;
 #[derive(Debug, Display, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum Command {
     Quit,
+    Suspend,
     NextTab,
     PreviousTab,
+    NextTheme,
+    PreviousTheme,
+    ToggleFooterHints,
     ClosePopup,
+    CancelPopup,
+    ConfirmPopup,
+    RetryPopup,
     SwitchMode(Mode),
     SwitchToLastMode,
+    NavigateBack,
+    NavigateForward,
+    StartHelpFilter,
     IncrementPage,
     DecrementPage,
     NextSummaryMode,
     PreviousSummaryMode,
+    SummaryMoveLeft,
+    SummaryMoveRight,
+    SummaryMoveUp,
+    SummaryMoveDown,
+    SummarySelect,
+    RefreshSummary,
     ToggleSortBy { reload: bool, forward: bool },
+    ToggleSortDirection,
     ScrollBottom,
     ScrollTop,
     ScrollDown,
     ScrollUp,
+    ScrollHalfPageDown,
+    ScrollHalfPageUp,
+    ScrollPageDown,
+    ScrollPageUp,
     ScrollCrateInfoDown,
     ScrollCrateInfoUp,
     ScrollSearchResultsDown,
     ScrollSearchResultsUp,
     SubmitSearch,
     ReloadData,
+    RefreshCrateDetails,
+    RefreshTrending,
     ToggleShowCrateInfo,
+    ToggleBookmark,
+    SearchCategory,
+    RefreshCategories,
+    SearchByOwner,
+    SetMinDownloads,
+    ToggleOnlyWithRepo,
+    FilterByLicense,
     CopyCargoAddCommandToClipboard,
+    AddToLocalCargoToml,
+    CopyTomlDependencyToClipboard,
+    ToggleMark,
+    CopyMarkedCargoAdd,
+    CompareCrates,
+    RefreshComparison,
     OpenDocsUrlInBrowser,
     OpenCratesIOUrlInBrowser,
+    OpenRepositoryUrlInBrowser,
+    OpenHomepageUrlInBrowser,
+    CopyCratesIoUrlToClipboard,
+    CopyDocsUrlToClipboard,
+    ShowCrateQrCode,
+    ToggleFeature,
+    CopyCargoAddWithFeatures,
+    ExportResults,
+    ExportCrateMarkdown,
 }
 
-pub const HELP_COMMANDS: &[Command] = &[Command::SwitchToLastMode];
+pub const COMMON_COMMANDS: &[Command] = &[
+    Command::Suspend,
+    Command::NavigateBack,
+    Command::NavigateForward,
+    Command::NextTab,
+    Command::PreviousTab,
+    Command::NextTheme,
+    Command::PreviousTheme,
+    Command::ToggleFooterHints,
+    Command::SwitchMode(Mode::CommandPalette),
+];
+pub const HELP_COMMANDS: &[Command] = &[
+    Command::SwitchToLastMode,
+    Command::StartHelpFilter,
+    Command::ScrollHalfPageDown,
+    Command::ScrollHalfPageUp,
+    Command::ScrollPageDown,
+    Command::ScrollPageUp,
+];
 pub const PICKER_COMMANDS: &[Command] = &[
     Command::SwitchMode(Mode::Help),
     Command::SwitchMode(Mode::Summary),
     Command::SwitchMode(Mode::Search),
     Command::SwitchMode(Mode::Filter),
+    Command::SwitchMode(Mode::Bookmarks),
+    Command::SwitchMode(Mode::Categories),
+    Command::SwitchMode(Mode::Trending),
+    Command::SwitchMode(Mode::Lockfile),
     Command::ScrollUp,
     Command::ScrollDown,
+    Command::ScrollHalfPageUp,
+    Command::ScrollHalfPageDown,
+    Command::ScrollPageUp,
+    Command::ScrollPageDown,
     Command::ScrollCrateInfoUp,
     Command::ScrollCrateInfoDown,
     Command::ToggleSortBy {
@@ -58,23 +129,59 @@ pub const PICKER_COMMANDS: &[Command] = &[
         reload: false,
         forward: false,
     },
+    Command::ToggleSortDirection,
     Command::IncrementPage,
     Command::DecrementPage,
     Command::ReloadData,
+    Command::RefreshCrateDetails,
     Command::ToggleShowCrateInfo,
+    Command::ToggleBookmark,
+    Command::SearchByOwner,
     Command::OpenDocsUrlInBrowser,
     Command::OpenCratesIOUrlInBrowser,
+    Command::OpenRepositoryUrlInBrowser,
+    Command::OpenHomepageUrlInBrowser,
+    Command::CopyCratesIoUrlToClipboard,
+    Command::CopyDocsUrlToClipboard,
+    Command::ShowCrateQrCode,
     Command::CopyCargoAddCommandToClipboard,
+    Command::SwitchMode(Mode::FeatureSelect),
+    Command::CopyCargoAddWithFeatures,
+    Command::AddToLocalCargoToml,
+    Command::CopyTomlDependencyToClipboard,
+    Command::ToggleMark,
+    Command::CopyMarkedCargoAdd,
+    Command::CompareCrates,
+    Command::ExportResults,
+    Command::ExportCrateMarkdown,
+    Command::ToggleOnlyWithRepo,
 ];
 pub const SUMMARY_COMMANDS: &[Command] = &[
     Command::Quit,
     Command::ScrollDown,
     Command::ScrollUp,
+    Command::ScrollHalfPageDown,
+    Command::ScrollHalfPageUp,
+    Command::ScrollPageDown,
+    Command::ScrollPageUp,
     Command::PreviousSummaryMode,
     Command::NextSummaryMode,
+    Command::SummaryMoveLeft,
+    Command::SummaryMoveRight,
+    Command::SummaryMoveUp,
+    Command::SummaryMoveDown,
+    Command::SummarySelect,
+    Command::RefreshSummary,
+    Command::OpenCratesIOUrlInBrowser,
+    Command::CopyCratesIoUrlToClipboard,
+    Command::ShowCrateQrCode,
     Command::SwitchMode(Mode::Help),
     Command::SwitchMode(Mode::Search),
     Command::SwitchMode(Mode::Filter),
+    Command::SwitchMode(Mode::Bookmarks),
+    Command::SwitchMode(Mode::Categories),
+    Command::SwitchMode(Mode::Trending),
+    Command::SwitchMode(Mode::Lockfile),
 ];
 pub const SEARCH_COMMANDS: &[Command] = &[
     Command::SwitchMode(Mode::PickerHideCrateInfo),
@@ -97,13 +204,94 @@ pub const SEARCH_COMMANDS: &[Command] = &[
     },
     Command::ScrollSearchResultsUp,
     Command::ScrollSearchResultsDown,
+    Command::SwitchMode(Mode::PickerHideCrateInfo),
+    Command::ToggleSortDirection,
+    Command::ScrollSearchResultsUp,
+    Command::ScrollSearchResultsDown,
+];
+pub const FILTER_COMMANDS: &[Command] = &[
     Command::SwitchMode(Mode::PickerHideCrateInfo),
     Command::ScrollSearchResultsUp,
     Command::ScrollSearchResultsDown,
+    Command::SetMinDownloads,
+    Command::FilterByLicense,
+];
+pub const BOOKMARKS_COMMANDS: &[Command] = &[
+    Command::SwitchMode(Mode::Help),
+    Command::SwitchMode(Mode::PickerHideCrateInfo),
+    Command::ScrollUp,
+    Command::ScrollDown,
+    Command::ScrollHalfPageUp,
+    Command::ScrollHalfPageDown,
+    Command::ScrollPageUp,
+    Command::ScrollPageDown,
+    Command::ToggleBookmark,
+];
+pub const CATEGORIES_COMMANDS: &[Command] = &[
+    Command::SwitchMode(Mode::Help),
+    Command::SwitchMode(Mode::PickerHideCrateInfo),
+    Command::ScrollUp,
+    Command::ScrollDown,
+    Command::ScrollHalfPageUp,
+    Command::ScrollHalfPageDown,
+    Command::ScrollPageUp,
+    Command::ScrollPageDown,
+    Command::SearchCategory,
+    Command::RefreshCategories,
+];
+pub const TRENDING_COMMANDS: &[Command] = &[
+    Command::SwitchMode(Mode::Help),
+    Command::SwitchMode(Mode::PickerHideCrateInfo),
+    Command::SwitchMode(Mode::Search),
+    Command::ScrollUp,
+    Command::ScrollDown,
+    Command::ScrollTop,
+    Command::ScrollBottom,
+    Command::ScrollHalfPageUp,
+    Command::ScrollHalfPageDown,
+    Command::ScrollPageUp,
+    Command::ScrollPageDown,
+    Command::RefreshTrending,
+    Command::ToggleBookmark,
+];
+pub const FEATURE_SELECT_COMMANDS: &[Command] = &[
+    Command::SwitchMode(Mode::Help),
+    Command::SwitchMode(Mode::PickerShowCrateInfo),
+    Command::ScrollUp,
+    Command::ScrollDown,
+    Command::ToggleFeature,
+    Command::CopyCargoAddWithFeatures,
+];
+pub const COMPARE_COMMANDS: &[Command] = &[
+    Command::SwitchMode(Mode::Help),
+    Command::SwitchMode(Mode::PickerHideCrateInfo),
+    Command::SwitchMode(Mode::Search),
+    Command::RefreshComparison,
+];
+pub const LOCKFILE_COMMANDS: &[Command] = &[
+    Command::SwitchMode(Mode::Help),
+    Command::SwitchMode(Mode::PickerHideCrateInfo),
+    Command::SwitchMode(Mode::Search),
+    Command::ScrollUp,
+    Command::ScrollDown,
+    Command::ScrollTop,
+    Command::ScrollBottom,
+    Command::ScrollHalfPageUp,
+    Command::ScrollHalfPageDown,
+    Command::ScrollPageUp,
+    Command::ScrollPageDown,
 ];
 pub const ALL_COMMANDS: &[(Mode, &[Command])] = &[
+    (Mode::Common, COMMON_COMMANDS),
     (Mode::Help, HELP_COMMANDS),
     (Mode::PickerHideCrateInfo, PICKER_COMMANDS),
     (Mode::Summary, SUMMARY_COMMANDS),
     (Mode::Search, SEARCH_COMMANDS),
+    (Mode::Filter, FILTER_COMMANDS),
+    (Mode::Bookmarks, BOOKMARKS_COMMANDS),
+    (Mode::Categories, CATEGORIES_COMMANDS),
+    (Mode::Trending, TRENDING_COMMANDS),
+    (Mode::FeatureSelect, FEATURE_SELECT_COMMANDS),
+    (Mode::Compare, COMPARE_COMMANDS),
+    (Mode::Lockfile, LOCKFILE_COMMANDS),
 ];
@@ -1,21 +1,50 @@
-use std::{env, path::PathBuf, str::FromStr, sync::OnceLock};
+use std::{
+    collections::BTreeMap,
+    env,
+    path::{Path, PathBuf},
+    str::FromStr,
+    sync::{Arc, Mutex, OnceLock, RwLock},
+};
 
+use chrono::format::{Item, StrftimeItems};
 use color_eyre::eyre::{Result, eyre};
 use directories::ProjectDirs;
 use figment::{
     Figment,
     providers::{Env, Format, Serialized, Toml, Yaml},
+    value::Value,
 };
-use ratatui::style::Color;
+use num_format::Locale;
+use ratatui::style::{Color, ParseColorError, Style};
 use serde::{Deserialize, Serialize};
 use serde_with::{DisplayFromStr, NoneAsEmptyString, serde_as};
-use tracing::level_filters::LevelFilter;
+use tracing::{level_filters::LevelFilter, warn};
+use unicode_width::UnicodeWidthStr;
+
+pub use crate::cli::SortBy;
+
+use crate::{
+    cli::{Cli, MAX_RATE},
+    clipboard::ClipboardBackend,
+    crates_io_api_helper::MAX_PAGE_SIZE,
+    export::ExportFormat,
+    logging::LogFormat,
+    serde_helper::keybindings::KeyBindings,
+    spinner::SpinnerStyle,
+    widgets::search_results::SearchColumn,
+};
 
-use crate::{cli::Cli, serde_helper::keybindings::KeyBindings};
+static CONFIG: RwLock<Option<Arc<Config>>> = RwLock::new(None);
+static CLI: OnceLock<Cli> = OnceLock::new();
 
-static CONFIG: OnceLock<Config> = OnceLock::new();
+/// Problems found while loading the config that were worked around, kept
+/// until the app can show them since logging isn't set up yet.
+static WARNINGS: Mutex<Vec<String>> = Mutex::new(Vec::new());
 pub const CONFIG_DEFAULT: &str = include_str!("../.config/config.default.toml");
 
+/// The user agent sent to crates.io unless `user_agent` is set.
+const DEFAULT_USER_AGENT: &str = "crates-tui (crates-tui@kdheepak.com)";
+
 #[serde_as]
 #[derive(Debug, Clone, Copy, Deserialize, Serialize)]
 pub struct Base16Palette {
@@ -84,25 +113,152 @@ pub struct Base16Palette {
     pub base0f: Color,
 }
 
+/// The built-in palettes `theme` can select, in base00 to base0f order.
+pub const THEME_PRESETS: &[(&str, [&str; 16])] = &[
+    (
+        "rose-pine",
+        [
+            "#191724", "#1f1d2e", "#26233a", "#6e6a86", "#908caa", "#e0def4", "#e0def4", "#524f67",
+            "#eb6f92", "#f6c177", "#ebbcba", "#31748f", "#9ccfd8", "#c4a7e7", "#f6c177", "#524f67",
+        ],
+    ),
+    (
+        "rose-pine-moon",
+        [
+            "#232136", "#2a273f", "#393552", "#6e6a86", "#908caa", "#e0def4", "#e0def4", "#56526e",
+            "#eb6f92", "#f6c177", "#ea9a97", "#3e8fb0", "#9ccfd8", "#c4a7e7", "#f6c177", "#56526e",
+        ],
+    ),
+    (
+        "rose-pine-dawn",
+        [
+            "#faf4ed", "#fffaf3", "#f2e9de", "#9893a5", "#797593", "#575279", "#575279", "#cecacd",
+            "#b4637a", "#ea9d34", "#d7827e", "#286983", "#56949f", "#907aa9", "#ea9d34", "#cecacd",
+        ],
+    ),
+    (
+        "high-contrast",
+        [
+            "#000000", "#1c1c1c", "#3a3a3a", "#a8a8a8", "#d0d0d0", "#ffffff", "#ffffff", "#ffffff",
+            "#ff5f5f", "#ffaf00", "#ffff00", "#5fff5f", "#5fffff", "#5fafff", "#ff87ff", "#d7875f",
+        ],
+    ),
+];
+
 impl Default for Base16Palette {
     fn default() -> Self {
+        Self::preset("rose-pine").expect("rose-pine is a built-in theme")
+    }
+}
+
+impl Base16Palette {
+    /// The built-in palette named `name` in [`THEME_PRESETS`].
+    pub fn preset(name: &str) -> Option<Self> {
+        let (_, colors) = THEME_PRESETS.iter().find(|(preset, _)| *preset == name)?;
+        Self::from_hex(colors).ok()
+    }
+
+    /// Applies the colors of a base16 YAML file over this palette.
+    ///
+    /// Keys other than `base00` to `base0f`, e.g. `scheme` and `author`, are
+    /// ignored. A missing file leaves the palette unchanged.
+    pub fn merge_file(mut self, path: &Path) -> Result<Self> {
+        let values = Figment::new()
+            .merge(Yaml::file(path))
+            .extract::<BTreeMap<String, Value>>()?;
+        for (key, color) in self.colors_mut() {
+            let Some(value) = values.get(key) else {
+                continue;
+            };
+            let value = value
+                .as_str()
+                .ok_or_else(|| eyre!("invalid color for {key}: expected a string"))?;
+            *color =
+                Color::from_str(value).map_err(|_| eyre!("invalid color for {key}: '{value}'"))?;
+        }
+        Ok(self)
+    }
+
+    fn colors_mut(&mut self) -> [(&'static str, &mut Color); 16] {
+        [
+            ("base00", &mut self.base00),
+            ("base01", &mut self.base01),
+            ("base02", &mut self.base02),
+            ("base03", &mut self.base03),
+            ("base04", &mut self.base04),
+            ("base05", &mut self.base05),
+            ("base06", &mut self.base06),
+            ("base07", &mut self.base07),
+            ("base08", &mut self.base08),
+            ("base09", &mut self.base09),
+            ("base0a", &mut self.base0a),
+            ("base0b", &mut self.base0b),
+            ("base0c", &mut self.base0c),
+            ("base0d", &mut self.base0d),
+            ("base0e", &mut self.base0e),
+            ("base0f", &mut self.base0f),
+        ]
+    }
+
+    fn from_hex(colors: &[&str; 16]) -> Result<Self, ParseColorError> {
+        let [
+            base00,
+            base01,
+            base02,
+            base03,
+            base04,
+            base05,
+            base06,
+            base07,
+            base08,
+            base09,
+            base0a,
+            base0b,
+            base0c,
+            base0d,
+            base0e,
+            base0f,
+        ] = colors.map(Color::from_str);
+        Ok(Self {
+            base00: base00?,
+            base01: base01?,
+            base02: base02?,
+            base03: base03?,
+            base04: base04?,
+            base05: base05?,
+            base06: base06?,
+            base07: base07?,
+            base08: base08?,
+            base09: base09?,
+            base0a: base0a?,
+            base0b: base0b?,
+            base0c: base0c?,
+            base0d: base0d?,
+            base0e: base0e?,
+            base0f: base0f?,
+        })
+    }
+
+    /// A palette where every color is the terminal's default, used when
+    /// `NO_COLOR` is set.
+    pub fn monochrome() -> Self {
         Self {
-            base00: Color::from_str("#191724").unwrap(),
-            base01: Color::from_str("#1f1d2e").unwrap(),
-            base02: Color::from_str("#26233a").unwrap(),
-            base03: Color::from_str("#6e6a86").unwrap(),
-            base04: Color::from_str("#908caa").unwrap(),
-            base05: Color::from_str("#e0def4").unwrap(),
-            base06: Color::from_str("#e0def4").unwrap(),
-            base07: Color::from_str("#524f67").unwrap(),
-            base08: Color::from_str("#eb6f92").unwrap(),
-            base09: Color::from_str("#f6c177").unwrap(),
-            base0a: Color::from_str("#ebbcba").unwrap(),
-            base0b: Color::from_str("#31748f").unwrap(),
-            base0c: Color::from_str("#9ccfd8").unwrap(),
-            base0d: Color::from_str("#c4a7e7").unwrap(),
-            base0e: Color::from_str("#f6c177").unwrap(),
-            base0f: Color::from_str("#524f67").unwrap(),
+            base00: Color::Reset,
+            base01: Color::Reset,
+            base02: Color::Reset,
+            base03: Color::Reset,
+            base04: Color::Reset,
+            base05: Color::Reset,
+            base06: Color::Reset,
+            base07: Color::Reset,
+            base08: Color::Reset,
+            base09: Color::Reset,
+            base0a: Color::Reset,
+            base0b: Color::Reset,
+            base0c: Color::Reset,
+            base0d: Color::Reset,
+            base0e: Color::Reset,
+            base0f: Color::Reset,
         }
     }
 }
@@ -111,7 +267,7 @@ impl Default for Base16Palette {
 ///
 /// This is the main configuration struct for the application.
 #[serde_as]
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct Config {
     /// The directory to use for storing application data (logs etc.).
     pub data_dir: PathBuf,
@@ -124,11 +280,28 @@ pub struct Config {
     /// etc.).
     pub config_file: PathBuf,
 
+    /// The base16 color file the palette is loaded from.
+    pub color_file: PathBuf,
+
+    /// The built-in palette the color file is applied over: `rose-pine`,
+    /// `rose-pine-moon`, `rose-pine-dawn` or `high-contrast`.
+    pub theme: Option<String>,
+
     /// The log level to use. Valid values are: error, warn, info, debug, trace,
     /// off. The default is info.
     #[serde_as(as = "NoneAsEmptyString")]
     pub log_level: Option<LevelFilter>,
 
+    /// How events are written to the log file: `pretty` or `json`.
+    pub log_format: LogFormat,
+
+    /// The number of log files kept, deleting the oldest first.
+    pub log_max_files: usize,
+
+    /// The size in bytes at which a log file is rotated, starting another
+    /// file for the same day. Set to 0 to only rotate daily.
+    pub log_max_bytes: u64,
+
     pub tick_rate: f64,
 
     pub frame_rate: f64,
@@ -139,8 +312,168 @@ pub struct Config {
 
     pub enable_paste: bool,
 
+    /// Ask for confirmation before quitting.
+    pub confirm_quit: bool,
+
+    /// Mark the crates the project in the current directory already depends
+    /// on in the search results.
+    pub detect_local_deps: bool,
+
+    /// Show the most useful key bindings of the current mode below the status
+    /// bar. Toggled at runtime with `ToggleFooterHints`.
+    pub show_footer_hints: bool,
+
+    /// Reopen the mode, search and selection of the last session when
+    /// started without a query.
+    pub restore_session: bool,
+
+    /// Load the first page of crates for the search tab alongside the summary
+    /// when started without a query, so that switching to it is instant.
+    pub prefetch_on_startup: bool,
+
+    /// The blank rows and columns between the search prompt's border and its
+    /// input.
     pub prompt_padding: u16,
 
+    /// The animation shown while loading: `braille`, `dots`, `line` or `ascii`.
+    pub spinner_style: SpinnerStyle,
+
+    /// The file format used when exporting search results.
+    pub export_format: ExportFormat,
+
+    /// How text is copied to the clipboard: `system`, `osc52` or `auto`.
+    pub clipboard_backend: ClipboardBackend,
+
+    /// Query crates.io while typing in the search prompt instead of waiting for
+    /// the search to be submitted.
+    pub live_search: bool,
+
+    /// Load the next page of search results when scrolling past the last
+    /// crate, appending it to the table instead of replacing the page.
+    pub infinite_scroll: bool,
+
+    /// The most search results kept in memory. Infinite scroll stops loading
+    /// pages once this many crates have been appended.
+    pub max_results: usize,
+
+    /// Fetch the next page of search results in the background once a page
+    /// has loaded, so that moving to it is instant.
+    pub prefetch_next_page: bool,
+
+    /// How long typing has to pause before a live search is sent. This is
+    /// checked on every tick, so it is rounded up to the tick rate.
+    pub search_debounce_ms: u64,
+
+    /// How many times a failed crates.io request is retried on connection or
+    /// timeout errors.
+    pub max_retries: u32,
+
+    /// The delay before the first retry, doubled on every following retry.
+    pub base_backoff_ms: u64,
+
+    /// Crates with fewer downloads than this are hidden from search results.
+    pub min_downloads: Option<u64>,
+
+    /// The `strftime` format used to show dates, e.g. `%Y-%m-%d`.
+    pub date_format: String,
+
+    /// Show dates relative to now, e.g. "3 days ago", instead of using
+    /// `date_format`.
+    pub relative_time: bool,
+
+    /// Show download counts compactly, e.g. "12.3M", in the search results.
+    pub compact_downloads: bool,
+
+    /// The locale whose digit grouping is used for counts, e.g. `en` for
+    /// "1,234,567" or `de` for "1.234.567".
+    pub number_locale: String,
+
+    /// Show each search result on a single line, truncating descriptions,
+    /// except for the selected crate.
+    pub compact_results: bool,
+
+    /// The command URLs are opened with, e.g. `wslview {url}`, where `{url}`
+    /// is replaced with the URL. Arguments are split like a shell would, so
+    /// quote paths with spaces. Uses the default browser when unset.
+    pub browser_command: Option<String>,
+
+    /// Make URLs in the crate info clickable with OSC 8 hyperlinks. Detected
+    /// from the terminal when unset.
+    pub hyperlinks: Option<bool>,
+
+    /// The columns of the search results table, in order, from `name`,
+    /// `description`, `downloads`, `updated` and `recent_downloads`.
+    pub search_columns: Vec<SearchColumn>,
+
+    /// The number of places `NavigateBack` can return to.
+    pub navigation_history_size: usize,
+
+    /// The glyph marking the selected row, one column wide, e.g. `>` for
+    /// fonts that don't render the block glyph well.
+    pub highlight_symbol: String,
+
+    /// Mark the selected row with a background color instead of
+    /// `highlight_symbol`.
+    pub highlight_background: bool,
+
+    /// Render the UI in the terminal's default colors, ignoring the palette.
+    /// Also enabled by setting the `NO_COLOR` environment variable.
+    pub no_color: bool,
+
+    /// How long the crates.io summary is reused before it is fetched again.
+    /// Set to 0 to always fetch it.
+    pub summary_cache_ttl_secs: u64,
+
+    /// Fetch the details of the crates above and below the selection while
+    /// the crate info is shown, so that moving the selection is faster at the
+    /// cost of more requests.
+    pub prefetch_adjacent: bool,
+
+    /// The number of crates requested per page of search results, at most
+    /// 100.
+    pub page_size: u64,
+
+    /// The sort order of searches, unless overridden below or with `--sort`.
+    pub default_sort: SortBy,
+
+    /// The sort order of searches for a crate or keyword selected in the
+    /// summary. Defaults to `default_sort`.
+    pub summary_sort: Option<SortBy>,
+
+    /// The sort order of searches for a category selected in the summary or
+    /// the categories tab. Defaults to `default_sort`.
+    pub category_sort: Option<SortBy>,
+
+    /// The number of threads the async runtime uses, where 1 runs everything
+    /// on the main thread. Defaults to one per CPU core.
+    pub runtime_threads: Option<usize>,
+
+    /// Show the number of requests sent to crates.io in the status bar.
+    pub show_request_stats: bool,
+
+    /// The maximum sustained number of requests sent to crates.io per second.
+    /// Set to 0 to disable rate limiting.
+    pub requests_per_second: f64,
+
+    /// The user agent sent with every request. crates.io asks tools to
+    /// identify themselves with a way to contact their maintainers.
+    pub user_agent: String,
+
+    /// The API of a registry mirroring crates.io, e.g.
+    /// `https://registry.example.com/api/v1`, used instead of crates.io. The
+    /// Summary tab is hidden when the registry has no summary endpoint.
+    pub registry_url: Option<String>,
+
+    /// The proxy all requests are sent through, e.g.
+    /// `http://proxy.example.com:8080`. Defaults to the proxy in the
+    /// `HTTPS_PROXY` and `HTTP_PROXY` environment variables.
+    pub proxy_url: Option<String>,
+
+    /// Warn that the network may be slow when a search takes longer than
+    /// this, and give up on it when no request has been sent for twice as
+    /// long. Set to 0 to wait indefinitely.
+    pub loading_timeout_secs: u64,
+
     pub key_bindings: KeyBindings,
 
     pub color: Base16Palette,
@@ -155,19 +488,91 @@ impl Default for Config {
             data_dir: default_data_dir(),
             config_home: default_config_dir(),
             config_file: default_config_file(),
+            color_file: default_color_file(),
+            theme: None,
             log_level: None,
+            log_format: LogFormat::default(),
+            log_max_files: 7,
+            log_max_bytes: 10 * 1024 * 1024,
             tick_rate: 1.0,
             frame_rate: 15.0,
             key_refresh_rate: 0.5,
             enable_mouse: false,
             enable_paste: false,
+            confirm_quit: false,
+            show_footer_hints: false,
+            restore_session: false,
+            prefetch_on_startup: false,
+            detect_local_deps: false,
             prompt_padding: 1,
+            spinner_style: SpinnerStyle::default(),
+            export_format: ExportFormat::default(),
+            clipboard_backend: ClipboardBackend::default(),
+            live_search: false,
+            infinite_scroll: false,
+            max_results: 5000,
+            prefetch_next_page: false,
+            search_debounce_ms: 300,
+            max_retries: 3,
+            base_backoff_ms: 500,
+            requests_per_second: 1.0,
+            show_request_stats: false,
+            loading_timeout_secs: 15,
+            user_agent: DEFAULT_USER_AGENT.into(),
+            registry_url: None,
+            proxy_url: None,
+            runtime_threads: None,
+            min_downloads: None,
+            page_size: 25,
+            default_sort: SortBy::Relevance,
+            summary_sort: None,
+            category_sort: None,
+            prefetch_adjacent: false,
+            summary_cache_ttl_secs: 3600,
+            date_format: "%Y-%m-%d %H:%M:%S".into(),
+            relative_time: false,
+            compact_downloads: false,
+            number_locale: "en".into(),
+            compact_results: false,
+            browser_command: None,
+            hyperlinks: None,
+            search_columns: vec![
+                SearchColumn::Name,
+                SearchColumn::Description,
+                SearchColumn::Downloads,
+            ],
+            navigation_history_size: 50,
+            highlight_symbol: "█".into(),
+            highlight_background: false,
+            no_color: false,
             key_bindings,
             color: rose_pine,
         }
     }
 }
 
+impl Config {
+    /// The symbol marking the selected row, or a blank of the same width when
+    /// the selection is shown with a background color instead.
+    pub fn highlight_symbol(&self) -> &str {
+        if self.highlight_background {
+            " "
+        } else {
+            &self.highlight_symbol
+        }
+    }
+
+    /// The style applied to the selected row.
+    pub fn highlight_style(&self) -> Style {
+        let style = Style::default().fg(self.color.base05);
+        match (self.highlight_background, self.no_color) {
+            (true, true) => style.reversed(),
+            (true, false) => style.bg(self.color.base02),
+            (false, _) => style,
+        }
+    }
+}
+
 /// Initialize the application configuration.
 ///
 /// This function should be called before any other function in the application.
@@ -177,6 +582,77 @@ impl Default for Config {
 /// - environment variables
 /// - command line arguments
 pub fn init(cli: &Cli) -> Result<()> {
+    set(load(cli)?)?;
+    let _ = CLI.set(cli.clone());
+    Ok(())
+}
+
+/// Initialize the application configuration with `config` instead of reading
+/// the config files, environment and command line, e.g. when embedding the UI
+/// in another application. Start from [`defaults()`] to get the default key
+/// bindings.
+///
+/// `config` is checked the same way as a loaded one. As there are no files to
+/// re-read, [`reload()`] fails afterwards.
+pub fn init_with(config: Config) -> Result<()> {
+    set(validate(config)?)
+}
+
+/// The configuration used when no config file, environment variable or
+/// command line option overrides it, including the default key bindings.
+pub fn defaults() -> Result<Config> {
+    Ok(Figment::new()
+        .merge(Serialized::defaults(Config::default()))
+        .merge(Toml::string(CONFIG_DEFAULT))
+        .extract::<Config>()?)
+}
+
+fn set(config: Config) -> Result<()> {
+    let mut current = CONFIG.write().unwrap();
+    if current.is_some() {
+        return Err(eyre!("failed to set config {config:?}"));
+    }
+    *current = Some(Arc::new(config));
+    Ok(())
+}
+
+/// Initialize the application configuration with default values for tests.
+///
+/// Unlike [`init()`], this may be called any number of times.
+#[cfg(test)]
+pub fn init_for_tests() {
+    let mut current = CONFIG.write().unwrap();
+    if current.is_none() {
+        *current = Some(Arc::new(Config::default()));
+    }
+}
+
+/// Logs `warning` and keeps it for [`take_warnings()`], as the config is loaded
+/// before logging is set up.
+fn add_warning(warning: String) {
+    warn!("{warning}");
+    WARNINGS.lock().unwrap().push(warning);
+}
+
+/// Takes the warnings of loading the config, e.g. a malformed color file that
+/// was replaced by the default palette.
+pub fn take_warnings() -> Vec<String> {
+    std::mem::take(&mut *WARNINGS.lock().unwrap())
+}
+
+/// Reload the application configuration.
+///
+/// Re-reads the configuration from the same sources as [`init()`] and swaps
+/// it in place. Key bindings and colors take effect on the next frame, rates
+/// are only read on startup. On error the current configuration is kept.
+pub fn reload() -> Result<()> {
+    let cli = CLI.get().ok_or_else(|| eyre!("config not initialized"))?;
+    let config = load(cli)?;
+    *CONFIG.write().unwrap() = Some(Arc::new(config));
+    Ok(())
+}
+
+fn load(cli: &Cli) -> Result<Config> {
     let config_file = cli.config_file.clone().unwrap_or_else(default_config_file);
     let color_file = cli.color_file.clone().unwrap_or_else(default_color_file);
     let mut config = Figment::new()
@@ -186,17 +662,108 @@ pub fn init(cli: &Cli) -> Result<()> {
         .merge(Env::prefixed("CRATES_TUI_"))
         .merge(Serialized::defaults(cli))
         .extract::<Config>()?;
-    let base16 = Figment::new()
-        .merge(Serialized::defaults(Base16Palette::default()))
-        .merge(Yaml::file(color_file))
-        .extract::<Base16Palette>()?;
-    config.color = base16;
+    let preset = match &config.theme {
+        Some(theme) => Base16Palette::preset(theme).ok_or_else(|| {
+            let names = THEME_PRESETS
+                .iter()
+                .map(|(name, _)| *name)
+                .collect::<Vec<_>>();
+            let names = names.join(", ");
+            eyre!("unknown theme `{theme}`, expected one of: {names}")
+        })?,
+        None => Base16Palette::default(),
+    };
+    config.color = load_palette(preset, &color_file);
+    if let Some(accent) = cli.accent {
+        config.color.base0a = accent;
+        config.color.base0d = accent;
+    }
+    // https://no-color.org: any non-empty value disables color
+    if env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty()) {
+        config.no_color = true;
+    }
+    if config.no_color {
+        config.color = Base16Palette::monochrome();
+    }
+    if cli.single_thread {
+        config.runtime_threads = Some(1);
+    }
     if let Some(data_dir) = cli.data_dir.clone() {
         config.data_dir = data_dir;
     }
-    CONFIG
-        .set(config)
-        .map_err(|config| eyre!("failed to set config {config:?}"))
+    validate(config)
+}
+
+/// Checks the settings that would break the app, clamping the ones that can
+/// be worked around.
+fn validate(mut config: Config) -> Result<Config> {
+    if config.page_size == 0 {
+        return Err(eyre!("page_size must be greater than 0"));
+    }
+    if config.max_results == 0 {
+        return Err(eyre!("max_results must be greater than 0"));
+    }
+    if config.page_size > MAX_PAGE_SIZE {
+        add_warning(format!(
+            "page_size {} is larger than the crates.io maximum, using {MAX_PAGE_SIZE}",
+            config.page_size
+        ));
+        config.page_size = MAX_PAGE_SIZE;
+    }
+    for (name, rate) in [
+        ("tick_rate", &mut config.tick_rate),
+        ("frame_rate", &mut config.frame_rate),
+        ("key_refresh_rate", &mut config.key_refresh_rate),
+    ] {
+        if !rate.is_finite() || *rate <= 0.0 {
+            return Err(eyre!("{name} must be greater than 0"));
+        }
+        if *rate > MAX_RATE {
+            add_warning(format!("{name} {rate} is too high, using {MAX_RATE}"));
+            *rate = MAX_RATE;
+        }
+    }
+    if config.search_columns.is_empty() {
+        return Err(eyre!("search_columns must list at least one column"));
+    }
+    // the tables reserve a single column for the symbol
+    if config.highlight_symbol.width() != 1 {
+        return Err(eyre!(
+            "highlight_symbol `{}` must be one column wide",
+            config.highlight_symbol
+        ));
+    }
+    if StrftimeItems::new(&config.date_format).any(|item| item == Item::Error) {
+        return Err(eyre!("invalid date_format `{}`", config.date_format));
+    }
+    if Locale::from_name(&config.number_locale).is_err() {
+        add_warning(format!(
+            "number_locale `{}` is not a known locale, using `en`",
+            config.number_locale
+        ));
+        config.number_locale = "en".into();
+    }
+    if let Some(proxy_url) = &config.proxy_url
+        && let Err(err) = reqwest::Proxy::all(proxy_url)
+    {
+        return Err(eyre!("Invalid proxy_url {proxy_url}: {err}"));
+    }
+    if let Some(registry_url) = &config.registry_url {
+        match reqwest::Url::parse(registry_url) {
+            Ok(url) if matches!(url.scheme(), "http" | "https") => {}
+            _ => return Err(eyre!("registry_url must be an http(s) URL: {registry_url}")),
+        }
+    }
+    if config.user_agent.trim().is_empty() {
+        return Err(eyre!("user_agent must not be empty"));
+    }
+    if config.log_max_files == 0 {
+        return Err(eyre!("log_max_files must be at least 1"));
+    }
+    if config.runtime_threads == Some(0) {
+        return Err(eyre!("runtime_threads must be greater than 0"));
+    }
+    Ok(config)
 }
 
 /// Get the application configuration.
@@ -206,8 +773,65 @@ pub fn init(cli: &Cli) -> Result<()> {
 /// # Panics
 ///
 /// This function will panic if [`init()`] has not been called.
-pub fn get() -> &'static Config {
-    CONFIG.get().expect("config not initialized")
+pub fn get() -> Arc<Config> {
+    CONFIG
+        .read()
+        .unwrap()
+        .clone()
+        .expect("config not initialized")
+}
+
+/// Replace the color palette of the application configuration.
+///
+/// Widgets read the palette through [`get()`] on every render, so the new
+/// colors take effect on the next frame.
+pub fn set_color(color: Base16Palette) {
+    let mut current = CONFIG.write().unwrap();
+    if let Some(config) = current.as_mut()
+        && !config.no_color
+    {
+        Arc::make_mut(config).color = color;
+    }
+}
+
+/// Loads all base16 palettes from `<config_home>/themes/*.yaml`.
+///
+/// Returns the palettes sorted by file name along with their file stem.
+/// Files that fail to parse are skipped with a warning.
+pub fn load_themes() -> Vec<(String, Base16Palette)> {
+    let directory = get().config_home.join("themes");
+    let Ok(entries) = std::fs::read_dir(&directory) else {
+        return vec![];
+    };
+    let mut paths = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.extension().is_some_and(|ext| ext == "yaml"))
+        .collect::<Vec<_>>();
+    paths.sort();
+    paths
+        .into_iter()
+        .filter_map(|path| {
+            let name = path.file_stem()?.to_string_lossy().into_owned();
+            Base16Palette::default()
+                .merge_file(&path)
+                .inspect_err(|err| warn!("Unable to load theme {}: {err}", path.display()))
+                .ok()
+                .map(|palette| (name, palette))
+        })
+        .collect()
+}
+
+/// Applies the color file over `preset`, falling back to `preset` with a
+/// warning when the file is malformed.
+fn load_palette(preset: Base16Palette, color_file: &Path) -> Base16Palette {
+    preset.merge_file(color_file).unwrap_or_else(|err| {
+        let warning = format!(
+            "Unable to load color file {}: {err}, using the default palette",
+            color_file.display()
+        );
+        add_warning(warning);
+        preset
+    })
 }
 
 /// Returns the path to the default configuration file.
@@ -260,4 +884,84 @@ mod tests {
 
         println!("{}", toml::to_string_pretty(&c).unwrap());
     }
+
+    #[test]
+    fn malformed_color_file_falls_back_to_the_preset() {
+        let path =
+            std::env::temp_dir().join(format!("crates-tui-color-{}.yaml", std::process::id()));
+        std::fs::write(
+            &path,
+            "scheme: \"Broken\"\nbase00: \"#000000\"\nbase08: \"zzz\"\n",
+        )
+        .unwrap();
+        let preset = Base16Palette::default();
+
+        let err = preset.merge_file(&path).unwrap_err();
+        let palette = load_palette(preset, &path);
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(err.to_string(), "invalid color for base08: 'zzz'");
+        assert!(
+            take_warnings()
+                .iter()
+                .any(|warning| warning.contains("invalid color for base08: 'zzz'"))
+        );
+        assert_eq!(palette.base00, preset.base00);
+        assert_eq!(palette.base08, preset.base08);
+    }
+
+    #[test]
+    fn theme_presets_parse() {
+        for (name, colors) in THEME_PRESETS {
+            assert!(
+                Base16Palette::from_hex(colors).is_ok(),
+                "theme {name} has an invalid color"
+            );
+            assert!(Base16Palette::preset(name).is_some());
+        }
+        assert!(Base16Palette::preset("unknown").is_none());
+    }
+
+    #[test]
+    fn defaults_have_key_bindings_and_pass_validation() {
+        let config = validate(defaults().unwrap()).unwrap();
+        assert!(
+            !config
+                .key_bindings
+                .get_config_for_command(crate::app::Mode::Common, crate::command::Command::Quit)
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn command_palette_key_is_not_shadowed() {
+        use crate::{app::Mode, command::Command};
+
+        let key_bindings = defaults().unwrap().key_bindings;
+        let key = parse_key_sequence("alt-x").unwrap();
+        for mode in [
+            Mode::Summary,
+            Mode::PickerShowCrateInfo,
+            Mode::PickerHideCrateInfo,
+            Mode::Search,
+            Mode::Filter,
+            Mode::Bookmarks,
+            Mode::Categories,
+            Mode::Trending,
+            Mode::Compare,
+            Mode::Lockfile,
+            Mode::FeatureSelect,
+            Mode::Popup,
+            Mode::Help,
+        ] {
+            let command = key_bindings
+                .event_to_command(mode, &key)
+                .or_else(|| key_bindings.event_to_command(Mode::Common, &key));
+            assert_eq!(
+                command,
+                Some(Command::SwitchMode(Mode::CommandPalette)),
+                "{mode}"
+            );
+        }
+    }
 }
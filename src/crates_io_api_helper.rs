@@ -1,63 +1,627 @@
-use std::sync::{Arc, Mutex, atomic::AtomicBool};
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex, RwLock, atomic::AtomicBool},
+    time::{Duration, Instant},
+};
 
 use crates_io_api::CratesQuery;
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+use strum::Display;
 use tokio::sync::mpsc::UnboundedSender;
+use tracing::{error, info, warn};
 
-use crate::action::Action;
+use crate::{action::Action, config};
 use color_eyre::Result;
 
+/// The ways a request to crates.io can fail.
+#[derive(Debug, thiserror::Error)]
+pub enum ApiError {
+    /// crates.io could not be reached, timed out or answered with a server
+    /// error.
+    #[error("{context}: {source}")]
+    Network {
+        context: String,
+        source: crates_io_api::Error,
+    },
+    /// The requested crate, owner or page does not exist.
+    #[error("{0}")]
+    NotFound(String),
+    /// The proxy requests are sent through could not be reached or refused
+    /// the request.
+    #[error("{context}: unable to connect through proxy {proxy}: {source}")]
+    Proxy {
+        context: String,
+        proxy: String,
+        source: crates_io_api::Error,
+    },
+    /// crates.io rejected the request for exceeding its rate limit.
+    #[error("{context}: crates.io is rate limiting requests, try again later")]
+    RateLimited { context: String },
+    /// The response could not be decoded.
+    #[error("{context}: {source}")]
+    Parse {
+        context: String,
+        source: crates_io_api::Error,
+    },
+    /// The HTTP client could not be created.
+    #[error("API Client Error: {0}")]
+    ClientInit(String),
+}
+
+impl ApiError {
+    /// A short explanation of the error for the popup, where `action`
+    /// describes what was being done, e.g. "searching for `serde`".
+    ///
+    /// The full error is only written to the log.
+    pub fn user_message(&self, action: &str) -> String {
+        match self {
+            Self::Network { .. } => {
+                format!("Could not reach crates.io while {action}. Check your internet connection.")
+            }
+            Self::Proxy { proxy, .. } => format!(
+                "Could not connect through the proxy {proxy} while {action}. Check your proxy settings."
+            ),
+            Self::NotFound(_) => format!("Nothing was found on crates.io while {action}."),
+            Self::RateLimited { .. } => format!(
+                "crates.io is rate limiting requests while {action}. Try again in a moment."
+            ),
+            Self::Parse { .. } => {
+                format!("crates.io sent an unexpected response while {action}.")
+            }
+            Self::ClientInit(_) => {
+                format!("Could not create the HTTP client while {action}.")
+            }
+        }
+    }
+
+    /// Classifies an error returned by `crates_io_api` while doing `context`.
+    fn from_api(context: &str, err: crates_io_api::Error) -> Self {
+        let context = context.to_string();
+        match &err {
+            crates_io_api::Error::NotFound(_) => Self::NotFound(format!("{context}: {err}")),
+            crates_io_api::Error::JsonDecode(_) => Self::Parse {
+                context,
+                source: err,
+            },
+            crates_io_api::Error::Http(http) => match http.status() {
+                Some(reqwest::StatusCode::TOO_MANY_REQUESTS) => Self::RateLimited { context },
+                status
+                    if (http.is_connect()
+                        || status == Some(reqwest::StatusCode::PROXY_AUTHENTICATION_REQUIRED))
+                        && let Some(proxy) = configured_proxy() =>
+                {
+                    Self::Proxy {
+                        context,
+                        proxy,
+                        source: err,
+                    }
+                }
+                Some(reqwest::StatusCode::NOT_FOUND) => Self::NotFound(format!("{context}: {err}")),
+                _ if http.is_decode() => Self::Parse {
+                    context,
+                    source: err,
+                },
+                _ => Self::Network {
+                    context,
+                    source: err,
+                },
+            },
+            _ => Self::Network {
+                context,
+                source: err,
+            },
+        }
+    }
+}
+
+/// The largest page size crates.io accepts.
+pub const MAX_PAGE_SIZE: u64 = 100;
+
+/// The crates.io API, used unless `registry_url` points somewhere else.
+const CRATES_IO_API_URL: &str = "https://crates.io/api/v1/";
+
+const DOCS_RS_URL: &str = "https://docs.rs";
+
+/// The page of search results fetched ahead of time, used once by the search
+/// it was fetched for.
+static PREFETCHED_PAGE: Mutex<Option<PrefetchedPage>> = Mutex::new(None);
+
+#[derive(Debug)]
+struct PrefetchedPage {
+    key: String,
+    crates: Vec<crates_io_api::Crate>,
+    versions: Vec<crates_io_api::Version>,
+    total: u64,
+}
+
+/// The token bucket shared by every request to crates.io.
+static RATE_LIMITER: Mutex<Option<TokenBucket>> = Mutex::new(None);
+
+/// The requests sent by the app so far, shown in the status bar.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RequestStats {
+    pub count: u64,
+    pub last_request: Option<Instant>,
+}
+
+static REQUEST_STATS: Mutex<RequestStats> = Mutex::new(RequestStats {
+    count: 0,
+    last_request: None,
+});
+
+/// Returns the number of requests sent so far, counting every retry, and when
+/// the last one was sent.
+pub fn request_stats() -> RequestStats {
+    *REQUEST_STATS.lock().unwrap()
+}
+
+fn record_request() {
+    let mut stats = REQUEST_STATS.lock().unwrap();
+    stats.count += 1;
+    stats.last_request = Some(Instant::now());
+}
+
+/// A token bucket that refills at `requests_per_second` and holds at most one
+/// second worth of tokens.
+struct TokenBucket {
+    tokens: f64,
+    last_refill: Instant,
+}
+
+impl TokenBucket {
+    /// Takes a token if one is available, otherwise returns how long to wait
+    /// for the next one.
+    fn try_acquire(&mut self, rate: f64) -> Result<(), Duration> {
+        let now = Instant::now();
+        let elapsed = now.duration_since(self.last_refill).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * rate).min(rate.max(1.0));
+        self.last_refill = now;
+        if self.tokens >= 1.0 {
+            self.tokens -= 1.0;
+            Ok(())
+        } else {
+            Err(Duration::from_secs_f64((1.0 - self.tokens) / rate))
+        }
+    }
+}
+
+/// Waits until the shared rate limiter allows another request.
+///
+/// Callers set the loading status before requesting, so the spinner keeps
+/// running while a task waits here for a token.
+async fn acquire_token() {
+    let rate = config::get().requests_per_second;
+    if rate <= 0.0 || !rate.is_finite() {
+        return;
+    }
+    loop {
+        let wait = {
+            let mut limiter = RATE_LIMITER.lock().unwrap();
+            let bucket = limiter.get_or_insert_with(|| TokenBucket {
+                tokens: rate.max(1.0),
+                last_refill: Instant::now(),
+            });
+            match bucket.try_acquire(rate) {
+                Ok(()) => return,
+                Err(wait) => wait,
+            }
+        };
+        tokio::time::sleep(wait).await;
+    }
+}
+
+/// Logs `err` in full and shows a readable summary of it in a popup, which
+/// offers to perform `retry` if given.
+pub fn report_error(
+    tx: &UnboundedSender<Action>,
+    action: &str,
+    err: &ApiError,
+    retry: Option<Action>,
+) {
+    error!("Error {action}: {err:?}");
+    let message = err.user_message(action);
+    let _ = tx.send(match retry {
+        Some(retry) => Action::ShowRetryPopup(message, Box::new(retry)),
+        None => Action::ShowErrorPopup(message),
+    });
+}
+
 /// Represents the parameters needed for fetching crates asynchronously.
 pub struct SearchParameters {
     pub search: String,
     pub page: u64,
     pub page_size: u64,
-    pub crates: Arc<Mutex<Vec<crates_io_api::Crate>>>,
-    pub versions: Arc<Mutex<Vec<crates_io_api::Version>>>,
+    pub fetched: FetchedCrates,
     pub loading_status: Arc<AtomicBool>,
     pub sort: crates_io_api::Sort,
+    pub category: Option<String>,
+    pub owner: Option<Owner>,
+    /// Whether fetched crates are appended to `fetched` instead of replacing
+    /// them.
+    pub append: bool,
+    /// When the last attempt at the request finished, successful or not, so
+    /// that a search that is still retrying isn't taken for a stalled one.
+    pub last_progress: Arc<Mutex<Option<Instant>>>,
     pub tx: UnboundedSender<Action>,
 }
 
+/// The crates and versions fetched by a search, shared between the fetch task
+/// and the UI.
+///
+/// Both sit behind one read-write lock, so readers never see crates without
+/// their versions and don't wait for each other. The writer prepares the new
+/// data first and only holds the write lock while swapping it in.
+#[derive(Debug, Default, Clone)]
+pub struct FetchedCrates(Arc<RwLock<FetchedData>>);
+
+#[derive(Debug, Default)]
+struct FetchedData {
+    crates: Vec<crates_io_api::Crate>,
+    versions: Vec<crates_io_api::Version>,
+}
+
+impl FetchedCrates {
+    /// Calls `f` with the fetched crates and versions under the read lock.
+    pub fn read<R>(
+        &self,
+        f: impl FnOnce(&[crates_io_api::Crate], &[crates_io_api::Version]) -> R,
+    ) -> R {
+        let data = self.0.read().unwrap();
+        f(&data.crates, &data.versions)
+    }
+
+    /// A copy of the fetched crates.
+    pub fn crates(&self) -> Vec<crates_io_api::Crate> {
+        self.read(|crates, _| crates.to_vec())
+    }
+
+    /// The number of fetched crates.
+    pub fn len(&self) -> usize {
+        self.read(|crates, _| crates.len())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Replaces the fetched crates and versions.
+    pub fn replace(
+        &self,
+        crates: Vec<crates_io_api::Crate>,
+        versions: Vec<crates_io_api::Version>,
+    ) {
+        *self.0.write().unwrap() = FetchedData { crates, versions };
+    }
+
+    /// Appends to the fetched crates and versions.
+    pub fn extend(&self, crates: Vec<crates_io_api::Crate>, versions: Vec<crates_io_api::Version>) {
+        let mut data = self.0.write().unwrap();
+        data.crates.extend(crates);
+        data.versions.extend(versions);
+    }
+}
+
+/// A crates.io user or team whose crates can be listed.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Owner {
+    pub id: u64,
+    pub login: String,
+    pub is_team: bool,
+}
+
 /// Performs the actual search, and sends the result back through the
 /// sender.
-pub async fn request_search_results(params: &SearchParameters) -> Result<(), String> {
-    // Fetch crates using the created client with the error handling in one place.
+pub async fn request_search_results(params: &SearchParameters) -> Result<(), ApiError> {
+    let (crates, versions, total) = match take_prefetched_page(params) {
+        Some(page) => (page.crates, page.versions, page.total),
+        None => {
+            // Fetch crates using the created client with the error handling in one place.
+            let client = create_client()?;
+            let query = create_query(params);
+            fetch_crates_and_metadata(client, query, &params.last_progress).await?
+        }
+    };
+    update_state_with_fetched_crates(crates, versions, total, params);
+    Ok(())
+}
+
+/// Fetches a page of search results ahead of time so that a following
+/// [`request_search_results`] with the same parameters doesn't have to wait
+/// for crates.io.
+pub async fn prefetch_search_results(params: &SearchParameters) -> Result<(), ApiError> {
     let client = create_client()?;
     let query = create_query(params);
-    let (crates, versions, total) = fetch_crates_and_metadata(client, query).await?;
-    update_state_with_fetched_crates(crates, versions, total, params);
+    let (crates, versions, total) =
+        fetch_crates_and_metadata(client, query, &params.last_progress).await?;
+    *PREFETCHED_PAGE.lock().unwrap() = Some(PrefetchedPage {
+        key: prefetch_key(params),
+        crates,
+        versions,
+        total,
+    });
     Ok(())
 }
 
+fn take_prefetched_page(params: &SearchParameters) -> Option<PrefetchedPage> {
+    let mut prefetched_page = PREFETCHED_PAGE.lock().unwrap();
+    if prefetched_page
+        .as_ref()
+        .is_some_and(|page| page.key == prefetch_key(params))
+    {
+        prefetched_page.take()
+    } else {
+        None
+    }
+}
+
+/// Identifies the page of results requested by `params`.
+fn prefetch_key(params: &SearchParameters) -> String {
+    format!(
+        "{}|{}|{}|{:?}|{:?}|{:?}",
+        params.search, params.page, params.page_size, params.sort, params.category, params.owner
+    )
+}
+
 /// Helper function to create client and fetch crates, wrapping both actions
 /// into a result pattern.
-fn create_client() -> Result<crates_io_api::AsyncClient, String> {
-    // Attempt to create the API client
-    crates_io_api::AsyncClient::new(
-        "crates-tui (crates-tui@kdheepak.com)",
-        std::time::Duration::from_millis(1000),
-    )
-    .map_err(|err| format!("API Client Error: {err:#?}"))
+fn create_client() -> Result<RegistryClient, ApiError> {
+    let base_url = config::get()
+        .registry_url
+        .clone()
+        .unwrap_or_else(|| CRATES_IO_API_URL.into());
+    let base_url = reqwest::Url::parse(&base_url)
+        .map_err(|err| ApiError::ClientInit(format!("Invalid registry_url {base_url}: {err}")))?;
+    Ok(RegistryClient {
+        http: create_http_client()?,
+        base_url,
+    })
+}
+
+/// Sends the crates.io API requests to `registry_url`, or to crates.io when it
+/// is unset.
+///
+/// `crates_io_api::AsyncClient` always talks to crates.io, so the requests are
+/// built here and decoded into its types.
+struct RegistryClient {
+    http: reqwest::Client,
+    base_url: reqwest::Url,
+}
+
+/// The authors of a crate version, as crates.io sends them.
+#[derive(Deserialize)]
+struct AuthorsResponse {
+    meta: crates_io_api::AuthorsMeta,
+}
+
+/// A page of the crates depending on a crate, as crates.io sends them.
+#[derive(Deserialize)]
+struct ReverseDependenciesPage {
+    dependencies: Vec<crates_io_api::Dependency>,
+    versions: Vec<crates_io_api::Version>,
+    meta: crates_io_api::Meta,
+}
+
+impl RegistryClient {
+    /// The URL of the endpoint at `segments` under the registry's API, each
+    /// segment escaped.
+    fn url(&self, segments: &[&str]) -> reqwest::Url {
+        let mut url = self.base_url.clone();
+        if let Ok(mut path) = url.path_segments_mut() {
+            path.pop_if_empty().extend(segments);
+        }
+        url
+    }
+
+    async fn get<T: DeserializeOwned>(
+        &self,
+        segments: &[&str],
+        query: &[(&str, String)],
+    ) -> Result<T, crates_io_api::Error> {
+        Ok(self
+            .http
+            .get(self.url(segments))
+            .query(query)
+            .send()
+            .await?
+            .error_for_status()?
+            .json::<T>()
+            .await?)
+    }
+
+    async fn crates(
+        &self,
+        query: crates_io_api::CratesQuery,
+    ) -> Result<crates_io_api::CratesPage, crates_io_api::Error> {
+        let mut pairs = vec![
+            ("page", query.page().to_string()),
+            ("per_page", query.page_size().to_string()),
+            ("sort", sort_param(query.sort()).to_string()),
+        ];
+        pairs.extend(query.user_id().map(|id| ("user_id", id.to_string())));
+        pairs.extend(query.team_id().map(|id| ("team_id", id.to_string())));
+        pairs.extend(query.search().map(|search| ("q", search.clone())));
+        pairs.extend(
+            query
+                .category()
+                .map(|category| ("category", category.clone())),
+        );
+        for id in query.ids().into_iter().flatten() {
+            pairs.push(("ids[]", id.clone()));
+        }
+        self.get(&["crates"], &pairs).await
+    }
+
+    async fn get_crate(
+        &self,
+        name: &str,
+    ) -> Result<crates_io_api::CrateResponse, crates_io_api::Error> {
+        self.get(&["crates", name], &[]).await
+    }
+
+    async fn summary(&self) -> Result<crates_io_api::Summary, crates_io_api::Error> {
+        self.get(&["summary"], &[]).await
+    }
+
+    async fn crate_owners(
+        &self,
+        name: &str,
+    ) -> Result<Vec<crates_io_api::User>, crates_io_api::Error> {
+        let owners: crates_io_api::Owners = self.get(&["crates", name, "owners"], &[]).await?;
+        Ok(owners.users)
+    }
+
+    /// Fetches every detail of the crate, with the authors and dependencies
+    /// of its latest version only.
+    async fn full_crate(
+        &self,
+        name: &str,
+    ) -> Result<crates_io_api::FullCrate, crates_io_api::Error> {
+        let krate = self.get_crate(name).await?;
+        let latest = krate.versions.first().cloned();
+        let versions = async {
+            let Some(version) = latest else {
+                return Ok(vec![]);
+            };
+            let authors = ["crates", name, &version.num, "authors"];
+            let dependencies = ["crates", name, &version.num, "dependencies"];
+            let (authors, dependencies) = tokio::try_join!(
+                self.get::<AuthorsResponse>(&authors, &[]),
+                self.get::<crates_io_api::Dependencies>(&dependencies, &[]),
+            )?;
+            let authors = crates_io_api::Authors {
+                names: authors.meta.names,
+            };
+            Ok(vec![crates_io_api::FullVersion::from_parts(
+                version,
+                authors,
+                dependencies.dependencies,
+            )])
+        };
+        let downloads = ["crates", name, "downloads"];
+        let (versions, downloads, owners, reverse_dependencies) = tokio::try_join!(
+            versions,
+            self.get::<crates_io_api::CrateDownloads>(&downloads, &[]),
+            self.crate_owners(name),
+            self.reverse_dependencies(name),
+        )?;
+        let data = krate.crate_data;
+        Ok(crates_io_api::FullCrate {
+            id: data.id,
+            name: data.name,
+            description: data.description,
+            license: krate
+                .versions
+                .first()
+                .and_then(|version| version.license.clone()),
+            documentation: data.documentation,
+            homepage: data.homepage,
+            repository: data.repository,
+            total_downloads: data.downloads,
+            recent_downloads: data.recent_downloads,
+            max_version: data.max_version,
+            max_stable_version: data.max_stable_version,
+            created_at: data.created_at,
+            updated_at: data.updated_at,
+            categories: krate.categories,
+            keywords: krate.keywords,
+            downloads,
+            owners,
+            reverse_dependencies,
+            versions,
+        })
+    }
+
+    /// Fetches every crate version depending on the crate, a page at a time.
+    async fn reverse_dependencies(
+        &self,
+        name: &str,
+    ) -> Result<crates_io_api::ReverseDependencies, crates_io_api::Error> {
+        let mut reverse_dependencies = crates_io_api::ReverseDependencies {
+            dependencies: vec![],
+            meta: crates_io_api::Meta { total: 0 },
+        };
+        for page in 1.. {
+            let query = [("page", page.to_string()), ("per_page", "100".to_string())];
+            let page: ReverseDependenciesPage = self
+                .get(&["crates", name, "reverse_dependencies"], &query)
+                .await?;
+            if page.dependencies.is_empty() {
+                break;
+            }
+            reverse_dependencies.meta.total = page.meta.total;
+            for dependency in page.dependencies {
+                let versions = page
+                    .versions
+                    .iter()
+                    .filter(|version| version.id == dependency.version_id);
+                for version in versions {
+                    reverse_dependencies
+                        .dependencies
+                        .push(crates_io_api::ReverseDependency {
+                            crate_version: version.clone(),
+                            dependency: dependency.clone(),
+                        });
+                }
+            }
+        }
+        Ok(reverse_dependencies)
+    }
+
+    async fn categories(
+        &self,
+        page: u64,
+        per_page: u64,
+    ) -> Result<CategoriesPage, crates_io_api::Error> {
+        let query = [
+            ("page", page.to_string()),
+            ("per_page", per_page.to_string()),
+            ("sort", "alpha".to_string()),
+        ];
+        self.get(&["categories"], &query).await
+    }
+}
+
+/// The name crates.io gives `sort` in queries.
+fn sort_param(sort: &crates_io_api::Sort) -> &'static str {
+    match sort {
+        crates_io_api::Sort::Alphabetical => "alpha",
+        crates_io_api::Sort::Relevance => "relevance",
+        crates_io_api::Sort::Downloads => "downloads",
+        crates_io_api::Sort::RecentDownloads => "recent-downloads",
+        crates_io_api::Sort::RecentUpdates => "recent-updates",
+        crates_io_api::Sort::NewlyAdded => "new",
+    }
 }
 
 fn create_query(params: &SearchParameters) -> CratesQuery {
     // Form the query and fetch the crates, passing along any errors.
-    crates_io_api::CratesQueryBuilder::default()
+    let mut builder = crates_io_api::CratesQueryBuilder::default()
         .search(&params.search)
         .page(params.page)
         .page_size(params.page_size)
-        .sort(params.sort.clone())
-        .build()
+        .sort(params.sort.clone());
+    if let Some(category) = &params.category {
+        builder = builder.category(category);
+    }
+    match &params.owner {
+        Some(owner) if owner.is_team => builder = builder.team_id(owner.id),
+        Some(owner) => builder = builder.user_id(owner.id),
+        None => {}
+    }
+    builder.build()
 }
 
 async fn fetch_crates_and_metadata(
-    client: crates_io_api::AsyncClient,
+    client: RegistryClient,
     query: crates_io_api::CratesQuery,
-) -> Result<(Vec<crates_io_api::Crate>, Vec<crates_io_api::Version>, u64), String> {
-    let page_result = client
-        .crates(query)
-        .await
-        .map_err(|err| format!("API Client Error: {err:#?}"))?;
+    last_progress: &Mutex<Option<Instant>>,
+) -> Result<(Vec<crates_io_api::Crate>, Vec<crates_io_api::Version>, u64), ApiError> {
+    let page_result = with_retry_notifying(
+        "API Client Error",
+        || *last_progress.lock().unwrap() = Some(Instant::now()),
+        || client.crates(query.clone()),
+    )
+    .await?;
     let crates = page_result.crates;
     let total = page_result.meta.total;
     let versions = page_result.versions;
@@ -68,44 +632,111 @@ async fn fetch_crates_and_metadata(
 /// Handles the result after fetching crates and sending corresponding
 /// actions.
 fn update_state_with_fetched_crates(
-    crates: Vec<crates_io_api::Crate>,
+    mut crates: Vec<crates_io_api::Crate>,
     versions: Vec<crates_io_api::Version>,
     total: u64,
     params: &SearchParameters,
 ) {
-    // Lock and update the shared state container
-    let mut app_crates = params.crates.lock().unwrap();
-    app_crates.clear();
-    app_crates.extend(crates);
-
-    let mut app_versions = params.versions.lock().unwrap();
-    app_versions.clear();
-    app_versions.extend(versions);
+    // Prepare the new data before taking the write lock, so that it is only
+    // held while swapping it in
+    if params.append {
+        let room = config::get()
+            .max_results
+            .saturating_sub(params.fetched.len());
+        crates.truncate(room);
+        let versions = versions
+            .into_iter()
+            .filter(|v| crates.iter().any(|c| c.id == v.crate_name))
+            .collect::<Vec<_>>();
+        params.fetched.extend(crates, versions);
+        let _ = params.tx.send(Action::StoreTotalNumberOfCrates(total));
+        let _ = params.tx.send(Action::Tick);
+        return;
+    }
+    crates.truncate(config::get().max_results);
+    let is_empty = crates.is_empty();
+    params.fetched.replace(crates, versions);
 
     // After a successful fetch, send relevant actions based on the result
-    if app_crates.is_empty() {
+    if is_empty {
         let _ = params.tx.send(Action::ShowErrorPopup(format!(
-            "Could not find any crates with query `{}`.",
+            "No crates found on crates.io for `{}`.",
             params.search
         )));
     } else {
         let _ = params.tx.send(Action::StoreTotalNumberOfCrates(total));
         let _ = params.tx.send(Action::Tick);
-        let _ = params.tx.send(Action::ScrollDown);
+        // not `ScrollDown`, which would move the summary if it is shown
+        let _ = params.tx.send(Action::ScrollSearchResultsDown);
     }
 }
 
+/// Fetches the crates with the given names, e.g. to list bookmarked crates.
+pub async fn request_crates_by_name(
+    names: Vec<String>,
+    crates: Arc<Mutex<Vec<crates_io_api::Crate>>>,
+) -> Result<(), ApiError> {
+    let client = create_client()?;
+    let query = crates_io_api::CratesQueryBuilder::default()
+        .page_size(names.len().clamp(1, 100) as u64)
+        .ids(names)
+        .build();
+    let page_result = with_retry("API Client Error", || client.crates(query.clone())).await?;
+    *crates.lock().unwrap() = page_result.crates;
+    Ok(())
+}
+
+/// Fetches the crates downloaded the most in the last 90 days.
+pub async fn request_trending(
+    page_size: u64,
+    crates: Arc<Mutex<Vec<crates_io_api::Crate>>>,
+) -> Result<(), ApiError> {
+    let client = create_client()?;
+    let query = crates_io_api::CratesQueryBuilder::default()
+        .page_size(page_size)
+        .sort(crates_io_api::Sort::RecentDownloads)
+        .build();
+    let page_result = with_retry("API Client Error", || client.crates(query.clone())).await?;
+    *crates.lock().unwrap() = page_result.crates;
+    Ok(())
+}
+
+/// Fetches the latest stable version of each of the named crates, at most 100
+/// at a time, and records them by crate name.
+pub async fn request_latest_versions(
+    names: Vec<String>,
+    latest_versions: Arc<Mutex<HashMap<String, String>>>,
+) -> Result<(), ApiError> {
+    let client = create_client()?;
+    let query = crates_io_api::CratesQueryBuilder::default()
+        .page_size(names.len().clamp(1, 100) as u64)
+        .ids(names)
+        .build();
+    let page_result = with_retry("Error fetching latest versions", || {
+        client.crates(query.clone())
+    })
+    .await?;
+    latest_versions
+        .lock()
+        .unwrap()
+        .extend(page_result.crates.into_iter().map(|krate| {
+            let version = krate.max_stable_version.unwrap_or(krate.max_version);
+            (krate.name, version)
+        }));
+    Ok(())
+}
+
 // Performs the async fetch of crate details.
 pub async fn request_crate_details(
     crate_name: &str,
     crate_info: Arc<Mutex<Option<crates_io_api::CrateResponse>>>,
-) -> Result<(), String> {
+) -> Result<(), ApiError> {
     let client = create_client()?;
 
-    let crate_data = client
-        .get_crate(crate_name)
-        .await
-        .map_err(|err| format!("Error fetching crate details: {err:#?}"))?;
+    let crate_data = with_retry("Error fetching crate details", || {
+        client.get_crate(crate_name)
+    })
+    .await?;
     *crate_info.lock().unwrap() = Some(crate_data);
     Ok(())
 }
@@ -114,27 +745,284 @@ pub async fn request_crate_details(
 pub async fn request_full_crate_details(
     crate_name: &str,
     full_crate_info: Arc<Mutex<Option<crates_io_api::FullCrate>>>,
-) -> Result<(), String> {
+) -> Result<(), ApiError> {
+    let full_crate_data = request_full_crate(crate_name).await?;
+    *full_crate_info.lock().unwrap() = Some(full_crate_data);
+    Ok(())
+}
+
+/// Fetches every detail of `crate_name`, including the dependencies of each
+/// version.
+pub async fn request_full_crate(crate_name: &str) -> Result<crates_io_api::FullCrate, ApiError> {
     let client = create_client()?;
+    with_retry("Error fetching crate details", || {
+        client.full_crate(crate_name)
+    })
+    .await
+}
 
-    let full_crate_data = client
-        .full_crate(crate_name, false)
-        .await
-        .map_err(|err| format!("Error fetching crate details: {err:#?}"))?;
+/// Fetches the details of two crates concurrently, e.g. to compare them.
+pub async fn request_crate_pair(
+    first: &str,
+    second: &str,
+) -> Result<(crates_io_api::CrateResponse, crates_io_api::CrateResponse), ApiError> {
+    let client = create_client()?;
+    let (first, second) = tokio::join!(
+        with_retry("Error fetching crate details", || client.get_crate(first)),
+        with_retry("Error fetching crate details", || client.get_crate(second)),
+    );
+    Ok((first?, second?))
+}
 
-    *full_crate_info.lock().unwrap() = Some(full_crate_data);
-    Ok(())
+pub async fn request_summary() -> Result<crates_io_api::Summary, ApiError> {
+    let client = create_client()?;
+    with_retry("Error fetching crate details", || client.summary()).await
 }
 
-pub async fn request_summary(
-    summary: Arc<Mutex<Option<crates_io_api::Summary>>>,
-) -> Result<(), String> {
+/// Fetches the users and teams owning a crate.
+pub async fn request_crate_owners(crate_name: &str) -> Result<Vec<crates_io_api::User>, ApiError> {
     let client = create_client()?;
+    with_retry("Error fetching crate owners", || {
+        client.crate_owners(crate_name)
+    })
+    .await
+}
+
+/// Resolves the owners of a crate, preferring the first user over teams.
+pub async fn request_crate_owner(crate_name: &str) -> Result<Owner, ApiError> {
+    let owners = request_crate_owners(crate_name).await?;
+    let is_team = |user: &crates_io_api::User| user.kind.as_deref() == Some("team");
+    owners
+        .iter()
+        .find(|user| !is_team(user))
+        .or_else(|| owners.first())
+        .map(|user| Owner {
+            id: user.id,
+            login: user.login.clone(),
+            is_team: is_team(user),
+        })
+        .ok_or_else(|| ApiError::NotFound(format!("No owners found for `{crate_name}`")))
+}
+
+/// Creates the HTTP client every request is sent with, identifying the app
+/// with the configured user agent.
+///
+/// Requests go through `proxy_url` when set, and otherwise through the proxy
+/// in the `HTTPS_PROXY`, `HTTP_PROXY` and `NO_PROXY` environment variables.
+fn create_http_client() -> Result<reqwest::Client, ApiError> {
+    let mut builder = reqwest::Client::builder().user_agent(config::get().user_agent.as_str());
+    if let Some(proxy_url) = &config::get().proxy_url {
+        let proxy = reqwest::Proxy::all(proxy_url)
+            .map_err(|err| ApiError::ClientInit(format!("Invalid proxy {proxy_url}: {err}")))?
+            .no_proxy(reqwest::NoProxy::from_env());
+        builder = builder.proxy(proxy);
+    }
+    builder
+        .build()
+        .map_err(|err| ApiError::ClientInit(err.to_string()))
+}
+
+/// The state of the documentation of a crate version on docs.rs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Display)]
+pub enum DocsStatus {
+    #[strum(serialize = "ok")]
+    Ok,
+    #[strum(serialize = "failed")]
+    Failed,
+    #[strum(serialize = "building")]
+    Building,
+    #[strum(serialize = "not on docs.rs")]
+    NotPublished,
+}
+
+/// A build of a crate version on docs.rs.
+#[derive(Deserialize)]
+struct DocsBuild {
+    build_status: BuildStatus,
+}
+
+/// Either `success`, `failure` or `in_progress`, or whether the build
+/// succeeded in older versions of docs.rs.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum BuildStatus {
+    Succeeded(bool),
+    Named(String),
+}
 
-    let summary_data = client
-        .summary()
-        .await
-        .map_err(|err| format!("Error fetching crate details: {err:#?}"))?;
-    *summary.lock().unwrap() = Some(summary_data);
+/// Fetches the state of the latest docs.rs build of a crate version.
+///
+/// Versions docs.rs doesn't know about, e.g. because the crate was never
+/// published there, are reported as `DocsStatus::NotPublished`.
+pub async fn request_docs_status(name: &str, version: &str) -> Result<DocsStatus, ApiError> {
+    let client = create_http_client()?;
+    let url = format!("{DOCS_RS_URL}/crate/{name}/{version}/builds.json");
+    let request = || async {
+        client
+            .get(&url)
+            .send()
+            .await?
+            .error_for_status()?
+            .json::<Vec<DocsBuild>>()
+            .await
+            .map_err(crates_io_api::Error::from)
+    };
+    let builds = match with_retry("Error fetching docs.rs build status", request).await {
+        Ok(builds) => builds,
+        Err(ApiError::NotFound(_)) => return Ok(DocsStatus::NotPublished),
+        Err(err) => return Err(err),
+    };
+    // the newest build comes first, and none are listed while the first is
+    // queued
+    let status = match builds.into_iter().next().map(|build| build.build_status) {
+        None => DocsStatus::Building,
+        Some(BuildStatus::Succeeded(true)) => DocsStatus::Ok,
+        Some(BuildStatus::Succeeded(false)) => DocsStatus::Failed,
+        Some(BuildStatus::Named(status)) => match status.as_str() {
+            "success" => DocsStatus::Ok,
+            "in_progress" => DocsStatus::Building,
+            _ => DocsStatus::Failed,
+        },
+    };
+    Ok(status)
+}
+
+/// A page of the categories listing.
+#[derive(Deserialize)]
+struct CategoriesPage {
+    categories: Vec<crates_io_api::Category>,
+    meta: crates_io_api::Meta,
+}
+
+/// Returns the proxy HTTPS requests are sent through, if any.
+fn configured_proxy() -> Option<String> {
+    config::get().proxy_url.clone().or_else(|| {
+        ["HTTPS_PROXY", "https_proxy", "ALL_PROXY", "all_proxy"]
+            .into_iter()
+            .find_map(|name| std::env::var(name).ok().filter(|value| !value.is_empty()))
+    })
+}
+
+/// Fetches all categories, following pagination until every category is
+/// loaded.
+pub async fn request_categories(
+    categories: Arc<Mutex<Vec<crates_io_api::Category>>>,
+) -> Result<(), ApiError> {
+    const PER_PAGE: u64 = 100;
+    let client = create_client()?;
+    let mut all_categories = vec![];
+    for page in 1.. {
+        let categories_page = with_retry("Error fetching categories", || {
+            client.categories(page, PER_PAGE)
+        })
+        .await?;
+        let fetched = categories_page.categories.len() as u64;
+        all_categories.extend(categories_page.categories);
+        if fetched < PER_PAGE || all_categories.len() as u64 >= categories_page.meta.total {
+            break;
+        }
+    }
+    *categories.lock().unwrap() = all_categories;
     Ok(())
 }
+
+/// Runs `request`, retrying with exponential backoff while it fails with a
+/// transient error.
+///
+/// The delay starts at `base_backoff_ms` and doubles on every retry, up to
+/// `max_retries` retries. Errors are classified with `context` describing the
+/// request.
+async fn with_retry<T, F, Fut>(context: &str, request: F) -> Result<T, ApiError>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, crates_io_api::Error>>,
+{
+    with_retry_notifying(context, || {}, request).await
+}
+
+/// Like [`with_retry`], calling `on_attempt` whenever an attempt finishes.
+async fn with_retry_notifying<T, F, Fut>(
+    context: &str,
+    mut on_attempt: impl FnMut(),
+    mut request: F,
+) -> Result<T, ApiError>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, crates_io_api::Error>>,
+{
+    let config = config::get();
+    let mut retries = 0;
+    loop {
+        acquire_token().await;
+        record_request();
+        let result = request().await;
+        on_attempt();
+        match result {
+            Ok(value) => {
+                if retries > 0 {
+                    info!("{context}: succeeded after {retries} retries");
+                }
+                return Ok(value);
+            }
+            Err(err) if retries < config.max_retries && is_transient(&err) => {
+                let backoff = config
+                    .base_backoff_ms
+                    .saturating_mul(2u64.saturating_pow(retries));
+                retries += 1;
+                warn!(
+                    "{context}: attempt {retries} of {} failed, retrying in {backoff}ms: {err}",
+                    config.max_retries + 1
+                );
+                tokio::time::sleep(Duration::from_millis(backoff)).await;
+            }
+            Err(err) => {
+                if retries > 0 {
+                    warn!("{context}: gave up after {} attempts", retries + 1);
+                }
+                return Err(ApiError::from_api(context, err));
+            }
+        }
+    }
+}
+
+/// Returns true for errors that may succeed when retried, i.e. connection
+/// failures, timeouts and server errors. Missing crates are never retried.
+fn is_transient(err: &crates_io_api::Error) -> bool {
+    match err {
+        crates_io_api::Error::Http(err) => {
+            err.is_connect()
+                || err.is_timeout()
+                || err.status().is_some_and(|status| status.is_server_error())
+        }
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn registry_urls_keep_the_base_path() {
+        let client = RegistryClient {
+            http: reqwest::Client::new(),
+            base_url: reqwest::Url::parse("https://registry.example.com/api/v1").unwrap(),
+        };
+        assert_eq!(
+            client.url(&["crates", "ratatui", "owners"]).as_str(),
+            "https://registry.example.com/api/v1/crates/ratatui/owners"
+        );
+        assert_eq!(
+            client.url(&["crates", "../summary"]).as_str(),
+            "https://registry.example.com/api/v1/crates/..%2Fsummary"
+        );
+        let client = RegistryClient {
+            base_url: reqwest::Url::parse(CRATES_IO_API_URL).unwrap(),
+            ..client
+        };
+        assert_eq!(
+            client.url(&["summary"]).as_str(),
+            "https://crates.io/api/v1/summary"
+        );
+    }
+}
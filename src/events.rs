@@ -1,16 +1,29 @@
 use core::fmt;
 use std::{pin::Pin, time::Duration};
 
+use color_eyre::eyre::Result;
 use crossterm::event::{Event as CrosstermEvent, *};
 use futures::{Stream, StreamExt};
+use itertools::Itertools;
+use notify::{RecommendedWatcher, RecursiveMode, Watcher};
 use serde::{Deserialize, Serialize};
 use tokio::time::interval;
-use tokio_stream::{StreamMap, wrappers::IntervalStream};
+use tokio_stream::{
+    StreamMap,
+    wrappers::{IntervalStream, UnboundedReceiverStream},
+};
+use tracing::warn;
 
 use crate::config;
 
+type BoxedStream = Pin<Box<dyn Stream<Item = Event>>>;
+
 pub struct Events {
-    streams: StreamMap<StreamName, Pin<Box<dyn Stream<Item = Event>>>>,
+    streams: StreamMap<StreamName, BoxedStream>,
+
+    /// Keeps the file watcher alive for as long as the event streams are
+    /// polled.
+    _config_watcher: Option<RecommendedWatcher>,
 }
 
 impl fmt::Debug for Events {
@@ -25,6 +38,7 @@ enum StreamName {
     KeyRefresh,
     Render,
     Crossterm,
+    ConfigWatcher,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -36,18 +50,37 @@ pub enum Event {
     Tick,
     KeyRefresh,
     Render,
+    ConfigChanged,
     Crossterm(CrosstermEvent),
 }
 
+impl Default for Events {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Events {
     pub fn new() -> Self {
+        let mut streams = StreamMap::from_iter([
+            (StreamName::Ticks, tick_stream()),
+            (StreamName::KeyRefresh, key_refresh_stream()),
+            (StreamName::Render, render_stream()),
+            (StreamName::Crossterm, crossterm_stream()),
+        ]);
+        let config_watcher = match config_watcher_stream() {
+            Ok((watcher, stream)) => {
+                streams.insert(StreamName::ConfigWatcher, stream);
+                Some(watcher)
+            }
+            Err(err) => {
+                warn!("Unable to watch config files: {err}");
+                None
+            }
+        };
         Self {
-            streams: StreamMap::from_iter([
-                (StreamName::Ticks, tick_stream()),
-                (StreamName::KeyRefresh, key_refresh_stream()),
-                (StreamName::Render, render_stream()),
-                (StreamName::Crossterm, crossterm_stream()),
-            ]),
+            streams,
+            _config_watcher: config_watcher,
         }
     }
 
@@ -56,25 +89,57 @@ impl Events {
     }
 }
 
-fn tick_stream() -> Pin<Box<dyn Stream<Item = Event>>> {
+fn tick_stream() -> BoxedStream {
     let tick_delay = Duration::from_secs_f64(1.0 / config::get().tick_rate);
     let tick_interval = interval(tick_delay);
     Box::pin(IntervalStream::new(tick_interval).map(|_| Event::Tick))
 }
 
-fn key_refresh_stream() -> Pin<Box<dyn Stream<Item = Event>>> {
+fn key_refresh_stream() -> BoxedStream {
     let key_refresh_delay = Duration::from_secs_f64(1.0 / config::get().key_refresh_rate);
     let key_refresh_interval = interval(key_refresh_delay);
     Box::pin(IntervalStream::new(key_refresh_interval).map(|_| Event::KeyRefresh))
 }
 
-fn render_stream() -> Pin<Box<dyn Stream<Item = Event>>> {
+fn render_stream() -> BoxedStream {
     let render_delay = Duration::from_secs_f64(1.0 / config::get().frame_rate);
     let render_interval = interval(render_delay);
     Box::pin(IntervalStream::new(render_interval).map(|_| Event::Render))
 }
 
-fn crossterm_stream() -> Pin<Box<dyn Stream<Item = Event>>> {
+/// Watches the config and color files, emitting `Event::ConfigChanged` when
+/// either of them is written.
+///
+/// The parent directories are watched rather than the files themselves since
+/// many editors save by replacing the file.
+fn config_watcher_stream() -> Result<(RecommendedWatcher, BoxedStream)> {
+    let config = config::get();
+    let files = [config.config_file.clone(), config.color_file.clone()];
+    let file_names = files
+        .iter()
+        .filter_map(|file| file.file_name().map(ToOwned::to_owned))
+        .collect_vec();
+    let (tx, rx) = tokio::sync::mpsc::unbounded_channel();
+    let mut watcher = notify::recommended_watcher(move |res: notify::Result<notify::Event>| {
+        if let Ok(event) = res
+            && (event.kind.is_modify() || event.kind.is_create())
+            && event.paths.iter().any(|path| {
+                path.file_name()
+                    .is_some_and(|name| file_names.iter().any(|f| f == name))
+            })
+        {
+            let _ = tx.send(Event::ConfigChanged);
+        }
+    })?;
+    for directory in files.iter().filter_map(|file| file.parent()).unique() {
+        if directory.is_dir() {
+            watcher.watch(directory, RecursiveMode::NonRecursive)?;
+        }
+    }
+    Ok((watcher, Box::pin(UnboundedReceiverStream::new(rx))))
+}
+
+fn crossterm_stream() -> BoxedStream {
     Box::pin(EventStream::new().fuse().filter_map(|event| async move {
         match event {
             // Ignore key release / repeat events
@@ -0,0 +1,348 @@
+use std::{fmt::Write, path::PathBuf};
+
+use color_eyre::eyre::Result;
+use serde::{Deserialize, Serialize};
+use strum::Display;
+
+use crate::{config, crates_io_api_helper, format, runtime};
+
+/// File format used when exporting search results.
+#[derive(
+    Debug, Default, Display, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, clap::ValueEnum,
+)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum ExportFormat {
+    #[default]
+    Json,
+    Csv,
+}
+
+impl ExportFormat {
+    fn extension(&self) -> &'static str {
+        match self {
+            ExportFormat::Json => "json",
+            ExportFormat::Csv => "csv",
+        }
+    }
+}
+
+/// The subset of crate fields written to an export file.
+#[derive(Debug, Serialize)]
+struct ExportedCrate<'a> {
+    name: &'a str,
+    description: Option<&'a str>,
+    downloads: u64,
+    max_version: &'a str,
+    updated_at: String,
+}
+
+impl<'a> From<&'a crates_io_api::Crate> for ExportedCrate<'a> {
+    fn from(krate: &'a crates_io_api::Crate) -> Self {
+        Self {
+            name: &krate.name,
+            description: krate.description.as_deref(),
+            downloads: krate.downloads,
+            max_version: &krate.max_version,
+            updated_at: krate.updated_at.to_rfc3339(),
+        }
+    }
+}
+
+/// Writes `crates` to a timestamped file in `<data_dir>/exports/` and returns
+/// the path of the written file.
+pub fn export_crates(crates: &[crates_io_api::Crate], format: ExportFormat) -> Result<PathBuf> {
+    let directory = config::get().data_dir.join("exports");
+    std::fs::create_dir_all(&directory)?;
+    let timestamp = chrono::Local::now().format("%Y%m%d-%H%M%S");
+    let path = directory.join(format!(
+        "{}-{timestamp}.{}",
+        env!("CARGO_PKG_NAME"),
+        format.extension()
+    ));
+    std::fs::write(&path, serialize_crates(crates, format)?)?;
+    Ok(path)
+}
+
+/// Writes the details of `krate` as Markdown to `<data_dir>/exports/<name>.md`
+/// and returns the path of the written file.
+pub fn export_crate_markdown(krate: &crates_io_api::FullCrate) -> Result<PathBuf> {
+    let directory = config::get().data_dir.join("exports");
+    std::fs::create_dir_all(&directory)?;
+    let path = directory.join(format!("{}.md", krate.name));
+    std::fs::write(&path, crate_markdown(krate))?;
+    Ok(path)
+}
+
+/// Formats the details of `krate` as Markdown, listing the dependencies of its
+/// latest version.
+fn crate_markdown(krate: &crates_io_api::FullCrate) -> String {
+    let mut markdown = format!("# {}\n\n", krate.name);
+    if let Some(description) = &krate.description {
+        let _ = writeln!(markdown, "{}\n", description.trim());
+    }
+
+    let version = krate
+        .max_stable_version
+        .as_ref()
+        .unwrap_or(&krate.max_version);
+    let _ = writeln!(markdown, "- **Version:** {version}");
+    let _ = writeln!(
+        markdown,
+        "- **Downloads:** {}",
+        format::format_count(krate.total_downloads)
+    );
+    if let Some(recent_downloads) = krate.recent_downloads {
+        let _ = writeln!(
+            markdown,
+            "- **Recent downloads:** {}",
+            format::format_count(recent_downloads)
+        );
+    }
+    if let Some(license) = &krate.license {
+        let _ = writeln!(markdown, "- **License:** {license}");
+    }
+
+    markdown.push_str("\n## Links\n\n");
+    let _ = writeln!(
+        markdown,
+        "- [crates.io](https://crates.io/crates/{})",
+        krate.name
+    );
+    for (name, url) in [
+        ("Documentation", &krate.documentation),
+        ("Repository", &krate.repository),
+        ("Homepage", &krate.homepage),
+    ] {
+        if let Some(url) = url {
+            let _ = writeln!(markdown, "- [{name}]({url})");
+        }
+    }
+
+    if !krate.keywords.is_empty() {
+        let keywords = krate
+            .keywords
+            .iter()
+            .map(|keyword| format!("`{}`", keyword.keyword))
+            .collect::<Vec<_>>();
+        let _ = writeln!(markdown, "\n## Keywords\n\n{}", keywords.join(", "));
+    }
+
+    let dependencies = krate
+        .versions
+        .iter()
+        .find(|full_version| &full_version.num == version)
+        .or(krate.versions.first())
+        .map(|full_version| full_version.dependencies.as_slice())
+        .unwrap_or_default();
+    for (kind, title) in [
+        ("normal", "Dependencies"),
+        ("build", "Build dependencies"),
+        ("dev", "Dev dependencies"),
+    ] {
+        let mut dependencies = dependencies
+            .iter()
+            .filter(|dependency| dependency.kind == kind)
+            .collect::<Vec<_>>();
+        if dependencies.is_empty() {
+            continue;
+        }
+        dependencies.sort_by(|a, b| a.crate_id.cmp(&b.crate_id));
+        let _ = writeln!(markdown, "\n## {title}\n");
+        for dependency in dependencies {
+            let optional = if dependency.optional {
+                " (optional)"
+            } else {
+                ""
+            };
+            let _ = writeln!(
+                markdown,
+                "- `{}` {}{optional}",
+                dependency.crate_id, dependency.req
+            );
+        }
+    }
+    markdown
+}
+
+/// Fetches the first page of search results for `query` and prints them to
+/// stdout instead of starting the TUI.
+pub fn print_search_results(
+    query: String,
+    sort: crates_io_api::Sort,
+    format: ExportFormat,
+) -> Result<()> {
+    runtime::build()?.block_on(fetch_and_print(query, sort, format))
+}
+
+async fn fetch_and_print(
+    query: String,
+    sort: crates_io_api::Sort,
+    format: ExportFormat,
+) -> Result<()> {
+    let (tx, _rx) = tokio::sync::mpsc::unbounded_channel();
+    let params = crates_io_api_helper::SearchParameters {
+        search: query,
+        page: 1,
+        page_size: config::get().page_size,
+        fetched: Default::default(),
+        loading_status: Default::default(),
+        sort,
+        category: None,
+        owner: None,
+        append: false,
+        last_progress: Default::default(),
+        tx,
+    };
+    crates_io_api_helper::request_search_results(&params).await?;
+    let crates = params.fetched.crates();
+    println!("{}", serialize_crates(&crates, format)?.trim_end());
+    Ok(())
+}
+
+fn serialize_crates(crates: &[crates_io_api::Crate], format: ExportFormat) -> Result<String> {
+    let records = crates.iter().map(ExportedCrate::from).collect::<Vec<_>>();
+    Ok(match format {
+        ExportFormat::Json => serde_json::to_string_pretty(&records)?,
+        ExportFormat::Csv => to_csv(&records),
+    })
+}
+
+fn to_csv(records: &[ExportedCrate]) -> String {
+    let mut csv = String::from("name,description,downloads,max_version,updated_at\n");
+    for record in records {
+        let fields = [
+            escape_csv_field(record.name),
+            escape_csv_field(record.description.unwrap_or_default()),
+            record.downloads.to_string(),
+            escape_csv_field(record.max_version),
+            escape_csv_field(&record.updated_at),
+        ];
+        csv.push_str(&fields.join(","));
+        csv.push('\n');
+    }
+    csv
+}
+
+/// Quotes a field if it contains a delimiter, quote or newline.
+fn escape_csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn dependency(crate_id: &str, kind: &str, req: &str, optional: bool) -> serde_json::Value {
+        json!({
+            "crate_id": crate_id,
+            "default_features": true,
+            "downloads": 0,
+            "features": [],
+            "id": 0,
+            "kind": kind,
+            "optional": optional,
+            "req": req,
+            "target": null,
+            "version_id": 0,
+        })
+    }
+
+    fn version(num: &str, dependencies: Vec<serde_json::Value>) -> serde_json::Value {
+        json!({
+            "crate": "ratatui",
+            "created_at": "2024-01-01T00:00:00Z",
+            "updated_at": "2024-01-01T00:00:00Z",
+            "dl_path": "",
+            "downloads": 0,
+            "features": {},
+            "id": 0,
+            "num": num,
+            "yanked": false,
+            "license": null,
+            "readme_path": null,
+            "links": { "dependencies": "", "version_downloads": "" },
+            "crate_size": null,
+            "published_by": null,
+            "rust_version": null,
+            "author_names": [],
+            "dependencies": dependencies,
+            "checksum": "",
+        })
+    }
+
+    fn full_crate() -> crates_io_api::FullCrate {
+        serde_json::from_value(json!({
+            "id": "ratatui",
+            "name": "ratatui",
+            "description": " A library to build rich terminal UIs \n",
+            "license": "MIT",
+            "documentation": "https://docs.rs/ratatui",
+            "homepage": null,
+            "repository": "https://github.com/ratatui/ratatui",
+            "total_downloads": 1234567,
+            "recent_downloads": null,
+            "max_version": "0.31.0-beta.1",
+            "max_stable_version": "0.30.0",
+            "created_at": "2024-01-01T00:00:00Z",
+            "updated_at": "2024-01-01T00:00:00Z",
+            "categories": [],
+            "keywords": [
+                { "id": "tui", "keyword": "tui", "crates_cnt": 1, "created_at": "2024-01-01T00:00:00Z" },
+            ],
+            "downloads": { "version_downloads": [], "meta": { "extra_downloads": [] } },
+            "owners": [],
+            "reverse_dependencies": { "dependencies": [], "meta": { "total": 0 } },
+            "versions": [
+                version("0.31.0-beta.1", vec![dependency("unstable", "normal", "^1", false)]),
+                version("0.30.0", vec![
+                    dependency("unicode-width", "normal", "^0.2", false),
+                    dependency("crossterm", "normal", "^0.29", true),
+                    dependency("pretty_assertions", "dev", "^1", false),
+                ]),
+            ],
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn crate_markdown_lists_the_stable_version() {
+        config::init_for_tests();
+        assert_eq!(
+            crate_markdown(&full_crate()),
+            "# ratatui
+
+A library to build rich terminal UIs
+
+- **Version:** 0.30.0
+- **Downloads:** 1,234,567
+- **License:** MIT
+
+## Links
+
+- [crates.io](https://crates.io/crates/ratatui)
+- [Documentation](https://docs.rs/ratatui)
+- [Repository](https://github.com/ratatui/ratatui)
+
+## Keywords
+
+`tui`
+
+## Dependencies
+
+- `crossterm` ^0.29 (optional)
+- `unicode-width` ^0.2
+
+## Dev dependencies
+
+- `pretty_assertions` ^1
+"
+        );
+    }
+}
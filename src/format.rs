@@ -0,0 +1,128 @@
+//! Formatting of timestamps and numbers shared by the widgets.
+
+use chrono::{DateTime, Utc};
+use num_format::{Locale, ToFormattedString};
+
+use crate::config;
+
+/// Formats a timestamp the way the config asks for: relative to now if
+/// `relative_time` is set, otherwise with `date_format`.
+pub fn format_timestamp(timestamp: DateTime<Utc>) -> String {
+    let config = config::get();
+    if config.relative_time {
+        relative_time(timestamp, Utc::now())
+            .unwrap_or_else(|| timestamp.format(&config.date_format).to_string())
+    } else {
+        timestamp.format(&config.date_format).to_string()
+    }
+}
+
+/// Describes how long before `now` the timestamp was, e.g. "3 days ago".
+///
+/// Returns `None` for timestamps more than a minute in the future, which
+/// can't be described this way.
+pub fn relative_time(timestamp: DateTime<Utc>, now: DateTime<Utc>) -> Option<String> {
+    let age = now.signed_duration_since(timestamp);
+    if age.num_seconds() < -60 {
+        return None;
+    }
+    let (count, unit) = if age.num_minutes() < 1 {
+        return Some("just now".into());
+    } else if age.num_hours() < 1 {
+        (age.num_minutes(), "minute")
+    } else if age.num_days() < 1 {
+        (age.num_hours(), "hour")
+    } else if age.num_days() < 30 {
+        (age.num_days(), "day")
+    } else if age.num_days() < 365 {
+        (age.num_days() / 30, "month")
+    } else {
+        (age.num_days() / 365, "year")
+    };
+    let plural = if count == 1 { "" } else { "s" };
+    Some(format!("{count} {unit}{plural} ago"))
+}
+
+/// Formats a download count the way the config asks for: compactly, e.g.
+/// "12.3M", if `compact_downloads` is set, otherwise in full.
+pub fn format_downloads(count: u64) -> String {
+    if config::get().compact_downloads {
+        humanize_count(count)
+    } else {
+        format_count(count)
+    }
+}
+
+/// Formats a count in full with the separators of `number_locale`, e.g.
+/// "12,345,678".
+pub fn format_count(count: u64) -> String {
+    let locale = Locale::from_name(&config::get().number_locale).unwrap_or(Locale::en);
+    count.to_formatted_string(&locale)
+}
+
+/// Formats a count with a unit suffix and at most three significant digits,
+/// e.g. "345K" or "12.3M".
+pub fn humanize_count(count: u64) -> String {
+    const UNITS: [&str; 4] = ["K", "M", "B", "T"];
+    if count < 1000 {
+        return count.to_string();
+    }
+    let mut value = count as f64;
+    let mut unit = "";
+    for next_unit in UNITS {
+        value /= 1000.0;
+        unit = next_unit;
+        // move on if rounding would show "1000"
+        if value < 999.5 {
+            break;
+        }
+    }
+    if value < 99.95 {
+        let formatted = format!("{value:.1}");
+        format!("{}{unit}", formatted.trim_end_matches(".0"))
+    } else {
+        format!("{value:.0}{unit}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Duration, TimeZone};
+
+    use super::*;
+
+    #[test]
+    fn relative_time_picks_the_largest_unit() {
+        let now = Utc.with_ymd_and_hms(2024, 6, 1, 12, 0, 0).unwrap();
+        let ago = |duration| relative_time(now - duration, now);
+        assert_eq!(ago(Duration::seconds(5)).as_deref(), Some("just now"));
+        assert_eq!(ago(Duration::minutes(1)).as_deref(), Some("1 minute ago"));
+        assert_eq!(ago(Duration::hours(5)).as_deref(), Some("5 hours ago"));
+        assert_eq!(ago(Duration::days(3)).as_deref(), Some("3 days ago"));
+        assert_eq!(ago(Duration::days(65)).as_deref(), Some("2 months ago"));
+        assert_eq!(ago(Duration::days(3650)).as_deref(), Some("10 years ago"));
+    }
+
+    #[test]
+    fn relative_time_handles_future_timestamps() {
+        let now = Utc.with_ymd_and_hms(2024, 6, 1, 12, 0, 0).unwrap();
+        assert_eq!(
+            relative_time(now + Duration::seconds(30), now).as_deref(),
+            Some("just now")
+        );
+        assert_eq!(relative_time(now + Duration::days(1), now), None);
+    }
+
+    #[test]
+    fn humanize_count_keeps_three_significant_digits() {
+        assert_eq!(humanize_count(0), "0");
+        assert_eq!(humanize_count(999), "999");
+        assert_eq!(humanize_count(1000), "1K");
+        assert_eq!(humanize_count(1500), "1.5K");
+        assert_eq!(humanize_count(99_960), "100K");
+        assert_eq!(humanize_count(345_000), "345K");
+        assert_eq!(humanize_count(999_999), "1M");
+        assert_eq!(humanize_count(12_345_678), "12.3M");
+        assert_eq!(humanize_count(u64::MAX), "18446744T");
+    }
+}
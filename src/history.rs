@@ -0,0 +1,107 @@
+use crate::{app::Mode, crates_io_api_helper::Owner};
+
+/// A place in the app that `NavigateBack` and `NavigateForward` return to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NavigationSnapshot {
+    pub mode: Mode,
+    pub selection: Option<usize>,
+
+    /// The search shown in the results table, only recorded for the picker
+    /// modes.
+    pub search: Option<SearchSnapshot>,
+}
+
+/// The parameters of the search shown in the results table.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SearchSnapshot {
+    pub query: String,
+    pub category: Option<String>,
+    pub owner: Option<Owner>,
+    pub page: u64,
+}
+
+/// Browser-like back and forward stacks.
+#[derive(Debug)]
+pub struct NavigationHistory<T> {
+    back: Vec<T>,
+    forward: Vec<T>,
+}
+
+impl<T> Default for NavigationHistory<T> {
+    fn default() -> Self {
+        Self {
+            back: Vec::new(),
+            forward: Vec::new(),
+        }
+    }
+}
+
+impl<T: PartialEq> NavigationHistory<T> {
+    /// Records `current` before navigating away from it and forgets the
+    /// entries that could be navigated forward to.
+    ///
+    /// At most `capacity` entries are kept, dropping the oldest first.
+    pub fn push(&mut self, current: T, capacity: usize) {
+        self.forward.clear();
+        if self.back.last() != Some(&current) {
+            self.back.push(current);
+        }
+        let excess = self.back.len().saturating_sub(capacity);
+        self.back.drain(..excess);
+    }
+
+    /// Returns the entry to go back to, remembering `current` to go forward to
+    /// again. Entries equal to `current` are skipped.
+    pub fn back(&mut self, current: T) -> Option<T> {
+        step(&mut self.back, &mut self.forward, current)
+    }
+
+    /// Returns the entry to go forward to, remembering `current` to go back to
+    /// again. Entries equal to `current` are skipped.
+    pub fn forward(&mut self, current: T) -> Option<T> {
+        step(&mut self.forward, &mut self.back, current)
+    }
+}
+
+fn step<T: PartialEq>(from: &mut Vec<T>, to: &mut Vec<T>, current: T) -> Option<T> {
+    while let Some(entry) = from.pop() {
+        if entry != current {
+            to.push(current);
+            return Some(entry);
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn back_and_forward_retrace_the_path() {
+        let mut history = NavigationHistory::default();
+        history.push(1, 10);
+        history.push(2, 10);
+        assert_eq!(history.back(3), Some(2));
+        assert_eq!(history.back(2), Some(1));
+        assert_eq!(history.back(1), None);
+        assert_eq!(history.forward(1), Some(2));
+        assert_eq!(history.forward(2), Some(3));
+        assert_eq!(history.forward(3), None);
+    }
+
+    #[test]
+    fn push_drops_forward_entries_and_respects_capacity() {
+        let mut history = NavigationHistory::default();
+        for entry in 1..=4 {
+            history.push(entry, 3);
+        }
+        history.push(4, 3);
+        assert_eq!(history.back(5), Some(4));
+        history.push(4, 3);
+        assert_eq!(history.forward(4), None);
+        assert_eq!(history.back(4), Some(3));
+        assert_eq!(history.back(3), Some(2));
+        assert_eq!(history.back(2), None);
+    }
+}
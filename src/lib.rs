@@ -0,0 +1,56 @@
+//! A TUI for searching and browsing crates.io.
+//!
+//! The binary is a thin wrapper over this library, which can also be used to
+//! embed the search UI in another application or to query crates.io without
+//! a terminal.
+//!
+//! The stable API is:
+//!
+//! - [`app::App`], which runs the UI on a [`ratatui::DefaultTerminal`],
+//!   optionally starting with a query and a [`config::SortBy`]
+//! - [`events::Events`], the stream of terminal and timer events driving it
+//! - [`config`], which must be initialized before anything else is used. The
+//!   binary uses [`config::init`], which needs its command line; an embedding
+//!   application passes its own [`config::Config`] to [`config::init_with`],
+//!   e.g. one built from [`config::defaults`]
+//! - [`crates_io_api_helper`], whose request functions fetch search results,
+//!   crate details and the crates.io summary, failing with
+//!   [`crates_io_api_helper::ApiError`]. Searches report progress on the
+//!   [`action::Action`] sender in their
+//!   [`crates_io_api_helper::SearchParameters`]; a headless caller can pass
+//!   one whose receiver is dropped
+//!
+//! The hidden modules are only public for the binary and may change at any
+//! time.
+
+pub mod action;
+pub mod app;
+mod bookmarks;
+mod browser;
+mod cache;
+#[doc(hidden)]
+pub mod cli;
+mod clipboard;
+mod command;
+pub mod config;
+pub mod crates_io_api_helper;
+#[doc(hidden)]
+pub mod errors;
+pub mod events;
+#[doc(hidden)]
+pub mod export;
+mod format;
+mod history;
+mod local_deps;
+#[doc(hidden)]
+pub mod logging;
+mod qr_code;
+mod runtime;
+mod serde_helper;
+mod session;
+mod spinner;
+#[cfg(test)]
+mod test_utils;
+#[doc(hidden)]
+pub mod tui;
+mod widgets;
@@ -0,0 +1,199 @@
+use std::{
+    collections::BTreeSet,
+    path::{Path, PathBuf},
+};
+
+use color_eyre::eyre::Result;
+use serde::Deserialize;
+use tracing::{debug, warn};
+
+/// The tables of a manifest that list dependencies.
+const DEPENDENCY_TABLES: [&str; 3] = ["dependencies", "dev-dependencies", "build-dependencies"];
+
+/// Returns the names of the crates the project in `directory` depends on,
+/// including those of its workspace members.
+///
+/// A missing or malformed `Cargo.toml` results in an empty set, since most
+/// directories the app is started in aren't Rust projects.
+pub fn load(directory: &Path) -> BTreeSet<String> {
+    let Some(manifest) = read_manifest(&directory.join("Cargo.toml")) else {
+        return BTreeSet::new();
+    };
+    let mut names = dependency_names(&manifest);
+    for member in workspace_members(directory, &manifest) {
+        if let Some(manifest) = read_manifest(&member.join("Cargo.toml")) {
+            names.extend(dependency_names(&manifest));
+        }
+    }
+    debug!(
+        "Found {} dependencies in {}",
+        names.len(),
+        directory.display()
+    );
+    names
+}
+
+fn read_manifest(path: &Path) -> Option<toml::Table> {
+    let contents = std::fs::read_to_string(path).ok()?;
+    toml::from_str(&contents)
+        .inspect_err(|err| warn!("Unable to parse {}: {err}", path.display()))
+        .ok()
+}
+
+/// Collects the dependencies of a manifest, including target specific and
+/// workspace dependencies, by the name of the crate on crates.io.
+fn dependency_names(manifest: &toml::Table) -> BTreeSet<String> {
+    let targets = manifest
+        .get("target")
+        .and_then(toml::Value::as_table)
+        .into_iter()
+        .flat_map(|targets| targets.values().filter_map(toml::Value::as_table));
+    let workspace = manifest.get("workspace").and_then(toml::Value::as_table);
+    std::iter::once(manifest)
+        .chain(targets)
+        .chain(workspace)
+        .flat_map(|table| {
+            DEPENDENCY_TABLES
+                .iter()
+                .filter_map(|name| table.get(*name).and_then(toml::Value::as_table))
+        })
+        .flat_map(|dependencies| {
+            dependencies.iter().map(|(name, dependency)| {
+                // renamed dependencies name the crate in `package`
+                dependency
+                    .get("package")
+                    .and_then(toml::Value::as_str)
+                    .unwrap_or(name)
+                    .to_string()
+            })
+        })
+        .collect()
+}
+
+/// Resolves the `workspace.members` of a manifest, expanding a trailing `*`.
+fn workspace_members(directory: &Path, manifest: &toml::Table) -> Vec<PathBuf> {
+    let members = manifest
+        .get("workspace")
+        .and_then(|workspace| workspace.get("members"))
+        .and_then(toml::Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(toml::Value::as_str);
+    members
+        .flat_map(|member| match member.strip_suffix('*') {
+            Some(parent) => std::fs::read_dir(directory.join(parent))
+                .into_iter()
+                .flatten()
+                .filter_map(|entry| entry.ok())
+                .map(|entry| entry.path())
+                .filter(|path| path.is_dir())
+                .collect(),
+            None => vec![directory.join(member)],
+        })
+        .collect()
+}
+
+/// A package pinned in `Cargo.lock`.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct LockedPackage {
+    pub name: String,
+    pub version: String,
+    #[serde(default)]
+    source: Option<String>,
+}
+
+impl LockedPackage {
+    /// Whether the package comes from crates.io, as opposed to a path, git
+    /// repository or another registry.
+    fn is_from_crates_io(&self) -> bool {
+        self.source.as_deref().is_some_and(|source| {
+            source.contains("github.com/rust-lang/crates.io-index")
+                || source.contains("index.crates.io")
+        })
+    }
+}
+
+#[derive(Deserialize)]
+struct Lockfile {
+    #[serde(default)]
+    package: Vec<LockedPackage>,
+}
+
+/// Reads the crates.io packages pinned in the `Cargo.lock` in `directory`.
+pub fn load_lockfile(directory: &Path) -> Result<Vec<LockedPackage>> {
+    let contents = std::fs::read_to_string(directory.join("Cargo.lock"))?;
+    parse_lockfile(&contents)
+}
+
+fn parse_lockfile(contents: &str) -> Result<Vec<LockedPackage>> {
+    let lockfile: Lockfile = toml::from_str(contents)?;
+    Ok(lockfile
+        .package
+        .into_iter()
+        .filter(LockedPackage::is_from_crates_io)
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn collects_dependency_names() {
+        let manifest = toml::from_str(
+            r#"
+            [dependencies]
+            serde = "1"
+            tui = { package = "ratatui", version = "0.30" }
+
+            [dev-dependencies]
+            pretty_assertions = "1"
+
+            [target.'cfg(unix)'.dependencies]
+            libc = "0.2"
+
+            [workspace.dependencies]
+            tokio = "1"
+            "#,
+        )
+        .unwrap();
+        assert_eq!(
+            dependency_names(&manifest),
+            BTreeSet::from(
+                ["libc", "pretty_assertions", "ratatui", "serde", "tokio"].map(String::from)
+            )
+        );
+    }
+
+    #[test]
+    fn parses_crates_io_packages_from_lockfile() {
+        let packages = parse_lockfile(
+            r#"
+            version = 4
+
+            [[package]]
+            name = "crates-tui"
+            version = "0.1.0"
+
+            [[package]]
+            name = "serde"
+            version = "1.0.200"
+            source = "registry+https://github.com/rust-lang/crates.io-index"
+            checksum = "0000"
+
+            [[package]]
+            name = "ratatui"
+            version = "0.30.0"
+            source = "git+https://github.com/ratatui/ratatui#0000"
+            "#,
+        )
+        .unwrap();
+        assert_eq!(
+            packages
+                .iter()
+                .map(|package| (package.name.as_str(), package.version.as_str()))
+                .collect::<Vec<_>>(),
+            [("serde", "1.0.200")]
+        );
+    }
+}
@@ -1,25 +1,58 @@
+use std::{
+    fmt,
+    fs::{File, OpenOptions},
+    io::{self, Write},
+    path::{Path, PathBuf},
+    sync::Mutex,
+};
+
+use chrono::{Local, NaiveDate};
 use color_eyre::eyre::Result;
-use tracing::level_filters::LevelFilter;
+use serde::{Deserialize, Serialize};
+use strum::Display;
+use tracing::{
+    Event, Subscriber,
+    field::{Field, Visit},
+    level_filters::LevelFilter,
+};
 use tracing_error::ErrorLayer;
 use tracing_subscriber::{
-    self, prelude::__tracing_subscriber_SubscriberExt, util::SubscriberInitExt,
+    self, Layer,
+    fmt::{FmtContext, FormatEvent, FormatFields, FormattedFields, format::Writer},
+    prelude::__tracing_subscriber_SubscriberExt,
+    registry::LookupSpan,
+    util::SubscriberInitExt,
 };
 
 use crate::config;
 
+/// How events are written to the log file.
+#[derive(Debug, Default, Display, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum LogFormat {
+    /// Human readable lines.
+    #[default]
+    Pretty,
+    /// A JSON object per line, for log tooling.
+    Json,
+}
+
 pub fn init() -> Result<()> {
     let config = config::get();
     let directory = config.data_dir.clone();
     std::fs::create_dir_all(directory.clone())?;
-    let log_file = format!("{}.log", env!("CARGO_PKG_NAME"));
-    let log_path = directory.join(log_file);
-    let log_file = std::fs::File::create(log_path)?;
+    let log_file = RollingFile::open(directory, config.log_max_files, config.log_max_bytes)?;
     let file_subscriber = tracing_subscriber::fmt::layer()
         .with_file(true)
         .with_line_number(true)
-        .with_writer(log_file)
+        .with_writer(Mutex::new(log_file))
         .with_target(false)
         .with_ansi(false);
+    let file_subscriber = match config.log_format {
+        LogFormat::Pretty => file_subscriber.boxed(),
+        LogFormat::Json => file_subscriber.event_format(JsonFormat).boxed(),
+    };
     tracing_subscriber::registry()
         .with(file_subscriber)
         .with(ErrorLayer::default())
@@ -34,6 +67,204 @@ pub fn init() -> Result<()> {
     Ok(())
 }
 
+/// Writes each event as a line of JSON with its level, target, source
+/// location, fields and the spans it happened in, outermost first.
+///
+/// This mirrors the layout of `tracing-subscriber`'s `json` feature, which
+/// needs `tracing-serde`.
+struct JsonFormat;
+
+impl<S, N> FormatEvent<S, N> for JsonFormat
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+    N: for<'a> FormatFields<'a> + 'static,
+{
+    fn format_event(
+        &self,
+        ctx: &FmtContext<'_, S, N>,
+        mut writer: Writer<'_>,
+        event: &Event<'_>,
+    ) -> fmt::Result {
+        let metadata = event.metadata();
+        let mut fields = JsonFields::default();
+        event.record(&mut fields);
+        let spans = ctx
+            .event_scope()
+            .into_iter()
+            .flat_map(|scope| scope.from_root())
+            .map(|span| {
+                let extensions = span.extensions();
+                let fields = extensions
+                    .get::<FormattedFields<N>>()
+                    .map(|fields| fields.fields.clone())
+                    .unwrap_or_default();
+                serde_json::json!({ "name": span.name(), "fields": fields })
+            })
+            .collect::<Vec<_>>();
+        let line = serde_json::json!({
+            "timestamp": Local::now().to_rfc3339(),
+            "level": metadata.level().as_str(),
+            "target": metadata.target(),
+            "file": metadata.file(),
+            "line": metadata.line(),
+            "fields": fields.0,
+            "span": spans.last(),
+            "spans": spans,
+        });
+        writeln!(writer, "{line}")
+    }
+}
+
+/// Collects the fields of an event, keeping numbers and booleans as such.
+#[derive(Default)]
+struct JsonFields(serde_json::Map<String, serde_json::Value>);
+
+impl Visit for JsonFields {
+    fn record_f64(&mut self, field: &Field, value: f64) {
+        self.0.insert(field.name().into(), value.into());
+    }
+
+    fn record_i64(&mut self, field: &Field, value: i64) {
+        self.0.insert(field.name().into(), value.into());
+    }
+
+    fn record_u64(&mut self, field: &Field, value: u64) {
+        self.0.insert(field.name().into(), value.into());
+    }
+
+    fn record_bool(&mut self, field: &Field, value: bool) {
+        self.0.insert(field.name().into(), value.into());
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.0.insert(field.name().into(), value.into());
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        self.0
+            .insert(field.name().into(), format!("{value:?}").into());
+    }
+}
+
+/// A log file per day, e.g. `crates-tui.2024-01-31.log`, appended to across
+/// sessions.
+///
+/// Once a file holds `max_bytes` the day continues in the next one, e.g.
+/// `crates-tui.2024-01-31.1.log`. Only the newest `max_files` files are kept.
+struct RollingFile {
+    directory: PathBuf,
+    date: NaiveDate,
+    index: u32,
+    file: File,
+    written: u64,
+    max_files: usize,
+    max_bytes: u64,
+}
+
+impl RollingFile {
+    fn open(directory: PathBuf, max_files: usize, max_bytes: u64) -> io::Result<Self> {
+        let date = Local::now().date_naive();
+        // carry on with the newest file of the day
+        let index = log_files(&directory)?
+            .into_iter()
+            .filter(|(file_date, _)| *file_date == date)
+            .map(|(_, index)| index)
+            .max()
+            .unwrap_or_default();
+        let (file, written) = open_log_file(&directory, date, index)?;
+        remove_old_log_files(&directory, max_files)?;
+        Ok(Self {
+            directory,
+            date,
+            index,
+            file,
+            written,
+            max_files,
+            max_bytes,
+        })
+    }
+
+    /// Switches to the file of `date` numbered `index`.
+    fn roll(&mut self, date: NaiveDate, index: u32) -> io::Result<()> {
+        (self.file, self.written) = open_log_file(&self.directory, date, index)?;
+        self.date = date;
+        self.index = index;
+        remove_old_log_files(&self.directory, self.max_files)
+    }
+}
+
+impl Write for RollingFile {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let today = Local::now().date_naive();
+        if today != self.date {
+            self.roll(today, 0)?;
+        } else if self.max_bytes > 0
+            && self.written > 0
+            && self.written + buf.len() as u64 > self.max_bytes
+        {
+            self.roll(today, self.index + 1)?;
+        }
+        let written = self.file.write(buf)?;
+        self.written += written as u64;
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.file.flush()
+    }
+}
+
+fn log_file_name(date: NaiveDate, index: u32) -> String {
+    let date = date.format("%Y-%m-%d");
+    match index {
+        0 => format!("{}.{date}.log", env!("CARGO_PKG_NAME")),
+        index => format!("{}.{date}.{index}.log", env!("CARGO_PKG_NAME")),
+    }
+}
+
+/// The date and number of a log file named by [`log_file_name`].
+fn parse_log_file_name(name: &str) -> Option<(NaiveDate, u32)> {
+    let name = name
+        .strip_prefix(concat!(env!("CARGO_PKG_NAME"), "."))?
+        .strip_suffix(".log")?;
+    let (date, index) = match name.split_once('.') {
+        Some((date, index)) => (date, index.parse().ok()?),
+        None => (name, 0),
+    };
+    Some((NaiveDate::parse_from_str(date, "%Y-%m-%d").ok()?, index))
+}
+
+/// The dates and numbers of the log files in `directory`, oldest first.
+fn log_files(directory: &Path) -> io::Result<Vec<(NaiveDate, u32)>> {
+    let mut log_files = std::fs::read_dir(directory)?
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| parse_log_file_name(&entry.file_name().to_string_lossy()))
+        .collect::<Vec<_>>();
+    log_files.sort();
+    Ok(log_files)
+}
+
+/// Opens the log file of `date` numbered `index` for appending, returning its
+/// current size.
+fn open_log_file(directory: &Path, date: NaiveDate, index: u32) -> io::Result<(File, u64)> {
+    let file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(directory.join(log_file_name(date, index)))?;
+    let size = file.metadata()?.len();
+    Ok((file, size))
+}
+
+/// Deletes all but the newest `max_files` log files in `directory`.
+fn remove_old_log_files(directory: &Path, max_files: usize) -> io::Result<()> {
+    let log_files = log_files(directory)?;
+    let excess = log_files.len().saturating_sub(max_files);
+    for &(date, index) in &log_files[..excess] {
+        std::fs::remove_file(directory.join(log_file_name(date, index)))?;
+    }
+    Ok(())
+}
+
 /// Similar to the `std::dbg!` macro, but generates `tracing` events rather
 /// than printing to stdout.
 ///
@@ -61,3 +292,79 @@ macro_rules! trace_dbg {
         trace_dbg!(level: tracing::Level::DEBUG, $ex)
     };
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn json_lines_hold_the_target_and_spans() {
+        let buffer = std::sync::Arc::new(Mutex::new(Vec::new()));
+        let writer = buffer.clone();
+        let subscriber = tracing_subscriber::registry().with(
+            tracing_subscriber::fmt::layer()
+                .with_writer(move || SharedBuffer(writer.clone()))
+                .with_ansi(false)
+                .event_format(JsonFormat),
+        );
+        tracing::subscriber::with_default(subscriber, || {
+            let _search = tracing::info_span!("search", page = 2).entered();
+            tracing::info!(results = 25, "loaded");
+        });
+
+        let line = String::from_utf8(buffer.lock().unwrap().clone()).unwrap();
+        let line = serde_json::from_str::<serde_json::Value>(&line).unwrap();
+        assert_eq!(line["target"], module_path!());
+        assert_eq!(line["fields"]["results"], 25);
+        assert_eq!(line["fields"]["message"], "loaded");
+        assert_eq!(line["span"]["name"], "search");
+        assert_eq!(line["spans"][0]["fields"], "page=2");
+    }
+
+    struct SharedBuffer(std::sync::Arc<Mutex<Vec<u8>>>);
+
+    impl Write for SharedBuffer {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.lock().unwrap().write(buf)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn keeps_the_newest_log_files() {
+        let directory =
+            std::env::temp_dir().join(format!("crates-tui-logs-{}", std::process::id()));
+        std::fs::create_dir_all(&directory).unwrap();
+        let files = [
+            ("2024-01-01", 0),
+            ("2024-01-02", 0),
+            ("2024-01-02", 10),
+            ("2024-01-02", 2),
+        ];
+        for (date, index) in files {
+            let date = NaiveDate::parse_from_str(date, "%Y-%m-%d").unwrap();
+            std::fs::write(directory.join(log_file_name(date, index)), "").unwrap();
+        }
+        std::fs::write(directory.join("bookmarks.toml"), "").unwrap();
+
+        remove_old_log_files(&directory, 2).unwrap();
+
+        let mut remaining = std::fs::read_dir(&directory)
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().to_string_lossy().into_owned())
+            .collect::<Vec<_>>();
+        remaining.sort();
+        std::fs::remove_dir_all(&directory).unwrap();
+        assert_eq!(
+            remaining,
+            [
+                "bookmarks.toml",
+                "crates-tui.2024-01-02.10.log",
+                "crates-tui.2024-01-02.2.log"
+            ]
+        );
+    }
+}
@@ -1,16 +1,4 @@
-mod action;
-mod app;
-mod cli;
-mod command;
-mod config;
-mod crates_io_api_helper;
-mod errors;
-mod events;
-mod logging;
-mod serde_helper;
-mod widgets;
-
-use app::App;
+use crates_tui::{app::App, cli, config, errors, export, logging};
 
 fn main() -> color_eyre::Result<()> {
     let cli = cli::parse();
@@ -19,10 +7,15 @@ fn main() -> color_eyre::Result<()> {
     errors::install_hooks()?;
 
     if cli.print_default_config {
-        println!("{}", toml::to_string_pretty(config::get())?);
+        println!("{}", toml::to_string_pretty(&*config::get())?);
         return Ok(());
     }
 
-    let mut app = App::new(cli.query);
+    if let Some(format) = cli.output_format() {
+        let sort = cli.sort.unwrap_or(config::get().default_sort).into();
+        return export::print_search_results(cli.query.unwrap_or_default(), sort, format);
+    }
+
+    let mut app = App::new(cli.query, cli.sort);
     ratatui::run(|tui| app.run(tui))
 }
@@ -0,0 +1,624 @@
+//! A minimal QR code encoder for sharing crate URLs.
+//!
+//! Only what a URL needs is supported: byte mode, the medium error correction
+//! level and versions 1 to 10, which hold up to 213 bytes.
+
+/// The largest version encoded, 57 modules wide.
+const MAX_VERSION: usize = 10;
+
+/// The error correction codewords per block of each version at level M,
+/// indexed by version.
+const ECC_CODEWORDS_PER_BLOCK: [usize; MAX_VERSION + 1] =
+    [0, 10, 16, 26, 18, 24, 16, 18, 22, 22, 26];
+
+/// The number of error correction blocks of each version at level M, indexed
+/// by version.
+const NUM_ERROR_CORRECTION_BLOCKS: [usize; MAX_VERSION + 1] = [0, 1, 1, 1, 2, 2, 4, 4, 4, 5, 5];
+
+/// The format bits identifying error correction level M.
+const ECC_LEVEL_M: u32 = 0b00;
+
+/// A QR code as a square grid of dark and light modules, without a quiet
+/// zone.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QrCode {
+    size: usize,
+    modules: Vec<bool>,
+}
+
+impl QrCode {
+    /// Encodes `data` in the smallest version that fits, or returns `None` if
+    /// it is too long.
+    pub fn encode(data: &[u8]) -> Option<Self> {
+        let version = (1..=MAX_VERSION).find(|&version| {
+            let bits = 4 + char_count_bits(version) + data.len() * 8;
+            bits <= num_data_codewords(version) * 8
+        })?;
+        let codewords = add_ecc_and_interleave(version, &data_codewords(version, data));
+
+        let mut builder = Builder::new(version);
+        builder.draw_function_patterns();
+        builder.draw_codewords(&codewords);
+        let mask = (0..8)
+            .min_by_key(|&mask| {
+                let mut candidate = builder.clone();
+                candidate.apply_mask(mask);
+                candidate.draw_format_bits(mask);
+                candidate.penalty()
+            })
+            .unwrap_or(0);
+        builder.apply_mask(mask);
+        builder.draw_format_bits(mask);
+        Some(Self {
+            size: builder.size,
+            modules: builder.modules,
+        })
+    }
+
+    /// The number of modules along each side.
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// Whether the module at column `x` and row `y` is dark. Modules outside
+    /// the code are light.
+    pub fn is_dark(&self, x: usize, y: usize) -> bool {
+        x < self.size && y < self.size && self.modules[y * self.size + x]
+    }
+}
+
+/// A QR code being drawn, which keeps track of the modules reserved for
+/// function patterns so that data and masks skip them.
+#[derive(Debug, Clone)]
+struct Builder {
+    version: usize,
+    size: usize,
+    modules: Vec<bool>,
+    is_function: Vec<bool>,
+}
+
+impl Builder {
+    fn new(version: usize) -> Self {
+        let size = version * 4 + 17;
+        Self {
+            version,
+            size,
+            modules: vec![false; size * size],
+            is_function: vec![false; size * size],
+        }
+    }
+
+    fn get(&self, x: usize, y: usize) -> bool {
+        self.modules[y * self.size + x]
+    }
+
+    fn set_function(&mut self, x: usize, y: usize, dark: bool) {
+        self.modules[y * self.size + x] = dark;
+        self.is_function[y * self.size + x] = true;
+    }
+
+    fn draw_function_patterns(&mut self) {
+        for i in 0..self.size {
+            self.set_function(6, i, i % 2 == 0);
+            self.set_function(i, 6, i % 2 == 0);
+        }
+        let last = self.size - 4;
+        self.draw_finder_pattern(3, 3);
+        self.draw_finder_pattern(last, 3);
+        self.draw_finder_pattern(3, last);
+
+        let positions = alignment_pattern_positions(self.version);
+        let count = positions.len();
+        for (i, &x) in positions.iter().enumerate() {
+            for (j, &y) in positions.iter().enumerate() {
+                let overlaps_finder =
+                    (i == 0 && (j == 0 || j == count - 1)) || (i == count - 1 && j == 0);
+                if !overlaps_finder {
+                    self.draw_alignment_pattern(x, y);
+                }
+            }
+        }
+
+        // reserve the format bits, drawn once the mask is chosen
+        self.draw_format_bits(0);
+        self.draw_version();
+    }
+
+    fn draw_finder_pattern(&mut self, x: usize, y: usize) {
+        for dy in -4..=4_isize {
+            for dx in -4..=4_isize {
+                let (Some(xx), Some(yy)) = (x.checked_add_signed(dx), y.checked_add_signed(dy))
+                else {
+                    continue;
+                };
+                if xx < self.size && yy < self.size {
+                    let distance = dx.abs().max(dy.abs());
+                    self.set_function(xx, yy, distance != 2 && distance != 4);
+                }
+            }
+        }
+    }
+
+    fn draw_alignment_pattern(&mut self, x: usize, y: usize) {
+        for dy in -2..=2_isize {
+            for dx in -2..=2_isize {
+                let distance = dx.abs().max(dy.abs());
+                self.set_function(
+                    x.saturating_add_signed(dx),
+                    y.saturating_add_signed(dy),
+                    distance != 1,
+                );
+            }
+        }
+    }
+
+    fn draw_format_bits(&mut self, mask: u32) {
+        let bits = format_bits(mask);
+        let bit = |i: usize| (bits >> i) & 1 != 0;
+        let size = self.size;
+
+        // around the top left finder
+        for i in 0..=5 {
+            self.set_function(8, i, bit(i));
+        }
+        self.set_function(8, 7, bit(6));
+        self.set_function(8, 8, bit(7));
+        self.set_function(7, 8, bit(8));
+        for i in 9..15 {
+            self.set_function(14 - i, 8, bit(i));
+        }
+
+        // split between the top right and bottom left finders
+        for i in 0..8 {
+            self.set_function(size - 1 - i, 8, bit(i));
+        }
+        for i in 8..15 {
+            self.set_function(8, size - 15 + i, bit(i));
+        }
+        self.set_function(8, size - 8, true);
+    }
+
+    fn draw_version(&mut self) {
+        if self.version < 7 {
+            return;
+        }
+        let bits = version_bits(self.version);
+        for i in 0..18 {
+            let dark = (bits >> i) & 1 != 0;
+            let a = self.size - 11 + i % 3;
+            let b = i / 3;
+            self.set_function(a, b, dark);
+            self.set_function(b, a, dark);
+        }
+    }
+
+    /// Places the codewords in the zig-zag order of the standard, two columns
+    /// at a time from the bottom right corner.
+    fn draw_codewords(&mut self, codewords: &[u8]) {
+        let mut i = 0;
+        let mut right = self.size - 1;
+        while right >= 1 {
+            if right == 6 {
+                right = 5;
+            }
+            for vertical in 0..self.size {
+                for j in 0..2 {
+                    let x = right - j;
+                    let upward = (right + 1) & 2 == 0;
+                    let y = if upward {
+                        self.size - 1 - vertical
+                    } else {
+                        vertical
+                    };
+                    if !self.is_function[y * self.size + x] && i < codewords.len() * 8 {
+                        self.modules[y * self.size + x] =
+                            (codewords[i >> 3] >> (7 - (i & 7))) & 1 != 0;
+                        i += 1;
+                    }
+                }
+            }
+            if right < 2 {
+                break;
+            }
+            right -= 2;
+        }
+    }
+
+    fn apply_mask(&mut self, mask: u32) {
+        for y in 0..self.size {
+            for x in 0..self.size {
+                let invert = match mask {
+                    0 => (x + y) % 2 == 0,
+                    1 => y % 2 == 0,
+                    2 => x % 3 == 0,
+                    3 => (x + y) % 3 == 0,
+                    4 => (x / 3 + y / 2) % 2 == 0,
+                    5 => x * y % 2 + x * y % 3 == 0,
+                    6 => (x * y % 2 + x * y % 3) % 2 == 0,
+                    _ => ((x + y) % 2 + x * y % 3) % 2 == 0,
+                };
+                let index = y * self.size + x;
+                if invert && !self.is_function[index] {
+                    self.modules[index] = !self.modules[index];
+                }
+            }
+        }
+    }
+
+    /// Scores how hard the code is to scan, from runs of the same color,
+    /// 2x2 blocks, finder-like patterns and the balance of dark modules.
+    fn penalty(&self) -> usize {
+        let size = self.size;
+        let mut penalty = 0;
+        for transposed in [false, true] {
+            let get = |a: usize, b: usize| {
+                if transposed {
+                    self.get(b, a)
+                } else {
+                    self.get(a, b)
+                }
+            };
+            for b in 0..size {
+                let line = (0..size).map(|a| get(a, b)).collect::<Vec<_>>();
+                let mut run = 1;
+                for a in 1..=size {
+                    if a < size && line[a] == line[a - 1] {
+                        run += 1;
+                        continue;
+                    }
+                    if run >= 5 {
+                        penalty += run - 2;
+                    }
+                    run = 1;
+                }
+                const FINDER: [bool; 11] = [
+                    true, false, true, true, true, false, true, false, false, false, false,
+                ];
+                for window in line.windows(FINDER.len()) {
+                    if window == FINDER || window.iter().eq(FINDER.iter().rev()) {
+                        penalty += 40;
+                    }
+                }
+            }
+        }
+        for y in 0..size - 1 {
+            for x in 0..size - 1 {
+                let color = self.get(x, y);
+                if color == self.get(x + 1, y)
+                    && color == self.get(x, y + 1)
+                    && color == self.get(x + 1, y + 1)
+                {
+                    penalty += 3;
+                }
+            }
+        }
+        let dark = self.modules.iter().filter(|&&dark| dark).count();
+        let total = size * size;
+        // the number of 5% steps away from half of the modules being dark
+        let deviation = (dark * 20).abs_diff(total * 10).div_ceil(total);
+        penalty + deviation.saturating_sub(1) * 10
+    }
+}
+
+/// The length of the character count field in byte mode.
+fn char_count_bits(version: usize) -> usize {
+    if version <= 9 { 8 } else { 16 }
+}
+
+/// The number of modules available for codewords, i.e. those not used by
+/// function patterns, format or version information.
+fn num_raw_data_modules(version: usize) -> usize {
+    let mut result = (16 * version + 128) * version + 64;
+    if version >= 2 {
+        let num_align = version / 7 + 2;
+        result -= (25 * num_align - 10) * num_align - 55;
+        if version >= 7 {
+            result -= 36;
+        }
+    }
+    result
+}
+
+fn num_data_codewords(version: usize) -> usize {
+    num_raw_data_modules(version) / 8
+        - ECC_CODEWORDS_PER_BLOCK[version] * NUM_ERROR_CORRECTION_BLOCKS[version]
+}
+
+fn alignment_pattern_positions(version: usize) -> Vec<usize> {
+    if version == 1 {
+        return vec![];
+    }
+    let num_align = version / 7 + 2;
+    let step = (version * 8 + num_align * 3 + 5) / (num_align * 4 - 4) * 2;
+    let mut positions = vec![6];
+    let mut position = version * 4 + 17 - 7;
+    for _ in 0..num_align - 1 {
+        positions.insert(1, position);
+        position -= step;
+    }
+    positions
+}
+
+/// The 15 format bits for level M and `mask`, with their BCH error
+/// correction.
+fn format_bits(mask: u32) -> u32 {
+    let data = ECC_LEVEL_M << 3 | mask;
+    let mut remainder = data;
+    for _ in 0..10 {
+        remainder = (remainder << 1) ^ ((remainder >> 9) * 0x537);
+    }
+    (data << 10 | remainder) ^ 0x5412
+}
+
+/// The 18 version bits with their BCH error correction.
+fn version_bits(version: usize) -> u32 {
+    let version = version as u32;
+    let mut remainder = version;
+    for _ in 0..12 {
+        remainder = (remainder << 1) ^ ((remainder >> 11) * 0x1f25);
+    }
+    version << 12 | remainder
+}
+
+/// Encodes `data` as a byte mode segment padded to the data capacity of
+/// `version`.
+fn data_codewords(version: usize, data: &[u8]) -> Vec<u8> {
+    let mut bits = Vec::new();
+    let mut push = |value: usize, length: usize| {
+        bits.extend((0..length).rev().map(|i| (value >> i) & 1 != 0));
+    };
+    push(0b0100, 4);
+    push(data.len(), char_count_bits(version));
+    for &byte in data {
+        push(byte as usize, 8);
+    }
+    let capacity = num_data_codewords(version) * 8;
+    let terminator = (capacity - bits.len()).min(4);
+    bits.extend(std::iter::repeat_n(false, terminator));
+    bits.extend(std::iter::repeat_n(
+        false,
+        bits.len().next_multiple_of(8) - bits.len(),
+    ));
+
+    let mut codewords = bits
+        .chunks(8)
+        .map(|byte| byte.iter().fold(0, |acc, &bit| acc << 1 | bit as u8))
+        .collect::<Vec<_>>();
+    for pad in [0xec, 0x11].into_iter().cycle() {
+        if codewords.len() >= capacity / 8 {
+            break;
+        }
+        codewords.push(pad);
+    }
+    codewords
+}
+
+/// Splits the data into blocks, appends the error correction codewords of
+/// each and interleaves them.
+fn add_ecc_and_interleave(version: usize, data: &[u8]) -> Vec<u8> {
+    let num_blocks = NUM_ERROR_CORRECTION_BLOCKS[version];
+    let block_ecc_len = ECC_CODEWORDS_PER_BLOCK[version];
+    let raw_codewords = num_raw_data_modules(version) / 8;
+    let num_short_blocks = num_blocks - raw_codewords % num_blocks;
+    let short_block_len = raw_codewords / num_blocks;
+
+    let divisor = reed_solomon_divisor(block_ecc_len);
+    let mut blocks = Vec::with_capacity(num_blocks);
+    let mut offset = 0;
+    for i in 0..num_blocks {
+        let data_len = short_block_len - block_ecc_len + usize::from(i >= num_short_blocks);
+        let mut block = data[offset..offset + data_len].to_vec();
+        offset += data_len;
+        let ecc = reed_solomon_remainder(&block, &divisor);
+        if i < num_short_blocks {
+            // a placeholder keeping the columns aligned, skipped below
+            block.push(0);
+        }
+        block.extend(ecc);
+        blocks.push(block);
+    }
+
+    let mut result = Vec::with_capacity(raw_codewords);
+    for i in 0..blocks[0].len() {
+        for (j, block) in blocks.iter().enumerate() {
+            if i != short_block_len - block_ecc_len || j >= num_short_blocks {
+                result.push(block[i]);
+            }
+        }
+    }
+    result
+}
+
+/// The generator polynomial of the given degree, without its leading term,
+/// highest power first.
+fn reed_solomon_divisor(degree: usize) -> Vec<u8> {
+    let mut result = vec![0; degree];
+    result[degree - 1] = 1;
+    let mut root = 1;
+    for _ in 0..degree {
+        for j in 0..degree {
+            result[j] = gf_multiply(result[j], root);
+            if j + 1 < degree {
+                result[j] ^= result[j + 1];
+            }
+        }
+        root = gf_multiply(root, 0x02);
+    }
+    result
+}
+
+fn reed_solomon_remainder(data: &[u8], divisor: &[u8]) -> Vec<u8> {
+    let mut result = vec![0; divisor.len()];
+    for &byte in data {
+        let factor = byte ^ result.remove(0);
+        result.push(0);
+        for (coefficient, &term) in result.iter_mut().zip(divisor) {
+            *coefficient ^= gf_multiply(term, factor);
+        }
+    }
+    result
+}
+
+/// Multiplies in GF(2^8) modulo x^8 + x^4 + x^3 + x^2 + 1.
+fn gf_multiply(x: u8, y: u8) -> u8 {
+    let mut z: u16 = 0;
+    for i in (0..8).rev() {
+        z = (z << 1) ^ ((z >> 7) * 0x11d);
+        z ^= u16::from((y >> i) & 1) * u16::from(x);
+    }
+    z as u8
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encodes_a_crate_url() {
+        assert_eq!(format_bits(0), 0x5412);
+        assert_eq!(version_bits(7), 0x07c94);
+        // the generator of version 1-M, as exponents of 2: 0, 251, 67, 46,
+        // 61, 118, 70, 64, 94, 32, 45
+        assert_eq!(
+            reed_solomon_divisor(10),
+            [0xd8, 0xc2, 0x9f, 0x6f, 0xc7, 0x5e, 0x5f, 0x71, 0x9d, 0xc1]
+        );
+
+        let code = QrCode::encode(b"https://crates.io/crates/ratatui").unwrap();
+        assert_eq!(code.size(), 29);
+        // finder patterns in three corners, with their separators
+        for (x, y) in [(0, 0), (22, 0), (0, 22)] {
+            assert!(code.is_dark(x, y) && code.is_dark(x + 3, y + 3));
+            assert!(!code.is_dark(x + 1, y + 1));
+        }
+        assert!(!code.is_dark(7, 7));
+        assert!(QrCode::encode(&[b'a'; 300]).is_none());
+    }
+
+    #[test]
+    fn codes_of_every_version_read_back() {
+        // the largest payload of each version, and an odd length in between
+        for len in [1, 14, 26, 42, 62, 84, 106, 122, 152, 180, 213, 100] {
+            let data = (0..len).map(|i| b'a' + (i % 26) as u8).collect::<Vec<_>>();
+            let code = QrCode::encode(&data).unwrap();
+            assert_eq!(read(&code), data, "{len} bytes");
+        }
+    }
+
+    /// Decodes a code the way a scanner would once it has found the grid:
+    /// reads the format bits, unmasks the data modules, checks each block
+    /// against its error correction and reads the byte mode segment.
+    fn read(code: &QrCode) -> Vec<u8> {
+        let size = code.size();
+        let version = (size - 17) / 4;
+
+        let format = (0..=5)
+            .map(|i| (8, i))
+            .chain([(8, 7), (8, 8), (7, 8)])
+            .chain((9..15).map(|i| (14 - i, 8)))
+            .enumerate()
+            .fold(0, |acc, (i, (x, y))| {
+                acc | u32::from(code.is_dark(x, y)) << i
+            });
+        let mask = (0..8).find(|&mask| format_bits(mask) == format).unwrap();
+        let format_copy = (0..8)
+            .map(|i| (size - 1 - i, 8))
+            .chain((8..15).map(|i| (8, size - 15 + i)))
+            .enumerate()
+            .fold(0, |acc, (i, (x, y))| {
+                acc | u32::from(code.is_dark(x, y)) << i
+            });
+        assert_eq!(format_copy, format);
+
+        let mut functions = Builder::new(version);
+        functions.draw_function_patterns();
+        // the mask conditions as the standard writes them, for row i and
+        // column j
+        let unmasked = |j: usize, i: usize| {
+            let invert = match mask {
+                0 => (i + j).is_multiple_of(2),
+                1 => i.is_multiple_of(2),
+                2 => j.is_multiple_of(3),
+                3 => (i + j).is_multiple_of(3),
+                4 => (i / 2 + j / 3).is_multiple_of(2),
+                5 => (i * j) % 2 + (i * j) % 3 == 0,
+                6 => ((i * j) % 2 + (i * j) % 3).is_multiple_of(2),
+                _ => ((i * j) % 3 + (i + j) % 2).is_multiple_of(2),
+            };
+            code.is_dark(j, i) != invert
+        };
+
+        // the same zig-zag as `draw_codewords`, reading instead of writing
+        let mut bits = Vec::new();
+        let mut right = size - 1;
+        loop {
+            if right == 6 {
+                right = 5;
+            }
+            for vertical in 0..size {
+                for x in [right, right - 1] {
+                    let upward = (right + 1) & 2 == 0;
+                    let y = if upward {
+                        size - 1 - vertical
+                    } else {
+                        vertical
+                    };
+                    if !functions.is_function[y * size + x] {
+                        bits.push(unmasked(x, y));
+                    }
+                }
+            }
+            if right < 2 {
+                break;
+            }
+            right -= 2;
+        }
+        let codewords = bits
+            .chunks_exact(8)
+            .map(|byte| byte.iter().fold(0, |acc, &bit| acc << 1 | u8::from(bit)))
+            .collect::<Vec<_>>();
+
+        let num_blocks = NUM_ERROR_CORRECTION_BLOCKS[version];
+        let ecc_len = ECC_CODEWORDS_PER_BLOCK[version];
+        let num_long_blocks = codewords.len() % num_blocks;
+        let short_data_len = codewords.len() / num_blocks - ecc_len;
+        let mut blocks = vec![Vec::new(); num_blocks];
+        let mut codewords = codewords.into_iter();
+        for i in 0..=short_data_len {
+            for (j, block) in blocks.iter_mut().enumerate() {
+                if i < short_data_len || j >= num_blocks - num_long_blocks {
+                    block.extend(codewords.next());
+                }
+            }
+        }
+        for _ in 0..ecc_len {
+            for block in &mut blocks {
+                block.extend(codewords.next());
+            }
+        }
+        let mut data = Vec::<u8>::new();
+        for block in blocks {
+            // a valid block is divisible by the generator, so vanishes at
+            // each of its roots 2^0 .. 2^(ecc_len - 1)
+            let mut root = 1;
+            for _ in 0..ecc_len {
+                let syndrome = block
+                    .iter()
+                    .fold(0, |acc, &codeword| gf_multiply(acc, root) ^ codeword);
+                assert_eq!(syndrome, 0);
+                root = gf_multiply(root, 0x02);
+            }
+            data.extend(&block[..block.len() - ecc_len]);
+        }
+
+        let mut bits = data
+            .iter()
+            .flat_map(|byte| (0..8).rev().map(move |i| (byte >> i) & 1))
+            .map(usize::from);
+        let mut take =
+            |length: usize| (0..length).fold(0, |acc, _| acc << 1 | bits.next().unwrap());
+        assert_eq!(take(4), 0b0100);
+        let len = take(char_count_bits(version));
+        (0..len).map(|_| take(8) as u8).collect()
+    }
+}
@@ -0,0 +1,19 @@
+use color_eyre::eyre::Result;
+use tokio::runtime::{Builder, Runtime};
+
+use crate::config;
+
+/// Builds the async runtime with `runtime_threads` worker threads, running
+/// everything on the calling thread when it is 1.
+pub fn build() -> Result<Runtime> {
+    let mut builder = match config::get().runtime_threads {
+        Some(1) => Builder::new_current_thread(),
+        Some(threads) => {
+            let mut builder = Builder::new_multi_thread();
+            builder.worker_threads(threads);
+            builder
+        }
+        None => Builder::new_multi_thread(),
+    };
+    Ok(builder.enable_all().build()?)
+}
@@ -16,32 +16,77 @@ pub mod keybindings {
         pub fn command_to_action(&self, command: Command) -> Action {
             match command {
                 Command::Quit => Action::Quit,
+                Command::Suspend => Action::Suspend,
                 Command::NextTab => Action::NextTab,
                 Command::PreviousTab => Action::PreviousTab,
+                Command::NextTheme => Action::NextTheme,
+                Command::PreviousTheme => Action::PreviousTheme,
+                Command::ToggleFooterHints => Action::ToggleFooterHints,
                 Command::ClosePopup => Action::ClosePopup,
+                Command::CancelPopup => Action::CancelPopup,
+                Command::ConfirmPopup => Action::ConfirmPopup,
+                Command::RetryPopup => Action::RetryPopup,
                 Command::SwitchMode(m) => Action::SwitchMode(m),
                 Command::SwitchToLastMode => Action::SwitchToLastMode,
+                Command::NavigateBack => Action::NavigateBack,
+                Command::NavigateForward => Action::NavigateForward,
+                Command::StartHelpFilter => Action::StartHelpFilter,
                 Command::IncrementPage => Action::IncrementPage,
                 Command::DecrementPage => Action::DecrementPage,
                 Command::NextSummaryMode => Action::NextSummaryMode,
                 Command::PreviousSummaryMode => Action::PreviousSummaryMode,
+                Command::SummaryMoveLeft => Action::SummaryMoveLeft,
+                Command::SummaryMoveRight => Action::SummaryMoveRight,
+                Command::SummaryMoveUp => Action::SummaryMoveUp,
+                Command::SummaryMoveDown => Action::SummaryMoveDown,
+                Command::SummarySelect => Action::SummarySelect,
+                Command::RefreshSummary => Action::RefreshSummary,
                 Command::ToggleSortBy { reload, forward } => {
                     Action::ToggleSortBy { reload, forward }
                 }
+                Command::ToggleSortDirection => Action::ToggleSortDirection,
                 Command::ScrollBottom => Action::ScrollBottom,
                 Command::ScrollTop => Action::ScrollTop,
                 Command::ScrollDown => Action::ScrollDown,
                 Command::ScrollUp => Action::ScrollUp,
+                Command::ScrollHalfPageDown => Action::ScrollHalfPageDown,
+                Command::ScrollHalfPageUp => Action::ScrollHalfPageUp,
+                Command::ScrollPageDown => Action::ScrollPageDown,
+                Command::ScrollPageUp => Action::ScrollPageUp,
                 Command::ScrollCrateInfoDown => Action::ScrollCrateInfoDown,
                 Command::ScrollCrateInfoUp => Action::ScrollCrateInfoUp,
                 Command::ScrollSearchResultsDown => Action::ScrollSearchResultsDown,
                 Command::ScrollSearchResultsUp => Action::ScrollSearchResultsUp,
                 Command::SubmitSearch => Action::SubmitSearch,
-                Command::ReloadData => Action::ReloadData,
+                Command::ReloadData => Action::RefreshData,
+                Command::RefreshCrateDetails => Action::RefreshCrateDetails,
+                Command::RefreshTrending => Action::RefreshTrending,
                 Command::ToggleShowCrateInfo => Action::ToggleShowCrateInfo,
+                Command::ToggleBookmark => Action::ToggleBookmark,
+                Command::SearchCategory => Action::SearchCategory,
+                Command::RefreshCategories => Action::RefreshCategories,
+                Command::SearchByOwner => Action::SearchByOwner,
+                Command::SetMinDownloads => Action::SetMinDownloads,
+                Command::ToggleOnlyWithRepo => Action::ToggleOnlyWithRepo,
+                Command::FilterByLicense => Action::FilterByLicense,
                 Command::CopyCargoAddCommandToClipboard => Action::CopyCargoAddCommandToClipboard,
+                Command::AddToLocalCargoToml => Action::AddToLocalCargoToml,
+                Command::CopyTomlDependencyToClipboard => Action::CopyTomlDependencyToClipboard,
+                Command::ToggleMark => Action::ToggleMark,
+                Command::CopyMarkedCargoAdd => Action::CopyMarkedCargoAdd,
+                Command::CompareCrates => Action::CompareCrates,
+                Command::RefreshComparison => Action::RefreshComparison,
                 Command::OpenDocsUrlInBrowser => Action::OpenDocsUrlInBrowser,
                 Command::OpenCratesIOUrlInBrowser => Action::OpenCratesIOUrlInBrowser,
+                Command::CopyCratesIoUrlToClipboard => Action::CopyCratesIoUrlToClipboard,
+                Command::CopyDocsUrlToClipboard => Action::CopyDocsUrlToClipboard,
+                Command::ShowCrateQrCode => Action::ShowCrateQrCode,
+                Command::ToggleFeature => Action::ToggleFeature,
+                Command::CopyCargoAddWithFeatures => Action::CopyCargoAddWithFeatures,
+                Command::OpenRepositoryUrlInBrowser => Action::OpenRepositoryUrlInBrowser,
+                Command::OpenHomepageUrlInBrowser => Action::OpenHomepageUrlInBrowser,
+                Command::ExportResults => Action::ExportResults,
+                Command::ExportCrateMarkdown => Action::ExportCrateMarkdown,
             }
         }
 
@@ -68,6 +113,44 @@ pub mod keybindings {
             }
         }
 
+        /// Returns true if `key_events` is the start of a longer key sequence
+        /// bound in `mode`, e.g. the first `g` of `gg`.
+        pub fn is_sequence_prefix(&self, mode: Mode, key_events: &[KeyEvent]) -> bool {
+            self.0.get(&mode).is_some_and(|bindings| {
+                bindings.keys().any(|sequence| {
+                    sequence.len() > key_events.len() && sequence.starts_with(key_events)
+                })
+            })
+        }
+
+        /// Returns the keys completing each sequence bound in `mode` that the
+        /// end of `key_events` starts, with the command it is bound to.
+        ///
+        /// As with `event_to_command`, earlier keys that don't start a sequence
+        /// are ignored.
+        pub fn completions(
+            &self,
+            mode: Mode,
+            key_events: &[KeyEvent],
+        ) -> Vec<(Vec<KeyEvent>, Command)> {
+            let Some(bindings) = self.0.get(&mode) else {
+                return vec![];
+            };
+            (0..key_events.len())
+                .map(|start| &key_events[start..])
+                .find(|pending| self.is_sequence_prefix(mode, pending))
+                .map(|pending| {
+                    bindings
+                        .iter()
+                        .filter(|(sequence, _)| {
+                            sequence.len() > pending.len() && sequence.starts_with(pending)
+                        })
+                        .map(|(sequence, command)| (sequence[pending.len()..].to_vec(), *command))
+                        .collect_vec()
+                })
+                .unwrap_or_default()
+        }
+
         pub fn get_keybindings_for_command(
             &self,
             mode: Mode,
@@ -107,11 +190,12 @@ pub mod keybindings {
                 .map(|(mode, inner_map)| {
                     let converted_inner_map = inner_map
                         .into_iter()
-                        .map(|(key_str, cmd)| (parse_key_sequence(&key_str).unwrap(), cmd))
-                        .collect();
-                    (mode, converted_inner_map)
+                        .map(|(key_str, cmd)| Ok((parse_key_sequence(&key_str)?, cmd)))
+                        .collect::<Result<_, String>>()?;
+                    Ok((mode, converted_inner_map))
                 })
-                .collect();
+                .collect::<Result<_, String>>()
+                .map_err(serde::de::Error::custom)?;
 
             Ok(KeyBindings(keybindings))
         }
@@ -313,4 +397,54 @@ pub mod keybindings {
 
         sequences.into_iter().map(parse_key_event).collect()
     }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn gg_resolves_to_scroll_top() {
+            let mode = Mode::PickerHideCrateInfo;
+            let mut key_bindings = KeyBindings::default();
+            let gg = parse_key_sequence("<g><g>").unwrap();
+            key_bindings.insert(mode, &gg, Command::ScrollTop);
+            let g = &gg[..1];
+            let j = parse_key_event("j").unwrap();
+
+            assert_eq!(key_bindings.event_to_command(mode, g), None);
+            assert!(key_bindings.is_sequence_prefix(mode, g));
+            assert_eq!(
+                key_bindings.event_to_command(mode, &gg),
+                Some(Command::ScrollTop)
+            );
+            // earlier keys in the same tick don't prevent the sequence matching
+            assert_eq!(
+                key_bindings.event_to_command(mode, &[j, gg[0], gg[1]]),
+                Some(Command::ScrollTop)
+            );
+            assert!(!key_bindings.is_sequence_prefix(mode, &gg));
+        }
+
+        #[test]
+        fn completions_list_the_rest_of_pending_sequences() {
+            let mode = Mode::PickerHideCrateInfo;
+            let mut key_bindings = KeyBindings::default();
+            let gg = parse_key_sequence("<g><g>").unwrap();
+            let gd = parse_key_sequence("<g><d>").unwrap();
+            key_bindings.insert(mode, &gg, Command::ScrollTop);
+            key_bindings.insert(mode, &gd, Command::OpenDocsUrlInBrowser);
+            let j = parse_key_event("j").unwrap();
+
+            let mut completions = key_bindings.completions(mode, &[j, gg[0]]);
+            completions.sort_by_key(|(_, command)| command.to_string());
+            assert_eq!(
+                completions,
+                [
+                    (vec![gd[1]], Command::OpenDocsUrlInBrowser),
+                    (vec![gg[1]], Command::ScrollTop),
+                ]
+            );
+            assert!(key_bindings.completions(mode, &[j]).is_empty());
+        }
+    }
 }
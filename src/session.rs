@@ -0,0 +1,74 @@
+use std::path::PathBuf;
+
+use color_eyre::eyre::Result;
+use serde::{Deserialize, Serialize};
+use tracing::warn;
+
+use crate::{app::Mode, cli::SortBy, config, crates_io_api_helper::Owner};
+
+/// Where the user left off, saved on quit so that the next run can reopen
+/// there.
+///
+/// Only the parameters of the search are kept; the results are fetched again
+/// when the session is restored.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SessionState {
+    pub mode: Mode,
+    pub query: String,
+    pub sort: SortBy,
+    pub sort_ascending: bool,
+    pub category: Option<String>,
+    pub owner: Option<Owner>,
+    pub page: u64,
+    pub selection: Option<usize>,
+}
+
+impl SessionState {
+    /// Loads the last session from the data directory.
+    ///
+    /// A missing or malformed session file results in `None` so that the app
+    /// starts fresh.
+    pub fn load() -> Option<Self> {
+        let path = session_file();
+        let contents = std::fs::read_to_string(&path).ok()?;
+        toml::from_str(&contents)
+            .inspect_err(|err| warn!("Unable to parse session file {}: {err}", path.display()))
+            .ok()
+    }
+
+    /// Writes the session to the data directory.
+    pub fn save(&self) -> Result<()> {
+        let path = session_file();
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(path, toml::to_string_pretty(self)?)?;
+        Ok(())
+    }
+}
+
+/// Returns the path to the session file.
+fn session_file() -> PathBuf {
+    config::get().data_dir.join("session.toml")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trips_through_toml() {
+        let session = SessionState {
+            mode: Mode::PickerHideCrateInfo,
+            query: "ratatui".into(),
+            sort: SortBy::Downloads,
+            sort_ascending: false,
+            category: None,
+            owner: None,
+            page: 2,
+            selection: Some(3),
+        };
+        let contents = toml::to_string_pretty(&session).unwrap();
+        assert_eq!(toml::from_str::<SessionState>(&contents).unwrap(), session);
+    }
+}
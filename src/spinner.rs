@@ -0,0 +1,51 @@
+use serde::{Deserialize, Serialize};
+use strum::{Display, EnumIter};
+
+/// The animation shown while data is loading.
+#[derive(Debug, Default, Display, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, EnumIter)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum SpinnerStyle {
+    #[default]
+    Braille,
+    Dots,
+    Line,
+    Ascii,
+}
+
+impl SpinnerStyle {
+    /// Returns the frames of the animation.
+    pub fn frames(&self) -> &'static [&'static str] {
+        match self {
+            SpinnerStyle::Braille => &["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"],
+            SpinnerStyle::Dots => &["·  ", "·· ", "···", " ··", "  ·", "   "],
+            SpinnerStyle::Line => &["─", "╲", "│", "╱"],
+            SpinnerStyle::Ascii => &["-", "\\", "|", "/"],
+        }
+    }
+
+    /// Returns the frame to show for the given frame count.
+    pub fn frame(&self, frame_count: usize) -> &'static str {
+        let frames = self.frames();
+        frames[frame_count % frames.len()]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use strum::IntoEnumIterator;
+
+    use super::*;
+
+    #[test]
+    fn every_style_returns_a_frame() {
+        for style in SpinnerStyle::iter() {
+            for frame_count in [0, 1, 7, 1000] {
+                assert!(
+                    !style.frame(frame_count).is_empty(),
+                    "{style} {frame_count}"
+                );
+            }
+        }
+    }
+}
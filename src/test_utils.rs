@@ -0,0 +1,40 @@
+//! Fixtures shared by the unit tests.
+
+use serde_json::{Value, json};
+
+/// The JSON crates.io sends for a crate called `name`, with every optional
+/// field empty.
+pub fn crate_json(name: &str) -> Value {
+    json!({
+        "id": name,
+        "name": name,
+        "description": null,
+        "license": null,
+        "documentation": null,
+        "homepage": null,
+        "repository": null,
+        "downloads": 0,
+        "recent_downloads": null,
+        "categories": null,
+        "keywords": null,
+        "versions": null,
+        "max_version": "0.1.0",
+        "max_stable_version": null,
+        "links": {
+            "owner_team": "",
+            "owner_user": "",
+            "owners": "",
+            "reverse_dependencies": "",
+            "version_downloads": "",
+            "versions": null,
+        },
+        "created_at": "2024-01-01T00:00:00Z",
+        "updated_at": "2024-01-01T00:00:00Z",
+        "exact_match": null,
+    })
+}
+
+/// A crate called `name`, with every optional field empty.
+pub fn krate(name: &str) -> crates_io_api::Crate {
+    serde_json::from_value(crate_json(name)).unwrap()
+}
@@ -0,0 +1,154 @@
+use std::{io::Write, sync::OnceLock};
+
+use color_eyre::eyre::Result;
+use crossterm::{
+    cursor::{MoveTo, RestorePosition, SavePosition},
+    event::{DisableBracketedPaste, DisableMouseCapture, EnableBracketedPaste, EnableMouseCapture},
+    execute, queue,
+    style::{Attribute, Print, ResetColor, SetAttribute, SetBackgroundColor, SetForegroundColor},
+};
+use ratatui::{
+    DefaultTerminal, crossterm::style::Color as CrosstermColor, prelude::IntoCrossterm,
+    style::Modifier,
+};
+
+use crate::{config, widgets::crate_info_table::Hyperlink};
+
+/// Enables the mouse capture and bracketed paste modes turned on in the
+/// config.
+pub fn enable_input_modes() -> Result<()> {
+    let config = config::get();
+    if config.enable_mouse {
+        execute!(std::io::stdout(), EnableMouseCapture)?;
+    }
+    if config.enable_paste {
+        execute!(std::io::stdout(), EnableBracketedPaste)?;
+    }
+    Ok(())
+}
+
+/// Disables the modes enabled by [`enable_input_modes`].
+pub fn disable_input_modes() -> Result<()> {
+    let config = config::get();
+    if config.enable_paste {
+        execute!(std::io::stdout(), DisableBracketedPaste)?;
+    }
+    if config.enable_mouse {
+        execute!(std::io::stdout(), DisableMouseCapture)?;
+    }
+    Ok(())
+}
+
+/// Restores the terminal and stops the process until it is continued, e.g.
+/// by `fg` in the shell, then sets the terminal back up.
+#[cfg(unix)]
+pub fn suspend(tui: &mut DefaultTerminal) -> Result<()> {
+    disable_input_modes()?;
+    ratatui::restore();
+    // SAFETY: raising a signal has no memory safety requirements. The default
+    // SIGTSTP handler stops the process, so this returns once it is continued.
+    unsafe {
+        libc::raise(libc::SIGTSTP);
+    }
+    resume(tui)
+}
+
+/// Does nothing, as there is no job control to continue the process with.
+#[cfg(not(unix))]
+pub fn suspend(_tui: &mut DefaultTerminal) -> Result<()> {
+    Ok(())
+}
+
+/// Sets the terminal back up after the process was continued and forces a
+/// full redraw.
+#[cfg(unix)]
+fn resume(tui: &mut DefaultTerminal) -> Result<()> {
+    crossterm::terminal::enable_raw_mode()?;
+    execute!(std::io::stdout(), crossterm::terminal::EnterAlternateScreen)?;
+    enable_input_modes()?;
+    tui.clear()?;
+    Ok(())
+}
+
+/// Whether URLs are turned into OSC 8 hyperlinks, either because the config
+/// says so or because the terminal is known to support them.
+pub fn hyperlinks_enabled() -> bool {
+    config::get().hyperlinks.unwrap_or_else(|| {
+        static SUPPORTED: OnceLock<bool> = OnceLock::new();
+        *SUPPORTED.get_or_init(terminal_supports_hyperlinks)
+    })
+}
+
+/// Guesses from the environment whether the terminal renders OSC 8
+/// hyperlinks, erring on the side of plain text.
+fn terminal_supports_hyperlinks() -> bool {
+    let var = |name| std::env::var(name).ok();
+    if var("TERM").is_some_and(|term| term == "dumb" || term == "linux") {
+        return false;
+    }
+    let known_program = var("TERM_PROGRAM").is_some_and(|program| {
+        ["iTerm.app", "WezTerm", "vscode", "ghostty", "Hyper"].contains(&program.as_str())
+    });
+    let vte = var("VTE_VERSION")
+        .and_then(|version| version.parse::<u32>().ok())
+        .is_some_and(|version| version >= 5000);
+    known_program
+        || vte
+        || [
+            "WT_SESSION",
+            "KITTY_WINDOW_ID",
+            "KONSOLE_VERSION",
+            "ALACRITTY_WINDOW_ID",
+        ]
+        .iter()
+        .any(|name| var(name).is_some())
+}
+
+/// Redraws the text of each link wrapped in an OSC 8 hyperlink.
+///
+/// This runs after the frame is drawn instead of putting the escape sequence
+/// in the buffer, where ratatui would count it towards the width of the cell.
+pub fn write_hyperlinks(links: &[Hyperlink]) -> Result<()> {
+    if links.is_empty() || !hyperlinks_enabled() {
+        return Ok(());
+    }
+    let mut stdout = std::io::stdout();
+    queue!(stdout, SavePosition)?;
+    for link in links {
+        let fg: CrosstermColor = link.style.fg.unwrap_or_default().into_crossterm();
+        let bg: CrosstermColor = link.style.bg.unwrap_or_default().into_crossterm();
+        queue!(
+            stdout,
+            MoveTo(link.position.x, link.position.y),
+            SetForegroundColor(fg),
+            SetBackgroundColor(bg),
+        )?;
+        for (modifier, attribute) in [
+            (Modifier::BOLD, Attribute::Bold),
+            (Modifier::DIM, Attribute::Dim),
+            (Modifier::ITALIC, Attribute::Italic),
+            (Modifier::UNDERLINED, Attribute::Underlined),
+            (Modifier::SLOW_BLINK, Attribute::SlowBlink),
+            (Modifier::RAPID_BLINK, Attribute::RapidBlink),
+            (Modifier::REVERSED, Attribute::Reverse),
+            (Modifier::HIDDEN, Attribute::Hidden),
+            (Modifier::CROSSED_OUT, Attribute::CrossedOut),
+        ] {
+            if link.style.add_modifier.contains(modifier) {
+                queue!(stdout, SetAttribute(attribute))?;
+            }
+        }
+        queue!(
+            stdout,
+            Print(format!(
+                "\x1b]8;;{}\x1b\\{}\x1b]8;;\x1b\\",
+                link.url, link.text
+            )),
+            SetAttribute(Attribute::Reset),
+            ResetColor,
+        )?;
+    }
+    queue!(stdout, RestorePosition)?;
+    stdout.flush()?;
+    Ok(())
+}
@@ -1,9 +1,18 @@
+pub mod bookmarks;
+pub mod categories;
+pub mod command_palette;
+pub mod compare;
 pub mod crate_info_table;
+pub mod footer_hints;
 pub mod help;
+pub mod lockfile;
 pub mod popup_message;
+pub mod qr_code;
 pub mod search_filter_prompt;
 pub mod search_page;
 pub mod search_results;
 pub mod status_bar;
 pub mod summary;
 pub mod tabs;
+pub mod trending;
+pub mod which_key;
@@ -0,0 +1,125 @@
+use std::sync::{
+    Arc, Mutex,
+    atomic::{AtomicBool, Ordering},
+};
+
+use ratatui::{layout::Flex, prelude::*};
+use tokio::sync::mpsc::UnboundedSender;
+
+use crate::{action::Action, bookmarks::Bookmarks, config, crates_io_api_helper};
+
+use super::search_results::{SearchResults, SearchResultsWidget};
+
+#[derive(Debug)]
+pub struct BookmarksPage {
+    /// A table component listing the bookmarked crates.
+    pub results: SearchResults,
+
+    /// A thread-safe, shared vector holding the bookmarked crates fetched
+    /// from crates.io.
+    pub crates: Arc<Mutex<Vec<crates_io_api::Crate>>>,
+
+    /// Sender end of an asynchronous channel for dispatching actions from
+    /// various parts of the app to be handled by the event loop.
+    tx: UnboundedSender<Action>,
+
+    /// A thread-safe indicator of whether data is currently being loaded,
+    /// allowing different parts of the app to know if it's in a loading state.
+    loading_status: Arc<AtomicBool>,
+}
+
+impl BookmarksPage {
+    pub fn new(tx: UnboundedSender<Action>, loading_status: Arc<AtomicBool>) -> Self {
+        Self {
+            results: Default::default(),
+            crates: Default::default(),
+            tx,
+            loading_status,
+        }
+    }
+
+    /// Spawns an asynchronous task to fetch the bookmarked crates from
+    /// crates.io.
+    pub fn request(&self, bookmarks: &Bookmarks) {
+        if bookmarks.is_empty() {
+            self.crates.lock().unwrap().clear();
+            let _ = self.tx.send(Action::UpdateBookmarks);
+            return;
+        }
+        let names = bookmarks.names();
+        let tx = self.tx.clone();
+        let crates = self.crates.clone();
+        let loading_status = self.loading_status.clone();
+        tokio::spawn(async move {
+            loading_status.store(true, Ordering::SeqCst);
+            if let Err(err) = crates_io_api_helper::request_crates_by_name(names, crates).await {
+                crates_io_api_helper::report_error(&tx, "loading bookmarks", &err, None);
+            }
+            loading_status.store(false, Ordering::SeqCst);
+            let _ = tx.send(Action::UpdateBookmarks);
+        });
+    }
+
+    /// Updates the table with the fetched crates that are still bookmarked.
+    pub fn update(&mut self, bookmarks: &Bookmarks) {
+        let mut crates = self
+            .crates
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|krate| bookmarks.contains(&krate.name))
+            .cloned()
+            .collect::<Vec<_>>();
+        crates.sort_by(|a, b| a.name.cmp(&b.name));
+        self.results.crates = crates;
+        self.results.content_length(self.results.crates.len());
+        let selected = self
+            .results
+            .selected()
+            .map(|i| i.min(self.results.crates.len().saturating_sub(1)))
+            .or(Some(0));
+        if self.results.crates.is_empty() {
+            self.results.select(None);
+        } else {
+            self.results.select(selected);
+        }
+    }
+
+    pub fn scroll_up(&mut self, count: usize) {
+        self.results.scroll_previous(count);
+    }
+
+    pub fn scroll_down(&mut self, count: usize) {
+        self.results.scroll_next(count);
+    }
+}
+
+pub struct BookmarksPageWidget<'a> {
+    bookmarks: &'a Bookmarks,
+}
+
+impl<'a> BookmarksPageWidget<'a> {
+    pub fn new(bookmarks: &'a Bookmarks) -> Self {
+        Self { bookmarks }
+    }
+}
+
+impl StatefulWidget for BookmarksPageWidget<'_> {
+    type State = BookmarksPage;
+
+    fn render(self, area: Rect, buf: &mut Buffer, state: &mut Self::State) {
+        if self.bookmarks.is_empty() {
+            let [center] = Layout::vertical([Constraint::Length(1)])
+                .flex(Flex::Center)
+                .areas(area);
+            Line::from("No bookmarks yet")
+                .fg(config::get().color.base03)
+                .centered()
+                .render(center, buf);
+            return;
+        }
+        SearchResultsWidget::new(true)
+            .bookmarks(self.bookmarks)
+            .render(area, buf, &mut state.results);
+    }
+}
@@ -0,0 +1,204 @@
+use std::sync::{
+    Arc, Mutex,
+    atomic::{AtomicBool, Ordering},
+};
+
+use itertools::Itertools;
+use ratatui::{layout::Flex, prelude::*, widgets::*};
+use tokio::sync::mpsc::UnboundedSender;
+
+use crate::{action::Action, app::Mode, command::Command, config, crates_io_api_helper, format};
+
+#[derive(Debug)]
+pub struct Categories {
+    /// The categories shown in the table.
+    pub categories: Vec<crates_io_api::Category>,
+
+    pub table_state: TableState,
+    pub scrollbar_state: ScrollbarState,
+
+    /// The number of rows that fit in the table at the last render.
+    pub page_size: usize,
+
+    /// A thread-safe, shared vector holding the categories fetched from
+    /// crates.io.
+    pub data: Arc<Mutex<Vec<crates_io_api::Category>>>,
+
+    /// Why the last fetch failed, shown in place of the table until a fetch
+    /// succeeds.
+    pub error: Arc<Mutex<Option<String>>>,
+
+    /// Sender end of an asynchronous channel for dispatching actions from
+    /// various parts of the app to be handled by the event loop.
+    tx: UnboundedSender<Action>,
+
+    /// A thread-safe indicator of whether data is currently being loaded,
+    /// allowing different parts of the app to know if it's in a loading state.
+    loading_status: Arc<AtomicBool>,
+}
+
+impl Categories {
+    pub fn new(tx: UnboundedSender<Action>, loading_status: Arc<AtomicBool>) -> Self {
+        Self {
+            categories: Default::default(),
+            table_state: Default::default(),
+            scrollbar_state: Default::default(),
+            page_size: 1,
+            data: Default::default(),
+            error: Default::default(),
+            tx,
+            loading_status,
+        }
+    }
+
+    /// Spawns an asynchronous task to fetch all categories from crates.io.
+    pub fn request(&self) {
+        let tx = self.tx.clone();
+        let loading_status = self.loading_status.clone();
+        let categories = self.data.clone();
+        let error = self.error.clone();
+        *error.lock().unwrap() = None;
+        tokio::spawn(async move {
+            loading_status.store(true, Ordering::SeqCst);
+            if let Err(err) = crates_io_api_helper::request_categories(categories).await {
+                *error.lock().unwrap() = Some(err.user_message("loading categories"));
+                crates_io_api_helper::report_error(
+                    &tx,
+                    "loading categories",
+                    &err,
+                    Some(Action::RefreshCategories),
+                );
+            }
+            loading_status.store(false, Ordering::SeqCst);
+            let _ = tx.send(Action::UpdateCategories);
+        });
+    }
+
+    pub fn update(&mut self) {
+        self.categories = self.data.lock().unwrap().clone();
+        self.scrollbar_state = self.scrollbar_state.content_length(self.categories.len());
+        if self.categories.is_empty() {
+            self.table_state.select(None);
+        } else if self.table_state.selected().is_none() {
+            self.scroll_to(0);
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.categories.is_empty()
+    }
+
+    /// Returns the slug of the selected category, used to filter searches.
+    pub fn selected_slug(&self) -> Option<String> {
+        self.table_state
+            .selected()
+            .and_then(|index| self.categories.get(index))
+            .map(|category| category.slug.clone())
+    }
+
+    pub fn scroll_previous(&mut self, count: usize) {
+        let i = self
+            .table_state
+            .selected()
+            .map_or(0, |i| i.saturating_sub(count));
+        self.scroll_to(i);
+    }
+
+    pub fn scroll_next(&mut self, count: usize) {
+        let i = self
+            .table_state
+            .selected()
+            .map_or(0, |i| i.saturating_add(count));
+        self.scroll_to(i);
+    }
+
+    pub fn scroll_to_top(&mut self) {
+        self.scroll_to(0);
+    }
+
+    pub fn scroll_to_bottom(&mut self) {
+        self.scroll_to(usize::MAX);
+    }
+
+    fn scroll_to(&mut self, index: usize) {
+        if self.categories.is_empty() {
+            self.table_state.select(None);
+        } else {
+            let index = index.min(self.categories.len() - 1);
+            self.table_state.select(Some(index));
+            self.scrollbar_state = self.scrollbar_state.position(index);
+        }
+    }
+}
+
+pub struct CategoriesWidget;
+
+impl StatefulWidget for CategoriesWidget {
+    type State = Categories;
+
+    fn render(self, area: Rect, buf: &mut Buffer, state: &mut Self::State) {
+        use Constraint::*;
+
+        if state.is_empty() {
+            let [center] = Layout::vertical([Length(1)]).flex(Flex::Center).areas(area);
+            let line = match state.error.lock().unwrap().as_ref() {
+                Some(error) => {
+                    let retry = config::get()
+                        .key_bindings
+                        .get_config_for_command(Mode::Categories, Command::RefreshCategories)
+                        .into_iter()
+                        .next()
+                        .unwrap_or_default();
+                    Line::from(format!("{error} Press {retry} to retry."))
+                        .fg(config::get().color.base08)
+                }
+                None => Line::from("Loading categories...").fg(config::get().color.base03),
+            };
+            line.centered().render(center, buf);
+            return;
+        }
+
+        let [table_area, scrollbar_area] = Layout::horizontal([Fill(1), Length(1)]).areas(area);
+        let [_, scrollbar_area] = Layout::vertical([Length(1), Fill(1)]).areas(scrollbar_area);
+
+        Scrollbar::default()
+            .track_symbol(Some(" "))
+            .thumb_symbol("▐")
+            .begin_symbol(None)
+            .end_symbol(None)
+            .track_style(config::get().color.base06)
+            .render(scrollbar_area, buf, &mut state.scrollbar_state);
+
+        let header = Row::new(["Name", "Slug", "Crates"].map(|h| Line::from(h.bold())))
+            .fg(config::get().color.base05)
+            .bg(config::get().color.base00);
+        let rows = state
+            .categories
+            .iter()
+            .enumerate()
+            .map(|(index, category)| {
+                let bg = match index % 2 {
+                    0 => config::get().color.base00,
+                    _ => config::get().color.base01,
+                };
+                Row::new([
+                    Line::from(category.category.clone()),
+                    Line::from(category.slug.clone()),
+                    Line::from(format::format_count(category.crates_cnt)).right_aligned(),
+                ])
+                .fg(config::get().color.base05)
+                .bg(bg)
+            })
+            .collect_vec();
+
+        let table = Table::new(rows, [Fill(1), Fill(1), Max(11)])
+            .header(header)
+            .column_spacing(3)
+            .highlight_symbol(format!(" {} ", config::get().highlight_symbol()))
+            .row_highlight_style(Style::default().bg(config::get().color.base02))
+            .highlight_spacing(HighlightSpacing::Always);
+        StatefulWidget::render(table, table_area, buf, &mut state.table_state);
+        // the header takes up the first row
+        state.page_size = (table_area.height as usize).saturating_sub(1).max(1);
+    }
+}
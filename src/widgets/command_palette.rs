@@ -0,0 +1,227 @@
+use crossterm::event::{Event as CrosstermEvent, KeyCode, KeyEvent, KeyModifiers};
+use itertools::Itertools;
+use ratatui::{layout::Flex, layout::Position, prelude::*, widgets::*};
+use tui_input::{Input, backend::crossterm::EventHandler};
+
+use crate::{
+    app::Mode,
+    command::{ALL_COMMANDS, Command},
+    config,
+};
+
+/// A searchable list of the commands available in the mode it was opened
+/// from, so that commands can be run without knowing their key bindings.
+#[derive(Debug, Default)]
+pub struct CommandPalette {
+    input: Input,
+
+    /// The mode the palette was opened from, whose commands are listed.
+    mode: Mode,
+
+    /// The commands matching the input, best match first, with their names
+    /// and key bindings.
+    matches: Vec<(Command, String, String)>,
+
+    pub state: ListState,
+
+    /// Where the cursor is drawn in the input at the last render.
+    cursor_position: Option<Position>,
+}
+
+impl CommandPalette {
+    /// Clears the input and lists the commands of `mode`.
+    pub fn open(&mut self, mode: Mode) {
+        self.mode = mode;
+        self.input.reset();
+        self.update_matches();
+    }
+
+    /// Returns the command to run when the palette is confirmed.
+    pub fn selected_command(&self) -> Option<Command> {
+        self.state
+            .selected()
+            .and_then(|index| self.matches.get(index))
+            .map(|(command, _, _)| *command)
+    }
+
+    pub fn cursor_position(&self) -> Option<Position> {
+        self.cursor_position
+    }
+
+    /// Moves the selection with the arrow keys or `ctrl-n`/`ctrl-p`, and
+    /// edits the input with every other key.
+    pub fn handle_key(&mut self, key: KeyEvent) {
+        let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+        match key.code {
+            KeyCode::Down => self.select_next(),
+            KeyCode::Char('n') if ctrl => self.select_next(),
+            KeyCode::Up => self.select_previous(),
+            KeyCode::Char('p') if ctrl => self.select_previous(),
+            _ => {
+                let changed = self
+                    .input
+                    .handle_event(&CrosstermEvent::Key(key))
+                    .is_some_and(|state| state.value);
+                if changed {
+                    self.update_matches();
+                }
+            }
+        }
+    }
+
+    fn select_next(&mut self) {
+        let last = self.matches.len().saturating_sub(1);
+        let index = self.state.selected().map_or(0, |i| (i + 1).min(last));
+        self.state.select(Some(index));
+    }
+
+    fn select_previous(&mut self) {
+        let index = self.state.selected().map_or(0, |i| i.saturating_sub(1));
+        self.state.select(Some(index));
+    }
+
+    /// Lists the commands of the mode and those available in every mode,
+    /// ranked by how well they match the input.
+    fn update_matches(&mut self) {
+        let key_bindings = &config::get().key_bindings;
+        let pattern = self.input.value();
+        self.matches = ALL_COMMANDS
+            .iter()
+            .filter(|(mode, _)| *mode == self.mode || *mode == Mode::Common)
+            .flat_map(|(mode, commands)| commands.iter().map(move |command| (*mode, *command)))
+            .unique_by(|(_, command)| *command)
+            .filter(|(_, command)| *command != Command::SwitchMode(Mode::CommandPalette))
+            .filter_map(|(mode, command)| {
+                let name = command_name(command);
+                let score = fuzzy_score(pattern, &name)?;
+                let keys = key_bindings
+                    .get_config_for_command(mode, command)
+                    .join(", ");
+                Some((score, (command, name, keys)))
+            })
+            .sorted_by_key(|(score, _)| -score)
+            .map(|(_, command)| command)
+            .collect();
+        self.state.select((!self.matches.is_empty()).then_some(0));
+    }
+}
+
+/// Returns a readable name for `command`, e.g. "Toggle bookmark".
+fn command_name(command: Command) -> String {
+    let name = match command {
+        Command::SwitchMode(mode) => format!("GoTo{mode}"),
+        Command::ToggleSortBy { forward: true, .. } => "NextSortOrder".into(),
+        Command::ToggleSortBy { forward: false, .. } => "PreviousSortOrder".into(),
+        command => command.to_string(),
+    };
+    let mut words = String::new();
+    for (index, c) in name.chars().enumerate() {
+        if index > 0 && c.is_uppercase() {
+            words.push(' ');
+            words.extend(c.to_lowercase());
+        } else {
+            words.push(c);
+        }
+    }
+    words
+}
+
+/// Scores how well `text` matches `pattern`, whose characters must appear in
+/// `text` in order, ignoring case. Matches at the start of words and runs of
+/// consecutive characters score higher.
+fn fuzzy_score(pattern: &str, text: &str) -> Option<i64> {
+    let text = text.chars().flat_map(char::to_lowercase).collect_vec();
+    let mut score = 0;
+    let mut position = 0;
+    let mut previous = None;
+    for c in pattern.chars().flat_map(char::to_lowercase) {
+        if c.is_whitespace() {
+            continue;
+        }
+        let index = position + text[position..].iter().position(|t| *t == c)?;
+        score += 1;
+        if index == 0 || text[index - 1] == ' ' {
+            score += 8;
+        }
+        if previous.is_some_and(|previous| previous + 1 == index) {
+            score += 4;
+        }
+        previous = Some(index);
+        position = index + 1;
+    }
+    // prefer shorter names among equally good matches
+    Some(score * 100 - text.len() as i64)
+}
+
+pub struct CommandPaletteWidget;
+
+impl StatefulWidget for CommandPaletteWidget {
+    type State = CommandPalette;
+
+    fn render(self, area: Rect, buf: &mut Buffer, state: &mut Self::State) {
+        use Constraint::*;
+
+        let color = config::get().color;
+        let [area] = Layout::horizontal([Percentage(60)])
+            .flex(Flex::Center)
+            .areas(area);
+        let [area] = Layout::vertical([Percentage(60)])
+            .flex(Flex::Center)
+            .areas(area);
+        Clear.render(area, buf);
+        let block = Block::bordered()
+            .title(" Commands ")
+            .border_style(color.base0d)
+            .bg(color.base00);
+        let inner = block.inner(area);
+        block.render(area, buf);
+
+        let [input_area, list_area] = Layout::vertical([Length(2), Fill(1)]).areas(inner);
+        Paragraph::new(Line::from(vec![
+            "> ".fg(color.base0d),
+            state.input.value().fg(color.base05),
+        ]))
+        .block(
+            Block::default()
+                .borders(Borders::BOTTOM)
+                .border_style(color.base03),
+        )
+        .render(input_area, buf);
+        state.cursor_position = Some(Position::new(
+            (input_area.x + 2 + state.input.visual_cursor() as u16)
+                .min(input_area.right().saturating_sub(1)),
+            input_area.y,
+        ));
+
+        let key_width = state
+            .matches
+            .iter()
+            .map(|(_, _, keys)| keys.len())
+            .max()
+            .unwrap_or_default();
+        let items = state.matches.iter().map(|(_, name, keys)| {
+            Line::from(vec![
+                format!("{keys:<key_width$}  ").fg(color.base0d),
+                name.clone().fg(color.base05),
+            ])
+        });
+        let list = List::new(items)
+            .highlight_symbol(format!("{} ", config::get().highlight_symbol()))
+            .highlight_style(config::get().highlight_style())
+            .highlight_spacing(HighlightSpacing::Always);
+        StatefulWidget::render(list, list_area, buf, &mut state.state);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fuzzy_score_prefers_word_starts() {
+        assert_eq!(fuzzy_score("xyz", "toggle bookmark"), None);
+        assert!(fuzzy_score("tb", "toggle bookmark") > fuzzy_score("tb", "scroll to bottom"));
+        assert!(fuzzy_score("", "quit").is_some());
+        assert_eq!(command_name(Command::ToggleBookmark), "Toggle bookmark");
+    }
+}
@@ -0,0 +1,209 @@
+use std::{
+    cmp::Ordering as CmpOrdering,
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicBool, Ordering},
+    },
+};
+
+use ratatui::{layout::Flex, prelude::*, widgets::*};
+use tokio::{sync::mpsc::UnboundedSender, task::JoinHandle};
+
+use crate::{action::Action, app::Mode, command::Command, config, crates_io_api_helper, format};
+
+use super::crate_info_table::relevant_version;
+
+#[derive(Debug)]
+pub struct Compare {
+    /// The details of the two crates being compared, once both are fetched.
+    pub data: Arc<Mutex<Option<(crates_io_api::CrateResponse, crates_io_api::CrateResponse)>>>,
+
+    /// Why the last fetch failed, shown in place of the comparison until a
+    /// fetch succeeds.
+    pub error: Arc<Mutex<Option<String>>>,
+
+    /// The names of the two crates being compared.
+    names: Option<(String, String)>,
+
+    /// The in-flight fetch, aborted on quit.
+    request_task_handle: Option<JoinHandle<()>>,
+
+    /// Sender end of an asynchronous channel for dispatching actions from
+    /// various parts of the app to be handled by the event loop.
+    tx: UnboundedSender<Action>,
+
+    /// A thread-safe indicator of whether data is currently being loaded,
+    /// allowing different parts of the app to know if it's in a loading state.
+    loading_status: Arc<AtomicBool>,
+}
+
+impl Compare {
+    pub fn new(tx: UnboundedSender<Action>, loading_status: Arc<AtomicBool>) -> Self {
+        Self {
+            data: Default::default(),
+            error: Default::default(),
+            names: None,
+            request_task_handle: None,
+            tx,
+            loading_status,
+        }
+    }
+
+    /// Spawns an asynchronous task to fetch the details of both crates.
+    pub fn request(&mut self, first: String, second: String) {
+        self.names = Some((first, second));
+        self.refresh();
+    }
+
+    /// Fetches the details of the crates being compared again.
+    pub fn refresh(&mut self) {
+        let Some((first, second)) = self.names.clone() else {
+            return;
+        };
+        self.abort_request();
+        *self.data.lock().unwrap() = None;
+        *self.error.lock().unwrap() = None;
+        let tx = self.tx.clone();
+        let loading_status = self.loading_status.clone();
+        let data = self.data.clone();
+        let error = self.error.clone();
+        self.request_task_handle = Some(tokio::spawn(async move {
+            loading_status.store(true, Ordering::SeqCst);
+            match crates_io_api_helper::request_crate_pair(&first, &second).await {
+                Ok(pair) => *data.lock().unwrap() = Some(pair),
+                Err(err) => {
+                    let action = format!("comparing `{first}` and `{second}`");
+                    *error.lock().unwrap() = Some(err.user_message(&action));
+                    crates_io_api_helper::report_error(
+                        &tx,
+                        &action,
+                        &err,
+                        Some(Action::RefreshComparison),
+                    );
+                }
+            }
+            loading_status.store(false, Ordering::SeqCst);
+        }));
+    }
+
+    pub fn abort_request(&mut self) {
+        if let Some(handle) = self.request_task_handle.take() {
+            handle.abort();
+        }
+    }
+}
+
+pub struct CompareWidget;
+
+impl StatefulWidget for CompareWidget {
+    type State = Compare;
+
+    fn render(self, area: Rect, buf: &mut Buffer, state: &mut Self::State) {
+        use Constraint::*;
+
+        let Some((first, second)) = state.data.lock().unwrap().clone() else {
+            let [center] = Layout::vertical([Length(1)]).flex(Flex::Center).areas(area);
+            let line = match state.error.lock().unwrap().as_ref() {
+                Some(error) => {
+                    let retry = config::get()
+                        .key_bindings
+                        .get_config_for_command(Mode::Compare, Command::RefreshComparison)
+                        .into_iter()
+                        .next()
+                        .unwrap_or_default();
+                    Line::from(format!("{error} Press {retry} to retry."))
+                        .fg(config::get().color.base08)
+                }
+                None => Line::from("Loading comparison...").fg(config::get().color.base03),
+            };
+            line.centered().render(center, buf);
+            return;
+        };
+
+        let license = |ci: &crates_io_api::CrateResponse| {
+            relevant_version(ci)
+                .and_then(|version| version.license.clone())
+                .unwrap_or_default()
+        };
+        let versions = |ci: &crates_io_api::CrateResponse| {
+            format!(
+                "{} (latest {})",
+                ci.versions.len(),
+                ci.crate_data.max_version
+            )
+        };
+        let (a, b) = (&first.crate_data, &second.crate_data);
+        let rows = [
+            compare_row(
+                "Downloads",
+                format::format_count(a.downloads),
+                format::format_count(b.downloads),
+                a.downloads.cmp(&b.downloads),
+            ),
+            compare_row(
+                "Recent Downloads",
+                a.recent_downloads
+                    .map(format::format_count)
+                    .unwrap_or_default(),
+                b.recent_downloads
+                    .map(format::format_count)
+                    .unwrap_or_default(),
+                a.recent_downloads.cmp(&b.recent_downloads),
+            ),
+            compare_row(
+                "Last Updated",
+                format::format_timestamp(a.updated_at),
+                format::format_timestamp(b.updated_at),
+                a.updated_at.cmp(&b.updated_at),
+            ),
+            compare_row(
+                "Versions",
+                versions(&first),
+                versions(&second),
+                CmpOrdering::Equal,
+            ),
+            compare_row(
+                "License",
+                license(&first),
+                license(&second),
+                CmpOrdering::Equal,
+            ),
+        ];
+        let header = Row::new([
+            Cell::from(""),
+            Cell::from(a.name.clone().bold()),
+            Cell::from(b.name.clone().bold()),
+        ])
+        .height(2);
+        let table = Table::new(rows, [Length(18), Fill(1), Fill(1)])
+            .header(header)
+            .column_spacing(3)
+            .style(
+                Style::default()
+                    .fg(config::get().color.base05)
+                    .bg(config::get().color.base00),
+            )
+            .block(Block::bordered().title("Compare"));
+        Widget::render(table, area, buf);
+    }
+}
+
+/// A row showing both values, highlighting the greater one as the winner.
+fn compare_row(
+    metric: &'static str,
+    first: String,
+    second: String,
+    ordering: CmpOrdering,
+) -> Row<'static> {
+    let winner = Style::default().fg(config::get().color.base0b).bold();
+    let (first_style, second_style) = match ordering {
+        CmpOrdering::Greater => (winner, Style::default()),
+        CmpOrdering::Less => (Style::default(), winner),
+        CmpOrdering::Equal => (Style::default(), Style::default()),
+    };
+    Row::new([
+        Cell::from(metric),
+        Cell::from(first).style(first_style),
+        Cell::from(second).style(second_style),
+    ])
+}
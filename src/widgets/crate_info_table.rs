@@ -1,14 +1,105 @@
+use std::collections::{BTreeSet, HashMap};
+
 use itertools::Itertools;
 use ratatui::{prelude::*, widgets::*};
 
-use crate::config;
+use unicode_width::UnicodeWidthStr;
+
+use crate::{config, crates_io_api_helper::DocsStatus, format};
 
 #[derive(Debug, Default)]
 pub struct CrateInfo {
     crate_info: TableState,
+
+    /// The URLs drawn in the last render, to be turned into hyperlinks.
+    hyperlinks: Vec<Hyperlink>,
+
+    /// The features picked for `CopyCargoAddWithFeatures`.
+    features: FeatureSelection,
+}
+
+/// The features picked for a crate, dropped when another crate is selected.
+#[derive(Debug, Default)]
+struct FeatureSelection {
+    crate_name: String,
+    selected: BTreeSet<String>,
+    cursor: usize,
+}
+
+/// Marks the selected row of the table.
+const HIGHLIGHT_SYMBOL: &str = "\u{2022} ";
+
+/// A URL drawn at `position`, possibly cut off to `text` by the column width.
+#[derive(Debug, Clone)]
+pub struct Hyperlink {
+    pub position: Position,
+    pub text: String,
+    pub url: String,
+    pub style: Style,
 }
 
 impl CrateInfo {
+    pub fn hyperlinks(&self) -> &[Hyperlink] {
+        &self.hyperlinks
+    }
+
+    pub fn clear_hyperlinks(&mut self) {
+        self.hyperlinks.clear();
+    }
+
+    /// Drops the links that were drawn over afterwards, e.g. by a popup.
+    pub fn retain_visible_hyperlinks(&mut self, buf: &Buffer) {
+        self.hyperlinks.retain(|link| {
+            link.text.chars().enumerate().all(|(offset, c)| {
+                let x = link.position.x + offset as u16;
+                buf.cell((x, link.position.y))
+                    .is_some_and(|cell| cell.symbol().chars().eq([c]))
+            })
+        });
+    }
+
+    /// The selection for `crate_name`, starting over if it was for another
+    /// crate.
+    fn features_of(&mut self, crate_name: &str) -> &mut FeatureSelection {
+        if self.features.crate_name != crate_name {
+            self.features = FeatureSelection {
+                crate_name: crate_name.into(),
+                ..Default::default()
+            };
+        }
+        &mut self.features
+    }
+
+    /// The features picked for `crate_name`, in alphabetical order.
+    pub fn selected_features(&mut self, crate_name: &str) -> Vec<String> {
+        self.features_of(crate_name)
+            .selected
+            .iter()
+            .cloned()
+            .collect()
+    }
+
+    pub fn previous_feature(&mut self, crate_name: &str, count: usize) {
+        let features = self.features_of(crate_name);
+        features.cursor = features.cursor.saturating_sub(count);
+    }
+
+    pub fn next_feature(&mut self, crate_name: &str, count: usize, feature_count: usize) {
+        let features = self.features_of(crate_name);
+        features.cursor = (features.cursor + count).min(feature_count.saturating_sub(1));
+    }
+
+    /// Picks or drops the feature under the cursor out of `feature_names`.
+    pub fn toggle_feature(&mut self, crate_name: &str, feature_names: &[String]) {
+        let features = self.features_of(crate_name);
+        let Some(name) = feature_names.get(features.cursor) else {
+            return;
+        };
+        if !features.selected.remove(name) {
+            features.selected.insert(name.clone());
+        }
+    }
+
     pub fn scroll_previous(&mut self) {
         let i = self
             .crate_info
@@ -26,13 +117,138 @@ impl CrateInfo {
     }
 }
 
+/// Shown in place of fields the crate doesn't set.
+const MISSING: &str = "—";
+
+/// Returns the version that license and MSRV are read from: the max stable
+/// version if there is one, otherwise the max version.
+pub fn relevant_version(ci: &crates_io_api::CrateResponse) -> Option<&crates_io_api::Version> {
+    let num = ci
+        .crate_data
+        .max_stable_version
+        .as_ref()
+        .unwrap_or(&ci.crate_data.max_version);
+    ci.versions
+        .iter()
+        .find(|version| &version.num == num)
+        .or_else(|| ci.versions.first())
+}
+
+/// Lists the users owning a crate, then its teams, wrapped to `width`.
+///
+/// Users are shown as `login (name)` and teams, whose logins look like
+/// `github:org:team`, are marked as such.
+fn owners_text(owners: &[crates_io_api::User], width: usize) -> Vec<Line<'static>> {
+    if owners.is_empty() {
+        return vec![Line::from(MISSING)];
+    }
+    let is_team = |user: &crates_io_api::User| user.kind.as_deref() == Some("team");
+    let owners = owners
+        .iter()
+        .sorted_by_key(|user| is_team(user))
+        .map(|user| match &user.name {
+            _ if is_team(user) => format!("{} (team)", user.login),
+            Some(name) if *name != user.login => format!("{} ({name})", user.login),
+            _ => user.login.clone(),
+        })
+        .join(", ");
+    textwrap::wrap(&owners, width)
+        .iter()
+        .map(|s| Line::from(s.to_string()))
+        .collect_vec()
+}
+
+/// The names of the features of the relevant version, in the order they are
+/// listed.
+pub fn feature_names(ci: &crates_io_api::CrateResponse) -> Vec<String> {
+    relevant_version(ci)
+        .map(|version| version.features.keys().sorted().cloned().collect())
+        .unwrap_or_default()
+}
+
+/// Formats each feature as `name = [sub-feature, ...]`, wrapped to `width`.
+///
+/// While features are being picked, each is prefixed with a checkbox and the
+/// one under the cursor is highlighted.
+fn features_text(
+    features: &HashMap<String, Vec<String>>,
+    width: usize,
+    selection: Option<(&BTreeSet<String>, usize)>,
+) -> Vec<Line<'static>> {
+    if features.is_empty() {
+        return vec![Line::from("default only")];
+    }
+    features
+        .iter()
+        .sorted_by_key(|(name, _)| name.as_str())
+        .enumerate()
+        .flat_map(|(index, (name, enables))| {
+            let mut feature = if enables.is_empty() {
+                name.clone()
+            } else {
+                format!("{name} = [{}]", enables.join(", "))
+            };
+            let mut style = Style::default();
+            if let Some((selected, cursor)) = selection {
+                let checkbox = if selected.contains(name) {
+                    "[x]"
+                } else {
+                    "[ ]"
+                };
+                feature = format!("{checkbox} {feature}");
+                if index == cursor {
+                    style = style.reversed();
+                }
+            }
+            textwrap::wrap(&feature, width)
+                .into_iter()
+                .map(|s| Line::styled(s.to_string(), style))
+                .collect_vec()
+        })
+        .collect()
+}
+
 pub struct CrateInfoTableWidget {
     crate_info: crates_io_api::CrateResponse,
+    docs_status: Option<DocsStatus>,
+    owners: Option<Vec<crates_io_api::User>>,
+    selecting_features: bool,
+    loading: bool,
 }
 
 impl CrateInfoTableWidget {
     pub fn new(crate_info: crates_io_api::CrateResponse) -> Self {
-        Self { crate_info }
+        Self {
+            crate_info,
+            docs_status: None,
+            owners: None,
+            selecting_features: false,
+            loading: false,
+        }
+    }
+
+    /// Marks the details as being fetched, e.g. while they are refreshed.
+    pub fn loading(mut self, loading: bool) -> Self {
+        self.loading = loading;
+        self
+    }
+
+    /// Lists the users and teams owning the crate, once they are known.
+    pub fn owners(mut self, owners: Option<Vec<crates_io_api::User>>) -> Self {
+        self.owners = owners;
+        self
+    }
+
+    /// Shows checkboxes next to the features and scrolls them into view.
+    pub fn selecting_features(mut self, selecting_features: bool) -> Self {
+        self.selecting_features = selecting_features;
+        self
+    }
+
+    /// Shows the docs.rs build status of the crate, once it is known.
+    pub fn docs_status(mut self, docs_status: Option<DocsStatus>) -> Self {
+        self.docs_status = docs_status;
+        self
     }
 }
 
@@ -41,29 +257,47 @@ impl StatefulWidget for CrateInfoTableWidget {
     fn render(self, area: Rect, buf: &mut Buffer, state: &mut Self::State) {
         let ci = self.crate_info.clone();
 
-        let created_at = ci
-            .crate_data
-            .created_at
-            .format("%Y-%m-%d %H:%M:%S")
-            .to_string();
-        let updated_at = ci
-            .crate_data
-            .updated_at
-            .format("%Y-%m-%d %H:%M:%S")
-            .to_string();
+        let created_at = format::format_timestamp(ci.crate_data.created_at);
+        let updated_at = format::format_timestamp(ci.crate_data.updated_at);
+
+        let version = relevant_version(&ci);
+        let license = version
+            .and_then(|v| v.license.clone())
+            .unwrap_or(MISSING.into());
+        let rust_version = version
+            .and_then(|v| v.rust_version.clone())
+            .unwrap_or(MISSING.into());
 
+        // the height of each row, to tell where the links end up
+        let mut heights = vec![];
         let mut rows = [
             ["Name", &ci.crate_data.name],
             ["Created At", &created_at],
             ["Updated At", &updated_at],
             ["Max Version", &ci.crate_data.max_version],
+            ["License", &license],
+            ["MSRV", &rust_version],
         ]
         .iter()
         .map(|row| {
             let cells = row.iter().map(|cell| Cell::from(*cell));
+            heights.push(1);
             Row::new(cells)
         })
         .collect_vec();
+        if let Some(status) = self.docs_status {
+            let color = match status {
+                DocsStatus::Ok => config::get().color.base0b,
+                DocsStatus::Failed => config::get().color.base08,
+                DocsStatus::Building => config::get().color.base0a,
+                DocsStatus::NotPublished => config::get().color.base03,
+            };
+            rows.push(Row::new(vec![
+                Cell::from("Docs"),
+                Cell::from(status.to_string().fg(color)),
+            ]));
+            heights.push(1);
+        }
         let keywords = self
             .crate_info
             .keywords
@@ -75,14 +309,41 @@ impl StatefulWidget for CrateInfoTableWidget {
             .iter()
             .map(|s| Line::from(s.to_string()))
             .collect_vec();
-        let height = keywords.len();
+        let height = keywords.len() as u16;
         rows.push(
             Row::new(vec![
                 Cell::from("Keywords"),
                 Cell::from(Text::from(keywords)),
             ])
-            .height(height as u16),
+            .height(height),
         );
+        heights.push(height);
+
+        if let Some(owners) = &self.owners {
+            let owners = owners_text(owners, (area.width as f64 * 0.75) as usize);
+            let height = owners.len() as u16;
+            rows.push(
+                Row::new(vec![Cell::from("Owners"), Cell::from(Text::from(owners))]).height(height),
+            );
+            heights.push(height);
+        }
+
+        let features = state.features_of(&ci.crate_data.name);
+        let selection = (self.selecting_features || !features.selected.is_empty())
+            .then_some((&features.selected, features.cursor));
+        let features_row = rows.len();
+        let features = version
+            .map(|v| features_text(&v.features, (area.width as f64 * 0.75) as usize, selection))
+            .unwrap_or_else(|| vec![Line::from(MISSING)]);
+        let height = features.len() as u16;
+        rows.push(
+            Row::new(vec![
+                Cell::from("Features"),
+                Cell::from(Text::from(features)),
+            ])
+            .height(height),
+        );
+        heights.push(height);
 
         if let Some(description) = self.crate_info.crate_data.description {
             // assume description is wrapped in 75%
@@ -90,28 +351,36 @@ impl StatefulWidget for CrateInfoTableWidget {
                 .iter()
                 .map(|s| Line::from(s.to_string()))
                 .collect_vec();
-            let height = desc.len();
+            let height = desc.len() as u16;
             rows.push(
                 Row::new(vec![
                     Cell::from("Description"),
                     Cell::from(Text::from(desc)),
                 ])
-                .height(height as u16),
+                .height(height),
             );
+            heights.push(height);
         }
-        if let Some(homepage) = self.crate_info.crate_data.homepage {
-            rows.push(Row::new(vec![Cell::from("Homepage"), Cell::from(homepage)]));
-        }
-        if let Some(repository) = self.crate_info.crate_data.repository {
-            rows.push(Row::new(vec![
-                Cell::from("Repository"),
-                Cell::from(repository),
-            ]));
-        }
+        let urls = [
+            ("Homepage", self.crate_info.crate_data.homepage),
+            ("Repository", self.crate_info.crate_data.repository),
+        ]
+        .into_iter()
+        .filter_map(|(name, url)| Some((name, url?)))
+        .map(|(name, url)| {
+            rows.push(Row::new(vec![Cell::from(name), Cell::from(url.clone())]));
+            heights.push(1);
+            (rows.len() - 1, url)
+        })
+        .collect::<HashMap<_, _>>();
+        rows.push(Row::new(vec![
+            Cell::from("Downloads"),
+            Cell::from(format::format_count(self.crate_info.crate_data.downloads)),
+        ]));
         if let Some(recent_downloads) = self.crate_info.crate_data.recent_downloads {
             rows.push(Row::new(vec![
                 Cell::from("Recent Downloads"),
-                Cell::from(recent_downloads.to_string()),
+                Cell::from(format::format_downloads(recent_downloads)),
             ]));
         }
         if let Some(max_stable_version) = self.crate_info.crate_data.max_stable_version {
@@ -123,6 +392,15 @@ impl StatefulWidget for CrateInfoTableWidget {
 
         let selected_max = rows.len().saturating_sub(1);
 
+        let mut block = Block::default().borders(Borders::ALL);
+        if self.loading {
+            block = block.title(
+                Line::from(" Loading… ")
+                    .fg(config::get().color.base03)
+                    .right_aligned(),
+            );
+        }
+        let inner = block.inner(area);
         let widths = [Constraint::Fill(1), Constraint::Fill(4)];
         let table_widget = Table::new(rows, widths)
             .style(
@@ -130,16 +408,158 @@ impl StatefulWidget for CrateInfoTableWidget {
                     .fg(config::get().color.base05)
                     .bg(config::get().color.base00),
             )
-            .block(Block::default().borders(Borders::ALL))
-            .highlight_symbol("\u{2022} ")
+            .block(block)
+            .highlight_symbol(HIGHLIGHT_SYMBOL)
             .row_highlight_style(config::get().color.base05)
             .highlight_spacing(HighlightSpacing::Always);
 
-        if let Some(i) = state.crate_info.selected() {
+        if self.selecting_features {
+            state.crate_info.select(Some(features_row));
+        } else if let Some(i) = state.crate_info.selected() {
             state.crate_info.select(Some(i.min(selected_max)));
         } else {
             state.crate_info.select(Some(0));
         }
         StatefulWidget::render(table_widget, area, buf, &mut state.crate_info);
+
+        // the value column, laid out like the table does
+        let [_, columns] = Layout::horizontal([
+            Constraint::Length(HIGHLIGHT_SYMBOL.width() as u16),
+            Constraint::Fill(0),
+        ])
+        .areas(inner);
+        let [_, values] = Layout::horizontal(widths).spacing(1).areas(columns);
+        state.hyperlinks.clear();
+        let mut y = inner.y;
+        for (row, height) in heights
+            .into_iter()
+            .enumerate()
+            .skip(state.crate_info.offset())
+        {
+            if y >= inner.bottom() {
+                break;
+            }
+            if let Some(url) = urls.get(&row) {
+                let mut width = 0;
+                let text = url
+                    .chars()
+                    .take_while(|c| {
+                        width += c.to_string().width();
+                        width <= values.width as usize
+                    })
+                    .collect::<String>();
+                let position = Position::new(values.x, y);
+                state.hyperlinks.push(Hyperlink {
+                    position,
+                    text,
+                    url: url.clone(),
+                    style: buf[position].style(),
+                });
+            }
+            y += height;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ratatui::{Terminal, backend::TestBackend};
+    use serde_json::json;
+
+    use super::*;
+    use crate::test_utils;
+
+    fn crate_response() -> crates_io_api::CrateResponse {
+        let mut crate_data = test_utils::crate_json("ratatui");
+        crate_data["created_at"] = json!("2020-01-02T03:04:05Z");
+        crate_data["updated_at"] = json!("2024-06-07T08:09:10Z");
+        serde_json::from_value(json!({
+            "categories": [],
+            "crate": crate_data,
+            "keywords": [],
+            "versions": [],
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn renders_created_and_updated_timestamps() {
+        config::init_for_tests();
+        let mut terminal = Terminal::new(TestBackend::new(60, 10)).unwrap();
+        terminal
+            .draw(|frame| {
+                frame.render_stateful_widget(
+                    CrateInfoTableWidget::new(crate_response()),
+                    frame.area(),
+                    &mut CrateInfo::default(),
+                )
+            })
+            .unwrap();
+        let lines = terminal
+            .backend()
+            .buffer()
+            .content
+            .chunks(60)
+            .map(|row| row.iter().map(|cell| cell.symbol()).collect::<String>())
+            .collect_vec();
+        let row = |label: &str| {
+            lines
+                .iter()
+                .find(|line| line.contains(label))
+                .cloned()
+                .unwrap_or_default()
+        };
+        assert!(row("Created At").contains("2020-01-02 03:04:05"));
+        assert!(row("Updated At").contains("2024-06-07 08:09:10"));
+    }
+
+    #[test]
+    fn hyperlinks_cover_the_drawn_urls() {
+        config::init_for_tests();
+        let mut response = crate_response();
+        response.crate_data.description = Some("A library to build rich terminal UIs".into());
+        response.crate_data.homepage = Some("https://ratatui.rs".into());
+        response.crate_data.repository = Some("https://github.com/ratatui/ratatui".into());
+        let mut terminal = Terminal::new(TestBackend::new(40, 20)).unwrap();
+        let mut crate_info = CrateInfo::default();
+        terminal
+            .draw(|frame| {
+                frame.render_stateful_widget(
+                    CrateInfoTableWidget::new(response),
+                    frame.area(),
+                    &mut crate_info,
+                )
+            })
+            .unwrap();
+
+        let buffer = terminal.backend().buffer();
+        let drawn = |link: &Hyperlink| {
+            (0..link.text.len() as u16)
+                .map(|offset| buffer[(link.position.x + offset, link.position.y)].symbol())
+                .collect::<String>()
+        };
+        let links = crate_info.hyperlinks();
+        assert_eq!(links.len(), 2);
+        assert_eq!(links[0].text, "https://ratatui.rs");
+        assert_eq!(drawn(&links[0]), links[0].text);
+        // cut off by the column width
+        assert_eq!(links[1].text, "https://github.com/ratatui/r");
+        assert_eq!(drawn(&links[1]), links[1].text);
+    }
+
+    #[test]
+    fn feature_selection_is_dropped_for_another_crate() {
+        let features = ["derive".to_string(), "serde".to_string()];
+        let mut crate_info = CrateInfo::default();
+        crate_info.next_feature("ratatui", 5, features.len());
+        crate_info.toggle_feature("ratatui", &features);
+        crate_info.previous_feature("ratatui", 1);
+        crate_info.toggle_feature("ratatui", &features);
+        assert_eq!(crate_info.selected_features("ratatui"), features);
+
+        crate_info.toggle_feature("ratatui", &features);
+        assert_eq!(crate_info.selected_features("ratatui"), ["serde"]);
+        assert!(crate_info.selected_features("tokio").is_empty());
+        assert!(crate_info.selected_features("ratatui").is_empty());
     }
 }
@@ -0,0 +1,116 @@
+use itertools::Itertools;
+use ratatui::{prelude::*, widgets::*};
+
+use crate::{app::Mode, command::Command, config};
+
+/// A one-line reminder of the most useful key bindings in the current mode,
+/// for those who haven't found the help screen yet.
+pub struct FooterHintsWidget {
+    mode: Mode,
+}
+
+impl FooterHintsWidget {
+    pub fn new(mode: Mode) -> Self {
+        Self { mode }
+    }
+
+    /// The commands hinted at in the mode, most useful first.
+    fn hints(&self) -> &'static [(Command, &'static str)] {
+        match self.mode {
+            Mode::Summary => &[
+                (Command::SummarySelect, "open"),
+                (Command::NextTab, "search"),
+                (Command::SwitchMode(Mode::Help), "help"),
+                (Command::Quit, "quit"),
+            ],
+            Mode::Search => &[
+                (Command::SubmitSearch, "submit"),
+                (Command::SwitchMode(Mode::PickerHideCrateInfo), "cancel"),
+                (Command::SwitchMode(Mode::Help), "help"),
+            ],
+            Mode::Filter => &[
+                (Command::SwitchMode(Mode::PickerHideCrateInfo), "apply"),
+                (Command::SwitchMode(Mode::Help), "help"),
+            ],
+            Mode::PickerShowCrateInfo | Mode::PickerHideCrateInfo => &[
+                (Command::SwitchMode(Mode::Search), "search"),
+                (Command::SwitchMode(Mode::Filter), "filter"),
+                (Command::ToggleShowCrateInfo, "details"),
+                (Command::SwitchMode(Mode::Help), "help"),
+            ],
+            Mode::Bookmarks => &[
+                (Command::ToggleBookmark, "remove"),
+                (Command::SwitchMode(Mode::PickerHideCrateInfo), "back"),
+                (Command::SwitchMode(Mode::Help), "help"),
+            ],
+            Mode::FeatureSelect => &[
+                (Command::ToggleFeature, "toggle"),
+                (Command::CopyCargoAddWithFeatures, "copy cargo add"),
+                (Command::SwitchMode(Mode::PickerShowCrateInfo), "back"),
+            ],
+            Mode::Trending => &[
+                (Command::ToggleBookmark, "bookmark"),
+                (Command::RefreshTrending, "refresh"),
+                (Command::SwitchMode(Mode::PickerHideCrateInfo), "back"),
+                (Command::SwitchMode(Mode::Help), "help"),
+            ],
+            Mode::Categories => &[
+                (Command::SearchCategory, "list crates"),
+                (Command::RefreshCategories, "reload"),
+                (Command::SwitchMode(Mode::PickerHideCrateInfo), "back"),
+                (Command::SwitchMode(Mode::Help), "help"),
+            ],
+            Mode::Compare => &[
+                (Command::RefreshComparison, "reload"),
+                (Command::SwitchMode(Mode::PickerHideCrateInfo), "back"),
+                (Command::SwitchMode(Mode::Help), "help"),
+            ],
+            Mode::Lockfile => &[
+                (Command::SwitchMode(Mode::PickerHideCrateInfo), "back"),
+                (Command::SwitchMode(Mode::Help), "help"),
+            ],
+            Mode::Help => &[
+                (Command::StartHelpFilter, "filter"),
+                (Command::SwitchToLastMode, "back"),
+            ],
+            Mode::Popup => &[
+                (Command::ClosePopup, "close"),
+                (Command::ConfirmPopup, "confirm"),
+                (Command::RetryPopup, "retry"),
+            ],
+            // the palette handles its own keys
+            Mode::Common | Mode::CommandPalette | Mode::Quit => &[],
+        }
+    }
+
+    /// Returns the first key bound to `command` in the mode, or in every mode.
+    fn key_for(&self, command: Command) -> Option<String> {
+        let key_bindings = &config::get().key_bindings;
+        [self.mode, Mode::Common].into_iter().find_map(|mode| {
+            key_bindings
+                .get_config_for_command(mode, command)
+                .into_iter()
+                .sorted()
+                .next()
+        })
+    }
+}
+
+impl Widget for FooterHintsWidget {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let color = config::get().color;
+        let hints = self.hints().iter().filter_map(|(command, label)| {
+            let key = self.key_for(*command)?;
+            Some(vec![
+                key.bold().fg(color.base0d),
+                format!(" {label}").fg(color.base04),
+            ])
+        });
+        let hints = Itertools::intersperse(hints, vec!["  ".into()])
+            .flatten()
+            .collect_vec();
+        Paragraph::new(Line::from(hints))
+            .bg(color.base01)
+            .render(area, buf);
+    }
+}
@@ -1,3 +1,4 @@
+use crossterm::event::{KeyCode, KeyEvent};
 use itertools::Itertools;
 use ratatui::{prelude::*, widgets::*};
 
@@ -11,11 +12,59 @@ use crate::{
 pub struct Help {
     pub state: TableState,
     pub mode: Option<Mode>,
+
+    /// Only key bindings whose command or keys contain this are listed.
+    pub filter: String,
+
+    /// Whether key presses are currently typed into `filter`.
+    pub filtering: bool,
+
+    /// The number of rows that fit in the table at the last render.
+    pub page_size: usize,
 }
 
 impl Help {
     pub fn new(state: TableState, mode: Option<Mode>) -> Self {
-        Self { state, mode }
+        Self {
+            state,
+            mode,
+            filter: String::new(),
+            filtering: false,
+            page_size: 1,
+        }
+    }
+
+    pub fn start_filter(&mut self) {
+        self.filtering = true;
+    }
+
+    pub fn clear_filter(&mut self) {
+        self.filter.clear();
+        self.filtering = false;
+    }
+
+    /// Edits the filter while it is being typed.
+    ///
+    /// `Enter` keeps the filter, `Esc` clears it.
+    pub fn handle_filter_key(&mut self, key: KeyEvent) {
+        match key.code {
+            KeyCode::Char(c) => self.filter.push(c),
+            KeyCode::Backspace => {
+                self.filter.pop();
+            }
+            KeyCode::Enter => self.filtering = false,
+            KeyCode::Esc => self.clear_filter(),
+            _ => {}
+        }
+    }
+
+    /// Describes the active filter for the status bar.
+    pub fn filter_status(&self) -> String {
+        if self.filtering || !self.filter.is_empty() {
+            format!("/{}", self.filter)
+        } else {
+            String::new()
+        }
     }
 
     pub fn scroll_up(&mut self) {
@@ -31,8 +80,6 @@ impl Help {
 
 pub struct HelpWidget;
 
-const HIGHLIGHT_SYMBOL: &str = "█ ";
-
 impl StatefulWidget for &HelpWidget {
     type State = Help;
     fn render(self, area: Rect, buf: &mut Buffer, state: &mut Self::State) {
@@ -40,7 +87,7 @@ impl StatefulWidget for &HelpWidget {
         let [_, area] = Layout::vertical([Min(0), Percentage(90)]).areas(area);
         let [_, area, _] = Layout::horizontal([Min(0), Percentage(85), Min(0)]).areas(area);
 
-        let all_key_bindings = all_key_bindings();
+        let all_key_bindings = filter_key_bindings(all_key_bindings(), &state.filter);
         select_by_mode(state, &all_key_bindings);
 
         let widths = [Max(10), Max(10), Min(0)];
@@ -50,10 +97,12 @@ impl StatefulWidget for &HelpWidget {
         let table = Table::new(into_rows(&all_key_bindings), widths)
             .header(header)
             .column_spacing(5)
-            .highlight_symbol(HIGHLIGHT_SYMBOL)
-            .row_highlight_style(config::get().color.base05)
+            .highlight_symbol(format!("{} ", config::get().highlight_symbol()))
+            .row_highlight_style(config::get().highlight_style())
             .highlight_spacing(HighlightSpacing::Always);
         StatefulWidget::render(table, area, buf, &mut state.state);
+        // the header takes up the first row
+        state.page_size = (area.height as usize).saturating_sub(1).max(1);
     }
 }
 
@@ -73,6 +122,22 @@ fn all_key_bindings() -> Vec<(Mode, Command, String)> {
         .collect_vec()
 }
 
+/// Keeps the key bindings whose command name or keys contain `filter`,
+/// ignoring case.
+fn filter_key_bindings(
+    key_bindings: Vec<(Mode, Command, String)>,
+    filter: &str,
+) -> Vec<(Mode, Command, String)> {
+    let filter = filter.to_lowercase();
+    key_bindings
+        .into_iter()
+        .filter(|(_, command, keys)| {
+            format!("{command:?}").to_lowercase().contains(&filter)
+                || keys.to_lowercase().contains(&filter)
+        })
+        .collect()
+}
+
 /// Returns the key bindings for a specific command and mode
 fn key_bindings_for_command(mode: Mode, command: Command) -> Vec<String> {
     config::get()
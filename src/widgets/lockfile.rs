@@ -0,0 +1,267 @@
+use std::{
+    collections::{HashMap, HashSet},
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicBool, Ordering},
+    },
+};
+
+use itertools::Itertools;
+use ratatui::{layout::Flex, prelude::*, widgets::*};
+use semver::Version;
+use tokio::sync::mpsc::UnboundedSender;
+
+use crate::{
+    action::Action,
+    config, crates_io_api_helper,
+    local_deps::{self, LockedPackage},
+};
+
+/// The most crates whose latest version is fetched in a single request.
+const BATCH_SIZE: usize = 100;
+
+#[derive(Debug)]
+pub struct Lockfile {
+    /// The crates.io packages pinned in `Cargo.lock`.
+    pub packages: Vec<LockedPackage>,
+
+    pub table_state: TableState,
+    pub scrollbar_state: ScrollbarState,
+
+    /// The number of rows that fit in the table at the last render.
+    pub page_size: usize,
+
+    /// The latest stable version of each crate, fetched from crates.io as its
+    /// rows come into view.
+    pub latest_versions: Arc<Mutex<HashMap<String, String>>>,
+
+    /// The crates whose latest version has already been requested. A failed
+    /// batch is removed again so that it is retried.
+    requested: Arc<Mutex<HashSet<String>>>,
+
+    /// Why `Cargo.lock` couldn't be read, shown in place of the table.
+    error: Option<String>,
+
+    /// Sender end of an asynchronous channel for dispatching actions from
+    /// various parts of the app to be handled by the event loop.
+    tx: UnboundedSender<Action>,
+
+    /// A thread-safe indicator of whether data is currently being loaded,
+    /// allowing different parts of the app to know if it's in a loading state.
+    loading_status: Arc<AtomicBool>,
+}
+
+impl Lockfile {
+    pub fn new(tx: UnboundedSender<Action>, loading_status: Arc<AtomicBool>) -> Self {
+        Self {
+            packages: Default::default(),
+            table_state: Default::default(),
+            scrollbar_state: Default::default(),
+            page_size: 1,
+            latest_versions: Default::default(),
+            requested: Default::default(),
+            error: None,
+            tx,
+            loading_status,
+        }
+    }
+
+    /// Reads `Cargo.lock` from the current directory, keeping the latest
+    /// versions fetched so far.
+    pub fn load(&mut self) {
+        let packages = std::env::current_dir()
+            .map_err(Into::into)
+            .and_then(|directory| local_deps::load_lockfile(&directory));
+        match packages {
+            Ok(packages) => {
+                self.packages = packages;
+                self.error = None;
+            }
+            Err(err) => {
+                self.packages.clear();
+                self.error = Some(format!("Unable to read Cargo.lock: {err}"));
+            }
+        }
+        self.scrollbar_state = self.scrollbar_state.content_length(self.packages.len());
+        let selected = self.table_state.selected().unwrap_or_default();
+        self.scroll_to(selected);
+    }
+
+    /// Spawns an asynchronous task to fetch the latest versions of the next
+    /// batch of crates from the top of the page that haven't been requested
+    /// yet.
+    fn request_latest_versions(&mut self) {
+        let selected = self.table_state.selected().unwrap_or_default();
+        let start = selected.saturating_sub(self.page_size);
+        let names = {
+            let mut requested = self.requested.lock().unwrap();
+            self.packages
+                .iter()
+                .skip(start)
+                .take(BATCH_SIZE)
+                .map(|package| package.name.clone())
+                .filter(|name| requested.insert(name.clone()))
+                .collect_vec()
+        };
+        if names.is_empty() {
+            return;
+        }
+        let tx = self.tx.clone();
+        let loading_status = self.loading_status.clone();
+        let latest_versions = self.latest_versions.clone();
+        let requested = self.requested.clone();
+        tokio::spawn(async move {
+            loading_status.store(true, Ordering::SeqCst);
+            if let Err(err) =
+                crates_io_api_helper::request_latest_versions(names.clone(), latest_versions).await
+            {
+                let mut requested = requested.lock().unwrap();
+                for name in &names {
+                    requested.remove(name);
+                }
+                drop(requested);
+                crates_io_api_helper::report_error(&tx, "fetching latest versions", &err, None);
+            }
+            loading_status.store(false, Ordering::SeqCst);
+            let _ = tx.send(Action::Refresh);
+        });
+    }
+
+    pub fn scroll_previous(&mut self, count: usize) {
+        let i = self
+            .table_state
+            .selected()
+            .map_or(0, |i| i.saturating_sub(count));
+        self.scroll_to(i);
+    }
+
+    pub fn scroll_next(&mut self, count: usize) {
+        let i = self
+            .table_state
+            .selected()
+            .map_or(0, |i| i.saturating_add(count));
+        self.scroll_to(i);
+    }
+
+    pub fn scroll_to_top(&mut self) {
+        self.scroll_to(0);
+    }
+
+    pub fn scroll_to_bottom(&mut self) {
+        self.scroll_to(usize::MAX);
+    }
+
+    fn scroll_to(&mut self, index: usize) {
+        if self.packages.is_empty() {
+            self.table_state.select(None);
+        } else {
+            let index = index.min(self.packages.len() - 1);
+            self.table_state.select(Some(index));
+            self.scrollbar_state = self.scrollbar_state.position(index);
+            self.request_latest_versions();
+        }
+    }
+}
+
+/// Whether `latest` is a newer release than `locked` by semver precedence.
+/// Versions that don't parse are never outdated.
+fn is_outdated(locked: &str, latest: &str) -> bool {
+    match (Version::parse(locked), Version::parse(latest)) {
+        (Ok(locked), Ok(latest)) => latest.cmp_precedence(&locked).is_gt(),
+        _ => false,
+    }
+}
+
+pub struct LockfileWidget;
+
+impl StatefulWidget for LockfileWidget {
+    type State = Lockfile;
+
+    fn render(self, area: Rect, buf: &mut Buffer, state: &mut Self::State) {
+        use Constraint::*;
+
+        if state.packages.is_empty() {
+            let message = state
+                .error
+                .clone()
+                .unwrap_or_else(|| "No crates.io dependencies in Cargo.lock".into());
+            let [center] = Layout::vertical([Length(1)]).flex(Flex::Center).areas(area);
+            Line::from(message)
+                .fg(config::get().color.base03)
+                .centered()
+                .render(center, buf);
+            return;
+        }
+
+        let [table_area, scrollbar_area] = Layout::horizontal([Fill(1), Length(1)]).areas(area);
+        let [_, scrollbar_area] = Layout::vertical([Length(1), Fill(1)]).areas(scrollbar_area);
+
+        Scrollbar::default()
+            .track_symbol(Some(" "))
+            .thumb_symbol("▐")
+            .begin_symbol(None)
+            .end_symbol(None)
+            .track_style(config::get().color.base06)
+            .render(scrollbar_area, buf, &mut state.scrollbar_state);
+
+        let header = Row::new(["Name", "Locked", "Latest", "Status"].map(|h| Line::from(h.bold())))
+            .fg(config::get().color.base05)
+            .bg(config::get().color.base00);
+        let latest_versions = state.latest_versions.lock().unwrap().clone();
+        let rows = state
+            .packages
+            .iter()
+            .enumerate()
+            .map(|(index, package)| {
+                let bg = match index % 2 {
+                    0 => config::get().color.base00,
+                    _ => config::get().color.base01,
+                };
+                let latest = latest_versions.get(&package.name);
+                let status = match latest {
+                    None => "…".fg(config::get().color.base03),
+                    Some(latest) if is_outdated(&package.version, latest) => {
+                        "outdated".fg(config::get().color.base09)
+                    }
+                    Some(_) => "up to date".fg(config::get().color.base0b),
+                };
+                Row::new([
+                    Line::from(package.name.clone()),
+                    Line::from(package.version.clone()),
+                    Line::from(latest.cloned().unwrap_or_default()),
+                    Line::from(status),
+                ])
+                .fg(config::get().color.base05)
+                .bg(bg)
+            })
+            .collect_vec();
+
+        let table = Table::new(rows, [Fill(1), Max(16), Max(16), Max(10)])
+            .header(header)
+            .column_spacing(3)
+            .highlight_symbol(format!(" {} ", config::get().highlight_symbol()))
+            .row_highlight_style(Style::default().bg(config::get().color.base02))
+            .highlight_spacing(HighlightSpacing::Always);
+        StatefulWidget::render(table, table_area, buf, &mut state.table_state);
+        // the header takes up the first row
+        state.page_size = (table_area.height as usize).saturating_sub(1).max(1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn outdated_follows_semver_precedence() {
+        assert!(is_outdated("1.0.0", "1.0.1"));
+        assert!(is_outdated("0.9.10", "0.10.0"));
+        assert!(is_outdated("1.0.0-alpha", "1.0.0"));
+        assert!(is_outdated("1.0.0-alpha.1", "1.0.0-beta"));
+        assert!(!is_outdated("1.0.0", "1.0.0"));
+        assert!(!is_outdated("1.0.0", "1.0.0+build"));
+        assert!(!is_outdated("1.0.0", "1.0.0-rc.1"));
+        assert!(!is_outdated("2.0.0", "1.9.9"));
+        assert!(!is_outdated("1.0", "2.0.0"));
+    }
+}
@@ -1,6 +1,21 @@
 use itertools::Itertools;
 use ratatui::{layout::Flex, prelude::*, widgets::*};
 
+use crate::{action::Action, app::Mode, command::Command, config};
+
+/// What a popup is for.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PopupKind {
+    /// Shows a message until it is closed.
+    Message,
+    /// Asks a yes/no question and performs the action if the user confirms.
+    Confirm(Action),
+    /// Shows an error and performs the action if the user retries.
+    Retry(Action),
+    /// Shows a notice and performs the action if the user cancels.
+    Cancel(Action),
+}
+
 #[derive(Debug, Default, Clone, Copy)]
 pub struct PopupMessageState {
     scroll: usize,
@@ -24,12 +39,84 @@ impl PopupMessageState {
 pub struct PopupMessageWidget {
     title: String,
     message: String,
+    kind: PopupKind,
 }
 
 impl PopupMessageWidget {
     pub fn new(title: String, message: String) -> Self {
-        Self { title, message }
+        Self {
+            title,
+            message,
+            kind: PopupKind::Message,
+        }
+    }
+
+    /// Creates a yes/no popup that performs `action` when confirmed.
+    pub fn confirm(title: String, message: String, action: Action) -> Self {
+        Self {
+            title,
+            message,
+            kind: PopupKind::Confirm(action),
+        }
+    }
+
+    /// Creates an error popup that performs `action` when retried.
+    pub fn retry(title: String, message: String, action: Action) -> Self {
+        Self {
+            title,
+            message,
+            kind: PopupKind::Retry(action),
+        }
+    }
+
+    /// Creates a notice that performs `action` when cancelled.
+    pub fn cancel(title: String, message: String, action: Action) -> Self {
+        Self {
+            title,
+            message,
+            kind: PopupKind::Cancel(action),
+        }
     }
+
+    /// Returns the action to perform if this popup is confirmed.
+    pub fn confirm_action(&self) -> Option<&Action> {
+        match &self.kind {
+            PopupKind::Confirm(action) => Some(action),
+            PopupKind::Message | PopupKind::Retry(_) | PopupKind::Cancel(_) => None,
+        }
+    }
+
+    /// Returns the action to perform if this popup is retried.
+    pub fn retry_action(&self) -> Option<&Action> {
+        match &self.kind {
+            PopupKind::Retry(action) => Some(action),
+            PopupKind::Message | PopupKind::Confirm(_) | PopupKind::Cancel(_) => None,
+        }
+    }
+
+    /// Returns the action to perform if this popup is cancelled.
+    pub fn cancel_action(&self) -> Option<&Action> {
+        match &self.kind {
+            PopupKind::Cancel(action) => Some(action),
+            PopupKind::Message | PopupKind::Confirm(_) | PopupKind::Retry(_) => None,
+        }
+    }
+}
+
+/// Returns the key bound to `command` in popups, or the command's name if it
+/// isn't bound, so that the instruction line matches the user's key bindings.
+fn key_for(command: Command) -> String {
+    let key_bindings = &config::get().key_bindings;
+    [Mode::Popup, Mode::Common]
+        .into_iter()
+        .find_map(|mode| {
+            key_bindings
+                .get_config_for_command(mode, command)
+                .into_iter()
+                .sorted()
+                .next()
+        })
+        .unwrap_or_else(|| command.to_string())
 }
 
 impl StatefulWidget for &PopupMessageWidget {
@@ -49,7 +136,31 @@ impl StatefulWidget for &PopupMessageWidget {
             .areas(center);
 
         state.scroll = state.scroll.min(line_count.saturating_sub(1));
-        let instruction = Line::from(vec!["Esc".bold(), " to close".into()]).right_aligned();
+        let instruction = match self.kind {
+            PopupKind::Message => Line::from(vec![
+                key_for(Command::CancelPopup).bold(),
+                " to close".into(),
+            ]),
+            PopupKind::Retry(_) => Line::from(vec![
+                key_for(Command::RetryPopup).bold(),
+                " to retry, ".into(),
+                key_for(Command::CancelPopup).bold(),
+                " to close".into(),
+            ]),
+            PopupKind::Confirm(_) => Line::from(vec![
+                key_for(Command::ConfirmPopup).bold(),
+                " to confirm, ".into(),
+                key_for(Command::CancelPopup).bold(),
+                " to cancel".into(),
+            ]),
+            PopupKind::Cancel(_) => Line::from(vec![
+                key_for(Command::ClosePopup).bold(),
+                " to keep waiting, ".into(),
+                key_for(Command::CancelPopup).bold(),
+                " to cancel".into(),
+            ]),
+        }
+        .right_aligned();
         let block = Block::bordered()
             .border_style(Color::DarkGray)
             .title(self.title.clone())
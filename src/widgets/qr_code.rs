@@ -0,0 +1,131 @@
+use itertools::Itertools;
+use ratatui::{layout::Flex, prelude::*, widgets::*};
+use unicode_width::UnicodeWidthStr;
+
+use crate::{config, qr_code::QrCode};
+
+/// The light modules around the code that scanners need to find it.
+const QUIET_ZONE: usize = 4;
+
+/// Shows a QR code of a URL in a popup, so that it can be opened on a phone.
+///
+/// Two rows of modules are drawn per line with half blocks. The code is
+/// always dark on light, as not every scanner reads inverted codes.
+#[derive(Debug, Clone)]
+pub struct QrCodeWidget {
+    url: String,
+    code: QrCode,
+}
+
+impl QrCodeWidget {
+    /// Returns `None` if the URL is too long to encode.
+    pub fn new(url: String) -> Option<Self> {
+        let code = QrCode::encode(url.as_bytes())?;
+        Some(Self { url, code })
+    }
+
+    fn is_dark(&self, x: usize, y: usize) -> bool {
+        let (Some(x), Some(y)) = (x.checked_sub(QUIET_ZONE), y.checked_sub(QUIET_ZONE)) else {
+            return false;
+        };
+        self.code.is_dark(x, y)
+    }
+}
+
+impl Widget for &QrCodeWidget {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        use Constraint::*;
+
+        let modules = self.code.size() + 2 * QUIET_ZONE;
+        let grid_width = modules as u16;
+        let grid_height = modules.div_ceil(2) as u16;
+        let url_width = self.url.width() as u16;
+        // the borders, and a line for the URL below the grid
+        let width = grid_width.max(url_width) + 2;
+        let height = grid_height + 3;
+        let color = config::get().color;
+        let instruction = Line::from(vec!["Esc".bold(), " to close".into()]).right_aligned();
+        let block = Block::bordered()
+            .border_style(Color::DarkGray)
+            .title(" QR Code ")
+            .title_bottom(instruction);
+
+        if width > area.width || height > area.height {
+            let [center] = Layout::horizontal([Percentage(50)])
+                .flex(Flex::Center)
+                .areas(area);
+            let message = format!(
+                "Terminal too small for the QR code (need {width}x{height}): {}",
+                self.url
+            );
+            // wrap inside the borders, and size the popup to fit every line
+            let lines = textwrap::wrap(&message, center.width.saturating_sub(2).max(1) as usize)
+                .iter()
+                .map(|line| Line::from(line.to_string()))
+                .collect_vec();
+            let [center] = Layout::vertical([Length(lines.len() as u16 + 2)])
+                .flex(Flex::Center)
+                .areas(center);
+            Clear.render(center, buf);
+            Paragraph::new(lines)
+                .fg(color.base08)
+                .block(block)
+                .render(center, buf);
+            return;
+        }
+
+        let [center] = Layout::horizontal([Length(width)])
+            .flex(Flex::Center)
+            .areas(area);
+        let [center] = Layout::vertical([Length(height)])
+            .flex(Flex::Center)
+            .areas(center);
+        Clear.render(center, buf);
+        let inner = block.inner(center);
+        block.render(center, buf);
+        let [grid, url] = Layout::vertical([Length(grid_height), Length(1)]).areas(inner);
+        let [grid] = Layout::horizontal([Length(grid_width)])
+            .flex(Flex::Center)
+            .areas(grid);
+
+        let module_color = |dark| if dark { Color::Black } else { Color::White };
+        for row in 0..grid.height {
+            for column in 0..grid.width {
+                let (x, y) = (column as usize, row as usize * 2);
+                buf[(grid.x + column, grid.y + row)]
+                    .set_symbol("▀")
+                    .set_fg(module_color(self.is_dark(x, y)))
+                    .set_bg(module_color(self.is_dark(x, y + 1)));
+            }
+        }
+        Line::from(self.url.as_str())
+            .fg(color.base0d)
+            .centered()
+            .render(url, buf);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ratatui::{Terminal, backend::TestBackend};
+
+    use super::*;
+
+    #[test]
+    fn too_small_message_shows_the_whole_url() {
+        config::init_for_tests();
+        let widget = QrCodeWidget::new("https://crates.io/crates/ratatui".into()).unwrap();
+        let mut terminal = Terminal::new(TestBackend::new(40, 12)).unwrap();
+        terminal
+            .draw(|frame| frame.render_widget(&widget, frame.area()))
+            .unwrap();
+        let text = terminal
+            .backend()
+            .buffer()
+            .content()
+            .iter()
+            .map(|cell| cell.symbol())
+            .collect::<String>();
+        assert!(text.contains("ratatui"), "{text}");
+    }
+}
@@ -22,6 +22,7 @@ pub struct SearchFilterPromptWidget<'a> {
     vertical_margin: u16,
     horizontal_margin: u16,
     search_mode: SearchMode,
+    sort_ascending: bool,
 }
 
 impl<'a> SearchFilterPromptWidget<'a> {
@@ -31,15 +32,34 @@ impl<'a> SearchFilterPromptWidget<'a> {
         input: &'a tui_input::Input,
         search_mode: SearchMode,
     ) -> Self {
+        // the border takes up one row and column on each side
+        let margin = 1 + config::get().prompt_padding;
         Self {
             mode,
             sort,
             input,
-            vertical_margin: 2,
-            horizontal_margin: 2,
+            vertical_margin: margin,
+            horizontal_margin: margin,
             search_mode,
+            sort_ascending: false,
         }
     }
+
+    /// Shows an arrow for the sort direction next to the sort name.
+    pub fn sort_ascending(mut self, sort_ascending: bool) -> Self {
+        self.sort_ascending = sort_ascending;
+        self
+    }
+
+    /// The number of rows needed to show the input line and, when focused,
+    /// the border and padding around it.
+    pub fn height(&self) -> u16 {
+        1 + 2 * self.margin().vertical
+    }
+
+    fn sort_direction(&self) -> Span<'static> {
+        if self.sort_ascending { " ↑" } else { " ↓" }.fg(config::get().color.base0d)
+    }
 }
 
 impl StatefulWidget for SearchFilterPromptWidget<'_> {
@@ -52,8 +72,8 @@ impl StatefulWidget for SearchFilterPromptWidget<'_> {
         if self.search_mode.is_focused() {
             self.sort_by_info().render(meta.inner(self.margin()), buf);
         }
-        self.input_text(input.width as usize)
-            .render(input.inner(self.margin()), buf);
+        let input = input.inner(self.margin());
+        self.input_text(input.width as usize).render(input, buf);
 
         self.update_cursor_state(area, state);
     }
@@ -82,12 +102,14 @@ impl SearchFilterPromptWidget<'_> {
         Paragraph::new(Line::from(vec![
             "Sort By: ".into(),
             format!("{:?}", self.sort.clone()).fg(config::get().color.base0d),
+            self.sort_direction(),
         ]))
         .right_aligned()
     }
 
     fn input_text(&self, width: usize) -> impl Widget + '_ {
-        let scroll = self.input.cursor().saturating_sub(width.saturating_sub(4));
+        // keep the cursor on the last visible column
+        let scroll = self.input.cursor().saturating_sub(width.saturating_sub(1));
         let text = if self.search_mode.is_focused() {
             Line::from(vec![self.input.value().into()])
         } else if self.mode.is_summary() || self.mode.is_help() {
@@ -97,6 +119,7 @@ impl SearchFilterPromptWidget<'_> {
                 self.input.value().into(),
                 " (".into(),
                 format!("{:?}", self.sort.clone()).fg(config::get().color.base0d),
+                self.sort_direction(),
                 ")".into(),
             ])
         };
@@ -104,12 +127,14 @@ impl SearchFilterPromptWidget<'_> {
     }
 
     fn update_cursor_state(&self, area: Rect, state: &mut SearchFilterPrompt) {
-        let width = ((area.width as f64 * 0.75) as u16).saturating_sub(2);
         if self.search_mode.is_focused() {
             let margin = self.margin();
+            let [input, _] = Layout::horizontal([Percentage(75), Fill(0)]).areas(area);
+            let input = input.inner(margin);
+            let max_x = input.right().saturating_sub(1).max(input.x);
             state.cursor_position = Some(Position::new(
-                (area.x + margin.horizontal + self.input.cursor() as u16).min(width),
-                area.y + margin.vertical,
+                (input.x + self.input.cursor() as u16).min(max_x),
+                input.y,
             ));
         } else {
             state.cursor_position = None
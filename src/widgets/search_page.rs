@@ -1,25 +1,30 @@
 use color_eyre::Result;
 use std::{
-    collections::HashMap,
+    collections::{BTreeSet, HashMap},
     sync::{
         Arc, Mutex,
         atomic::{AtomicBool, Ordering},
     },
+    time::{Duration, Instant},
 };
 use strum::EnumIs;
-use tracing::info;
+use tracing::{info, warn};
 
-use crossterm::event::{Event as CrosstermEvent, KeyEvent};
+use crossterm::event::{Event as CrosstermEvent, KeyCode, KeyEvent, KeyModifiers};
 use itertools::Itertools;
 use ratatui::prelude::*;
 use ratatui::{layout::Position, widgets::StatefulWidget};
 use tokio::{sync::mpsc::UnboundedSender, task::JoinHandle};
-use tui_input::{Input, backend::crossterm::EventHandler};
+use tui_input::{Input, InputRequest, backend::crossterm::EventHandler};
 
 use crate::{
     action::Action,
     app::Mode,
-    crates_io_api_helper,
+    bookmarks::Bookmarks,
+    cache::LruCache,
+    config,
+    crates_io_api_helper::{self, DocsStatus, Owner},
+    history::SearchSnapshot,
     widgets::{search_filter_prompt::SearchFilterPrompt, search_results::SearchResults},
 };
 
@@ -28,6 +33,13 @@ use super::{
     search_results::SearchResultsWidget,
 };
 
+/// The number of crates whose details are kept after moving the selection
+/// away from them.
+const DETAILS_CACHE_SIZE: usize = 64;
+
+/// The owners of the crate named first.
+type CrateOwners = Arc<Mutex<Option<(String, Vec<crates_io_api::User>)>>>;
+
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, EnumIs)]
 pub enum SearchMode {
     #[default]
@@ -69,6 +81,19 @@ pub struct SearchPage {
     /// for filtering for the list of crates in the current view.
     pub filter: String,
 
+    /// Crates with fewer downloads than this are hidden from the current view.
+    pub min_downloads: Option<u64>,
+
+    /// Only show crates that link to a repository.
+    pub only_with_repo: bool,
+
+    /// Only show crates whose license contains this, ignoring case.
+    pub license_filter: Option<String>,
+
+    /// Whether the crates or the filter changed since `results` was last
+    /// computed.
+    results_dirty: bool,
+
     /// A table component designed to handle the listing and selection of crates
     /// within the terminal UI.
     pub results: SearchResults,
@@ -82,25 +107,37 @@ pub struct SearchPage {
     pub prompt: SearchFilterPrompt,
 
     /// The current page number being displayed or interacted with in the UI.
+    /// With infinite scroll this is the last page appended to the results.
     pub page: u64,
 
+    /// The page the results start at, which is earlier than `page` once
+    /// further pages have been appended by infinite scroll.
+    first_page: u64,
+
     /// The number of crates displayed per page in the UI.
     pub page_size: u64,
 
     /// Sort preference for search results
     pub sort: crates_io_api::Sort,
 
+    /// Whether results are listed in ascending order. crates.io can't reverse
+    /// a sort, so results are reversed client-side when this differs from the
+    /// natural direction of `sort`.
+    pub sort_ascending: bool,
+
+    /// The slug of the crates.io category the search is restricted to, if any.
+    pub category: Option<String>,
+
+    /// The user or team the search is restricted to, if any.
+    pub owner: Option<Owner>,
+
     /// The total number of crates fetchable from crates.io, which may not be
     /// known initially and can be used for UI elements like pagination.
     pub total_num_crates: Option<u64>,
 
-    /// A thread-safe, shared vector holding the list of crates fetched from
-    /// crates.io, wrapped in a mutex to control concurrent access.
-    pub crates: Arc<Mutex<Vec<crates_io_api::Crate>>>,
-
-    /// A thread-safe, shared vector holding the list of version fetched from
-    /// crates.io, wrapped in a mutex to control concurrent access.
-    pub versions: Arc<Mutex<Vec<crates_io_api::Version>>>,
+    /// The crates and versions fetched from crates.io, shared with the fetch
+    /// task.
+    fetched: crates_io_api_helper::FetchedCrates,
 
     /// A thread-safe shared container holding the detailed information about
     /// the currently selected crate; this can be `None` if no crate is
@@ -112,8 +149,67 @@ pub struct SearchPage {
     /// selected.
     pub crate_response: Arc<Mutex<Option<crates_io_api::CrateResponse>>>,
 
+    /// Recently fetched crate details, so that moving the selection back onto
+    /// a crate shows its details without another request.
+    details_cache: Arc<Mutex<LruCache<crates_io_api::CrateResponse>>>,
+
+    /// The docs.rs build status of the selected crate's latest version,
+    /// with the name of the crate it belongs to.
+    pub docs_status: Arc<Mutex<Option<(String, DocsStatus)>>>,
+
+    /// The owners of the selected crate, fetched once its details are shown.
+    pub crate_owners: CrateOwners,
+
+    /// Whether the details of the selected crate are being fetched, shown in
+    /// the crate info pane.
+    details_loading: Arc<AtomicBool>,
+
     pub last_task_details_handle: HashMap<uuid::Uuid, JoinHandle<()>>,
 
+    /// The requests prefetching the details of the crates next to the
+    /// selection, by crate name.
+    adjacent_prefetch_handles: HashMap<String, JoinHandle<()>>,
+
+    /// The in-flight search request, aborted when a newer search is sent.
+    last_search_task_handle: Option<JoinHandle<()>>,
+
+    /// When the in-flight search was sent, to detect slow and stalled
+    /// searches.
+    search_started: Option<Instant>,
+
+    /// When the last attempt of the in-flight search finished, shared with
+    /// its task.
+    search_progress: Arc<Mutex<Option<Instant>>>,
+
+    /// Whether the user has been warned that the in-flight search is slow.
+    slow_search_warned: bool,
+
+    /// The in-flight prefetch of the page after `page`, aborted when the
+    /// search changes.
+    prefetch_task_handle: Option<JoinHandle<()>>,
+
+    /// The row to select once the results being loaded arrive.
+    pub pending_selection: Option<usize>,
+
+    /// When the search input last changed, if a live search is pending.
+    last_keystroke: Option<Instant>,
+
+    /// Whether the user picked `sort`, with `--sort` or by toggling it. A sort
+    /// that came from the summary or a category is replaced by `default_sort`
+    /// once another query is typed.
+    sort_chosen: bool,
+
+    /// The queries submitted so far, oldest first.
+    query_history: Vec<String>,
+
+    /// The entry of `query_history` shown in the input while walking through
+    /// it with Up and Down.
+    query_history_index: Option<usize>,
+
+    /// The query being typed before walking through `query_history`,
+    /// restored when walking past the newest entry.
+    query_draft: String,
+
     /// Sender end of an asynchronous channel for dispatching actions from
     /// various parts of the app to be handled by the event loop.
     tx: UnboundedSender<Action>,
@@ -130,19 +226,42 @@ impl SearchPage {
             search_mode: Default::default(),
             search: String::new(),
             filter: String::new(),
+            min_downloads: config::get().min_downloads,
+            only_with_repo: false,
+            license_filter: None,
+            results_dirty: false,
             results: SearchResults::default(),
             input: Input::default(),
             prompt: SearchFilterPrompt::default(),
             page: 1,
-            page_size: 25,
-            sort: crates_io_api::Sort::Relevance,
+            first_page: 1,
+            page_size: config::get().page_size,
+            sort: config::get().default_sort.into(),
+            sort_ascending: is_ascending_by_default(&config::get().default_sort.into()),
+            category: None,
+            owner: None,
             total_num_crates: None,
-            crates: Default::default(),
-            versions: Default::default(),
+            fetched: Default::default(),
             full_crate_info: Default::default(),
             crate_info: Default::default(),
             crate_response: Default::default(),
+            docs_status: Default::default(),
+            crate_owners: Default::default(),
+            details_loading: Default::default(),
+            details_cache: Arc::new(Mutex::new(LruCache::new(DETAILS_CACHE_SIZE))),
             last_task_details_handle: Default::default(),
+            adjacent_prefetch_handles: Default::default(),
+            last_search_task_handle: None,
+            search_started: None,
+            search_progress: Default::default(),
+            slow_search_warned: false,
+            prefetch_task_handle: None,
+            pending_selection: None,
+            last_keystroke: None,
+            sort_chosen: false,
+            query_history: Vec::new(),
+            query_history_index: None,
+            query_draft: String::new(),
             tx,
             loading_status,
         }
@@ -152,72 +271,383 @@ impl SearchPage {
         match action {
             Action::ScrollTop => self.results.scroll_to_top(),
             Action::ScrollBottom => self.results.scroll_to_bottom(),
-            Action::ScrollSearchResultsUp => self.scroll_up(),
-            Action::ScrollSearchResultsDown => self.scroll_down(),
+            Action::ScrollSearchResultsUp => self.scroll_up(1),
+            Action::ScrollSearchResultsDown => self.scroll_down(1),
             _ => {}
         }
     }
 
-    pub fn update_search_table_results(&mut self) {
-        self.results.content_length(self.results.crates.len());
+    /// Recomputes the results table if the crates or the filter changed since
+    /// it was last computed.
+    ///
+    /// Returns `true` if the results were recomputed.
+    pub fn refresh_search_table_results(&mut self) -> bool {
+        let dirty = self.results_dirty;
+        if dirty {
+            self.update_search_table_results();
+        }
+        dirty
+    }
 
-        let filter = self.filter.clone();
-        let filter_words = filter.split_whitespace().collect::<Vec<_>>();
+    /// Marks the results table as needing to be recomputed on the next tick.
+    pub fn mark_results_dirty(&mut self) {
+        self.results_dirty = true;
+    }
 
-        let crates: Vec<_> = self
-            .crates
-            .lock()
-            .unwrap()
-            .iter()
-            .filter(|c| {
-                filter_words.iter().all(|word| {
-                    c.name.to_lowercase().contains(word)
-                        || c.description
-                            .clone()
-                            .unwrap_or_default()
-                            .to_lowercase()
-                            .contains(word)
+    pub fn update_search_table_results(&mut self) {
+        let filter_words = self.filter.split_whitespace().collect::<Vec<_>>();
+        let min_downloads = self.min_downloads.unwrap_or_default();
+
+        let license_filter = self.license_filter.as_deref().map(str::to_lowercase);
+        let mut crates = self.fetched.read(|all_crates, versions| {
+            let license = |c: &crates_io_api::Crate| {
+                versions
+                    .iter()
+                    .filter(|v| v.crate_name == c.id)
+                    .find_or_first(|v| v.num == c.max_version)
+                    .and_then(|v| v.license.as_deref())
+                    .map(str::to_lowercase)
+            };
+
+            all_crates
+                .iter()
+                .filter(|c| c.downloads >= min_downloads)
+                .filter(|c| !self.only_with_repo || c.repository.is_some())
+                .filter(|c| {
+                    license_filter.as_ref().is_none_or(|filter| {
+                        license(c).is_some_and(|license| license.contains(filter.as_str()))
+                    })
                 })
-            })
-            .cloned()
-            .collect_vec();
+                .filter(|c| {
+                    filter_words.iter().all(|word| {
+                        c.name.to_lowercase().contains(word)
+                            || c.description
+                                .as_deref()
+                                .unwrap_or_default()
+                                .to_lowercase()
+                                .contains(word)
+                    })
+                })
+                .cloned()
+                .collect_vec()
+        });
+        if self.sort_ascending != is_ascending_by_default(&self.sort) {
+            crates.reverse();
+        }
         self.results.crates = crates;
+        self.results.content_length(self.results.crates.len());
+        self.results_dirty = false;
+    }
+
+    pub fn scroll_up(&mut self, count: usize) {
+        self.results.scroll_previous(count);
+    }
+
+    /// Moves the selection down, loading the next page once the last crate
+    /// is reached if infinite scroll is enabled.
+    pub fn scroll_down(&mut self, count: usize) {
+        let infinite_scroll = config::get().infinite_scroll && self.can_append_next_page();
+        let last = self.results.crates.len().saturating_sub(1);
+        if infinite_scroll && self.results.selected() == Some(last) {
+            // don't wrap around while there is more to load
+            let _ = self.tx.send(Action::IncrementPage);
+            return;
+        }
+        self.results.scroll_next(count);
+        if infinite_scroll && self.results.selected() == Some(last) {
+            let _ = self.tx.send(Action::IncrementPage);
+        }
+    }
+
+    /// Edits the prompt input.
+    ///
+    /// Returns `true` if the key was a readline editing key, which shouldn't
+    /// also be looked up in the key bindings. Ctrl and Alt keys bound in the
+    /// current mode are left to the key bindings, so a binding on e.g.
+    /// `ctrl-k` wins over its readline meaning.
+    pub fn handle_key(&mut self, key: KeyEvent) -> bool {
+        if self.search_mode.is_search() && self.handle_history_key(key) {
+            return true;
+        }
+        if self.is_bound_modifier_key(key) {
+            return false;
+        }
+        let readline = handle_readline_key(&mut self.input, key);
+        let changed = readline.unwrap_or_else(|| {
+            self.input
+                .handle_event(&CrosstermEvent::Key(key))
+                .is_some_and(|state| state.value)
+        });
+        if changed {
+            self.query_history_index = None;
+        }
+        if changed && self.search_mode.is_search() && config::get().live_search {
+            self.last_keystroke = Some(Instant::now());
+        }
+        readline.is_some()
+    }
+
+    fn is_bound_modifier_key(&self, key: KeyEvent) -> bool {
+        key.modifiers
+            .intersects(KeyModifiers::CONTROL | KeyModifiers::ALT)
+            && config::get()
+                .key_bindings
+                .event_to_command(self.mode, &[key])
+                .is_some()
+    }
+
+    /// Replaces the input with the previous (Up) or next (Down) submitted
+    /// query, like a shell.
+    ///
+    /// Returns `true` if the key moved through the history. Otherwise Up and
+    /// Down are left to the key bindings.
+    fn handle_history_key(&mut self, key: KeyEvent) -> bool {
+        if !key.modifiers.is_empty() {
+            return false;
+        }
+        let index = match (key.code, self.query_history_index) {
+            (KeyCode::Up, _) if self.query_history.is_empty() => return false,
+            (KeyCode::Up, None) => {
+                self.query_draft = self.input.value().into();
+                Some(self.query_history.len() - 1)
+            }
+            (KeyCode::Up, Some(index)) => Some(index.saturating_sub(1)),
+            (KeyCode::Down, Some(index)) if index + 1 < self.query_history.len() => Some(index + 1),
+            (KeyCode::Down, Some(_)) => None,
+            _ => return false,
+        };
+        let query = match index {
+            Some(index) => self.query_history[index].clone(),
+            None => std::mem::take(&mut self.query_draft),
+        };
+        self.query_history_index = index;
+        self.input = Input::new(query);
+        if config::get().live_search {
+            self.last_keystroke = Some(Instant::now());
+        }
+        true
+    }
+
+    /// Inserts `text` at the cursor, joining multiple lines into one.
+    pub fn handle_paste(&mut self, text: &str) {
+        let text = text
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+            .join(" ");
+        for c in text.chars() {
+            self.input.handle(InputRequest::InsertChar(c));
+        }
+        if self.search_mode.is_search() && config::get().live_search {
+            self.last_keystroke = Some(Instant::now());
+        }
     }
 
-    pub fn scroll_up(&mut self) {
-        self.results.scroll_previous();
+    /// Sends a live search once typing has paused for `search_debounce_ms`.
+    pub fn tick(&mut self) {
+        let debounce = Duration::from_millis(config::get().search_debounce_ms);
+        if self
+            .last_keystroke
+            .is_some_and(|instant| instant.elapsed() >= debounce)
+        {
+            self.last_keystroke = None;
+            self.search = self.input.value().into();
+            self.category = None;
+            self.owner = None;
+            self.page = 1;
+            self.reset_sort();
+            let _ = self.tx.send(Action::ReloadData);
+        }
+        self.check_search_timeout();
     }
 
-    pub fn scroll_down(&mut self) {
-        self.results.scroll_next();
+    /// Warns once the in-flight search takes longer than
+    /// `loading_timeout_secs`, and aborts it when none of its attempts has
+    /// finished for twice that long. A search that is still retrying is slow
+    /// rather than stalled, and is left to finish.
+    fn check_search_timeout(&mut self) {
+        let timeout = Duration::from_secs(config::get().loading_timeout_secs);
+        let Some(started) = self.search_started else {
+            return;
+        };
+        if timeout.is_zero()
+            || self
+                .last_search_task_handle
+                .as_ref()
+                .is_none_or(JoinHandle::is_finished)
+        {
+            self.search_started = None;
+            return;
+        }
+        if started.elapsed() < timeout {
+            return;
+        }
+        let last_progress = self.search_progress.lock().unwrap().unwrap_or(started);
+        let stalled = last_progress.elapsed() >= timeout * 2;
+        if stalled {
+            self.cancel_search();
+            let _ = self.tx.send(Action::ShowRetryPopup(
+                format!(
+                    "Search timed out: crates.io did not respond within {}s",
+                    timeout.as_secs() * 2
+                ),
+                Box::new(Action::ReloadData),
+            ));
+        } else if !self.slow_search_warned {
+            self.slow_search_warned = true;
+            let _ = self.tx.send(Action::ShowCancelPopup(
+                "Still loading… network may be slow".into(),
+                Box::new(Action::CancelSearch),
+            ));
+        }
     }
 
-    pub fn handle_key(&mut self, key: KeyEvent) {
-        self.input.handle_event(&CrosstermEvent::Key(key));
+    /// Aborts the in-flight search, keeping the results shown before it.
+    pub fn cancel_search(&mut self) {
+        if let Some(handle) = self.last_search_task_handle.take() {
+            handle.abort();
+            self.loading_status.store(false, Ordering::SeqCst);
+        }
+        self.search_started = None;
     }
 
     pub fn handle_filter_prompt_change(&mut self) {
         self.filter = self.input.value().into();
+        self.results_dirty = true;
         self.results.select(None);
     }
 
+    fn clear_filter(&mut self) {
+        self.filter.clear();
+        self.results_dirty = true;
+    }
+
+    /// Sets the download threshold from the number typed into the prompt, or
+    /// clears it if the prompt is empty.
+    pub fn set_min_downloads_from_input(&mut self) -> Result<()> {
+        let value = self.input.value().trim().replace(['_', ','], "");
+        self.min_downloads = if value.is_empty() {
+            None
+        } else {
+            Some(value.parse().map_err(|_| {
+                color_eyre::eyre::eyre!("Invalid download threshold `{}`", self.input.value())
+            })?)
+        };
+        self.clear_filter();
+        self.input.reset();
+        self.update_search_table_results();
+        let _ = self.tx.send(Action::SwitchMode(Mode::PickerHideCrateInfo));
+        Ok(())
+    }
+
+    pub fn toggle_only_with_repo(&mut self) {
+        self.only_with_repo = !self.only_with_repo;
+        self.update_search_table_results();
+    }
+
+    /// Only shows crates whose license contains the text typed into the
+    /// prompt, e.g. `MIT`, or shows all licenses again if the prompt is empty.
+    pub fn set_license_filter_from_input(&mut self) {
+        let value = self.input.value().trim();
+        self.license_filter = (!value.is_empty()).then(|| value.to_string());
+        self.clear_filter();
+        self.input.reset();
+        self.update_search_table_results();
+        let _ = self.tx.send(Action::SwitchMode(Mode::PickerHideCrateInfo));
+    }
+
+    /// Describes the active quick filters for the status bar.
+    pub fn quick_filters(&self) -> Vec<String> {
+        let mut filters = vec![];
+        if self.only_with_repo {
+            filters.push("with repository".to_string());
+        }
+        if let Some(license) = &self.license_filter {
+            filters.push(format!("license: {license}"));
+        }
+        filters
+    }
+
     pub fn cursor_position(&self) -> Option<Position> {
         self.prompt.cursor_position()
     }
 
     pub fn increment_page(&mut self) {
-        if let Some(n) = self.total_num_crates {
-            let max_page_size = (n / self.page_size) + 1;
-            if self.page < max_page_size {
-                self.page = self.page.saturating_add(1).min(max_page_size);
-                self.reload_data();
+        if config::get().infinite_scroll {
+            self.append_next_page();
+            return;
+        }
+        if let Some(last_page) = self.last_page()
+            && self.page < last_page
+        {
+            self.page = self.page.saturating_add(1).min(last_page);
+            self.reload_data();
+        }
+    }
+
+    /// The number of the last page of results, or `None` if there are no
+    /// results to page through.
+    fn last_page(&self) -> Option<u64> {
+        self.total_num_crates
+            .filter(|total| *total > 0)
+            .map(|total| total.div_ceil(self.page_size).max(1))
+    }
+
+    /// Whether there are any results to show, after filtering.
+    fn has_results(&self) -> bool {
+        self.total_num_crates != Some(0) && !self.results.crates.is_empty()
+    }
+
+    /// Whether crates.io has results past the last loaded page.
+    fn has_next_page(&self) -> bool {
+        self.total_num_crates
+            .is_some_and(|total| self.page.saturating_mul(self.page_size) < total)
+    }
+
+    /// Whether as many crates as `max_results` allows have been loaded.
+    fn result_cap_reached(&self) -> bool {
+        self.fetched.len() >= config::get().max_results
+    }
+
+    /// Whether infinite scroll may load another page.
+    fn can_append_next_page(&self) -> bool {
+        self.has_next_page() && !self.result_cap_reached()
+    }
+
+    /// Fetches the page after `page` in the background if
+    /// `prefetch_next_page` is enabled and there is one.
+    pub fn prefetch_next_page(&mut self) {
+        if let Some(handle) = self.prefetch_task_handle.take() {
+            handle.abort();
+        }
+        if !config::get().prefetch_next_page || !self.has_next_page() {
+            return;
+        }
+        let mut search_params = self.create_search_parameters();
+        search_params.page = self.page.saturating_add(1);
+        self.prefetch_task_handle = Some(tokio::spawn(async move {
+            if let Err(err) = crates_io_api_helper::prefetch_search_results(&search_params).await {
+                warn!("Unable to prefetch page {}: {err}", search_params.page);
             }
+        }));
+    }
+
+    /// Fetches the next page and appends it to the results, keeping the
+    /// current selection. Does nothing while another load is in flight.
+    fn append_next_page(&mut self) {
+        if self.loading_status.load(Ordering::SeqCst) || !self.can_append_next_page() {
+            return;
         }
+        self.page = self.page.saturating_add(1);
+        let mut search_params = self.create_search_parameters();
+        search_params.append = true;
+        self.request_search_results(search_params);
     }
 
+    /// Goes back a page, even if the local filter hides all of this one.
     pub fn decrement_page(&mut self) {
         let min_page_size = 1;
-        if self.page > min_page_size {
+        if self.last_page().is_some() && self.page > min_page_size {
             self.page = self.page.saturating_sub(1).max(min_page_size);
             self.reload_data();
         }
@@ -239,14 +669,167 @@ impl SearchPage {
         for (_, v) in self.last_task_details_handle.iter() {
             v.abort()
         }
-        self.last_task_details_handle.clear()
+        self.last_task_details_handle.clear();
+        self.details_loading.store(false, Ordering::SeqCst);
+    }
+
+    /// Fetches the details of the selected crate again, bypassing the cache,
+    /// along with its full details if they were loaded.
+    pub fn refresh_crate_details(&mut self) {
+        let Some(crate_name) = self.results.selected_crate_name() else {
+            return;
+        };
+        let full_crate_info_loaded = self
+            .full_crate_info
+            .lock()
+            .unwrap()
+            .as_ref()
+            .is_some_and(|krate| krate.name == crate_name);
+        self.clear_all_previous_task_details_handles();
+        self.details_cache.lock().unwrap().remove(&crate_name);
+        *self.docs_status.lock().unwrap() = None;
+        *self.crate_owners.lock().unwrap() = None;
+        self.request_crate_details();
+        if full_crate_info_loaded {
+            self.request_full_crate_details();
+        }
+    }
+
+    /// Aborts the in-flight search and all crate details requests.
+    pub fn abort_all_tasks(&mut self) {
+        if let Some(handle) = self.last_search_task_handle.take() {
+            handle.abort();
+        }
+        if let Some(handle) = self.prefetch_task_handle.take() {
+            handle.abort();
+        }
+        for (_, handle) in self.adjacent_prefetch_handles.drain() {
+            handle.abort();
+        }
+        self.clear_all_previous_task_details_handles();
     }
 
     pub fn submit_query(&mut self) {
+        self.results.clear_marks();
         self.clear_all_previous_task_details_handles();
-        self.filter.clear();
+        self.clear_filter();
         self.search = self.input.value().into();
+        self.category = None;
+        self.owner = None;
+        self.last_keystroke = None;
+        self.query_history_index = None;
+        self.reset_sort();
+        if !self.search.is_empty() && self.query_history.last() != Some(&self.search) {
+            self.query_history.push(self.search.clone());
+        }
+        let _ = self.tx.send(Action::SwitchMode(Mode::PickerHideCrateInfo));
+    }
+
+    /// Lists the crates in the given crates.io category.
+    pub fn search_by_category(&mut self, category: String) {
+        self.results.clear_marks();
+        self.clear_all_previous_task_details_handles();
+        self.clear_filter();
+        self.search.clear();
+        self.input.reset();
+        self.page = 1;
+        self.category = Some(category);
+        self.owner = None;
+        let config = config::get();
+        self.set_sort(config.category_sort.unwrap_or(config.default_sort).into());
         let _ = self.tx.send(Action::SwitchMode(Mode::PickerHideCrateInfo));
+        let _ = self.tx.send(Action::ReloadData);
+    }
+
+    pub fn snapshot(&self) -> SearchSnapshot {
+        SearchSnapshot {
+            query: self.search.clone(),
+            category: self.category.clone(),
+            owner: self.owner.clone(),
+            page: self.page,
+        }
+    }
+
+    /// Reloads the search recorded in `snapshot`.
+    pub fn restore(&mut self, snapshot: SearchSnapshot) {
+        self.set_query(snapshot.query);
+        self.input.reset();
+        self.category = snapshot.category;
+        self.owner = snapshot.owner;
+        self.page = snapshot.page;
+        self.reload_data();
+    }
+
+    /// Selects `pending_selection` once the results have loaded.
+    pub fn apply_pending_selection(&mut self) {
+        if let Some(index) = self.pending_selection.take()
+            && !self.results.crates.is_empty()
+        {
+            self.results
+                .select(Some(index.min(self.results.crates.len() - 1)));
+        }
+    }
+
+    /// Replaces the query, dropping any category, owner or filter.
+    pub fn set_query(&mut self, query: String) {
+        self.results.clear_marks();
+        self.clear_all_previous_task_details_handles();
+        self.clear_filter();
+        self.search = query;
+        self.page = 1;
+        self.category = None;
+        self.owner = None;
+    }
+
+    /// Lists the crates owned by the given user or team.
+    pub fn search_by_owner(&mut self, owner: Owner) {
+        self.results.clear_marks();
+        self.clear_all_previous_task_details_handles();
+        self.clear_filter();
+        self.search.clear();
+        self.input.reset();
+        self.page = 1;
+        self.category = None;
+        self.owner = Some(owner);
+        let _ = self.tx.send(Action::SwitchMode(Mode::PickerHideCrateInfo));
+        let _ = self.tx.send(Action::ReloadData);
+    }
+
+    /// Spawns an asynchronous task that resolves the owner of the selected
+    /// crate and then lists that owner's crates.
+    pub fn request_owner_crates(&self) {
+        let Some(crate_name) = self.results.selected_crate_name() else {
+            let _ = self
+                .tx
+                .send(Action::ShowErrorPopup("No selection made".into()));
+            return;
+        };
+        let tx = self.tx.clone();
+        let loading_status = self.loading_status.clone();
+        tokio::spawn(async move {
+            loading_status.store(true, Ordering::SeqCst);
+            match crates_io_api_helper::request_crate_owner(&crate_name).await {
+                Ok(owner) => {
+                    let _ = tx.send(Action::ShowOwnerCrates(owner));
+                }
+                Err(err) => {
+                    let action = format!("looking up the owners of `{crate_name}`");
+                    crates_io_api_helper::report_error(&tx, &action, &err, None);
+                }
+            }
+            loading_status.store(false, Ordering::SeqCst);
+        });
+    }
+
+    /// Describes what the current results are for, shown in the status bar.
+    pub fn query_description(&self) -> String {
+        if let Some(owner) = &self.owner {
+            format!("owner:{}", owner.login)
+        } else if let Some(category) = &self.category {
+            format!("category:{category}")
+        } else {
+            self.input.value().to_string()
+        }
     }
 
     /// Reloads the list of crates based on the current search parameters,
@@ -255,11 +838,20 @@ impl SearchPage {
     /// the application state, such as the crates listing, current crate
     /// info, and loading status.
     pub fn reload_data(&mut self) {
+        if let Some(handle) = self.prefetch_task_handle.take() {
+            handle.abort();
+        }
+        self.first_page = self.page;
         self.prepare_reload();
         let search_params = self.create_search_parameters();
         self.request_search_results(search_params);
     }
 
+    /// Drops the cached crate details so that they are fetched again.
+    pub fn clear_details_cache(&mut self) {
+        self.details_cache.lock().unwrap().clear();
+    }
+
     /// Clears current search results and resets the UI to prepare for new data.
     pub fn prepare_reload(&mut self) {
         self.results.select(None);
@@ -273,25 +865,39 @@ impl SearchPage {
             search: self.search.clone(),
             page: self.page.clamp(1, u64::MAX),
             page_size: self.page_size,
-            crates: self.crates.clone(),
-            versions: self.versions.clone(),
+            fetched: self.fetched.clone(),
             loading_status: self.loading_status.clone(),
             sort: self.sort.clone(),
+            category: self.category.clone(),
+            owner: self.owner.clone(),
+            append: false,
+            last_progress: Default::default(),
             tx: self.tx.clone(),
         }
     }
 
     /// Spawns an asynchronous task to fetch crate data from crates.io.
-    pub fn request_search_results(&self, params: crates_io_api_helper::SearchParameters) {
-        tokio::spawn(async move {
+    pub fn request_search_results(&mut self, params: crates_io_api_helper::SearchParameters) {
+        if let Some(handle) = self.last_search_task_handle.take() {
+            handle.abort();
+        }
+        self.search_started = Some(Instant::now());
+        self.search_progress = params.last_progress.clone();
+        self.slow_search_warned = false;
+        self.last_search_task_handle = Some(tokio::spawn(async move {
             params.loading_status.store(true, Ordering::SeqCst);
-            if let Err(error_message) = crates_io_api_helper::request_search_results(&params).await
-            {
-                let _ = params.tx.send(Action::ShowErrorPopup(error_message));
+            if let Err(err) = crates_io_api_helper::request_search_results(&params).await {
+                let action = format!("searching for `{}`", params.search);
+                crates_io_api_helper::report_error(
+                    &params.tx,
+                    &action,
+                    &err,
+                    Some(Action::ReloadData),
+                );
             }
             let _ = params.tx.send(Action::UpdateSearchTableResults);
             params.loading_status.store(false, Ordering::SeqCst);
-        });
+        }));
     }
 
     /// Spawns an asynchronous task to fetch crate details from crates.io based
@@ -300,23 +906,75 @@ impl SearchPage {
         if self.results.crates.is_empty() {
             return;
         }
+        self.prefetch_adjacent_details();
         if let Some(crate_name) = self.results.selected_crate_name() {
+            let cached = self.details_cache.lock().unwrap().get(&crate_name).cloned();
+            if let Some(cached) = cached {
+                let version = cached.crate_data.max_version.clone();
+                *self.crate_response.lock().unwrap() = Some(cached);
+                let uuid = uuid::Uuid::new_v4();
+                let docs_status = self.docs_status.clone();
+                let crate_owners = self.crate_owners.clone();
+                let tx = self.tx.clone();
+                let handle = tokio::spawn(async move {
+                    tokio::join!(
+                        update_docs_status(docs_status, tx.clone(), crate_name.clone(), version),
+                        update_crate_owners(crate_owners, tx, crate_name),
+                    );
+                });
+                self.last_task_details_handle.insert(uuid, handle);
+                return;
+            }
             let tx = self.tx.clone();
             let crate_response = self.crate_response.clone();
+            let details_cache = self.details_cache.clone();
+            let docs_status = self.docs_status.clone();
+            let crate_owners = self.crate_owners.clone();
             let loading_status = self.loading_status.clone();
+            let details_loading = self.details_loading.clone();
 
             // Spawn the async work to fetch crate details.
             let uuid = uuid::Uuid::new_v4();
             let last_task_details_handle = tokio::spawn(async move {
                 info!("Requesting details for {crate_name}: {uuid}");
                 loading_status.store(true, Ordering::SeqCst);
-                if let Err(error_message) =
-                    crates_io_api_helper::request_crate_details(&crate_name, crate_response).await
+                details_loading.store(true, Ordering::SeqCst);
+                let result = crates_io_api_helper::request_crate_details(
+                    &crate_name,
+                    crate_response.clone(),
+                )
+                .await;
+                if result.is_ok()
+                    && let Some(response) = crate_response.lock().unwrap().clone()
                 {
-                    let _ = tx.send(Action::ShowErrorPopup(error_message));
+                    details_cache
+                        .lock()
+                        .unwrap()
+                        .insert(crate_name.clone(), response);
+                }
+                if let Err(err) = result {
+                    let action = format!("loading details for `{crate_name}`");
+                    crates_io_api_helper::report_error(
+                        &tx,
+                        &action,
+                        &err,
+                        Some(Action::UpdateCurrentSelectionCrateInfo),
+                    );
                 };
                 loading_status.store(false, Ordering::SeqCst);
+                details_loading.store(false, Ordering::SeqCst);
                 info!("Retrieved details for {crate_name}: {uuid}");
+                let version = crate_response
+                    .lock()
+                    .unwrap()
+                    .as_ref()
+                    .map(|response| response.crate_data.max_version.clone());
+                if let Some(version) = version {
+                    tokio::join!(
+                        update_docs_status(docs_status, tx.clone(), crate_name.clone(), version),
+                        update_crate_owners(crate_owners, tx.clone(), crate_name),
+                    );
+                }
                 let _ = tx.send(Action::ClearTaskDetailsHandle(uuid.to_string()));
             });
             self.last_task_details_handle
@@ -324,6 +982,60 @@ impl SearchPage {
         }
     }
 
+    /// Fetches the details of the crates above and below the selection into
+    /// the cache, so that moving onto them doesn't wait for a request.
+    fn prefetch_adjacent_details(&mut self) {
+        if !config::get().prefetch_adjacent || !self.search_mode.should_show_crate_info() {
+            return;
+        }
+        let Some(selected) = self.results.selected() else {
+            return;
+        };
+        let adjacent = [selected.checked_sub(1), selected.checked_add(1)]
+            .into_iter()
+            .flatten()
+            .filter_map(|index| self.results.crates.get(index))
+            .map(|krate| krate.name.clone())
+            .filter(|name| !name.is_empty())
+            .collect_vec();
+        // the selection moved away from these
+        self.adjacent_prefetch_handles.retain(|name, handle| {
+            let keep = adjacent.contains(name);
+            if !keep {
+                handle.abort();
+            }
+            keep
+        });
+        for crate_name in adjacent {
+            if self.adjacent_prefetch_handles.contains_key(&crate_name)
+                || self
+                    .details_cache
+                    .lock()
+                    .unwrap()
+                    .get(&crate_name)
+                    .is_some()
+            {
+                continue;
+            }
+            let details_cache = self.details_cache.clone();
+            let name = crate_name.clone();
+            let handle = tokio::spawn(async move {
+                let response = Arc::default();
+                match crates_io_api_helper::request_crate_details(&name, Arc::clone(&response))
+                    .await
+                {
+                    Ok(()) => {
+                        if let Some(response) = response.lock().unwrap().take() {
+                            details_cache.lock().unwrap().insert(name, response);
+                        }
+                    }
+                    Err(err) => warn!("Unable to prefetch details for {name}: {err}"),
+                }
+            });
+            self.adjacent_prefetch_handles.insert(crate_name, handle);
+        }
+    }
+
     /// Spawns an asynchronous task to fetch crate details from crates.io based
     /// on currently selected crate
     pub fn request_full_crate_details(&mut self) {
@@ -340,11 +1052,17 @@ impl SearchPage {
             let last_task_details_handle = tokio::spawn(async move {
                 info!("Requesting details for {crate_name}: {uuid}");
                 loading_status.store(true, Ordering::SeqCst);
-                if let Err(error_message) =
+                if let Err(err) =
                     crates_io_api_helper::request_full_crate_details(&crate_name, full_crate_info)
                         .await
                 {
-                    let _ = tx.send(Action::ShowErrorPopup(error_message));
+                    let action = format!("loading details for `{crate_name}`");
+                    crates_io_api_helper::report_error(
+                        &tx,
+                        &action,
+                        &err,
+                        Some(Action::ShowFullCrateInfo),
+                    );
                 };
                 loading_status.store(false, Ordering::SeqCst);
                 info!("Retrieved details for {crate_name}: {uuid}");
@@ -356,20 +1074,40 @@ impl SearchPage {
     }
 
     pub fn results_status(&self) -> String {
+        if !self.has_results() {
+            return "No results".into();
+        }
         let selected = self.selected_with_page_context();
         let ncrates = self.total_num_crates.unwrap_or_default();
+        if self.has_next_page() && self.result_cap_reached() {
+            return format!("{selected}/{ncrates} Results (result cap reached)");
+        }
         format!("{selected}/{ncrates} Results")
     }
 
     pub fn selected_with_page_context(&self) -> u64 {
         self.results.selected().map_or(0, |n| {
-            (self.page.saturating_sub(1) * self.page_size) + n as u64 + 1
+            (self.first_page.saturating_sub(1) * self.page_size) + n as u64 + 1
         })
     }
 
+    /// Returns the position through the full result set and the total number
+    /// of results, if the total is known.
+    ///
+    /// While a page loads nothing is selected, so the position falls back to
+    /// the first crate of the page being loaded.
+    pub fn progress(&self) -> Option<(u64, u64)> {
+        let total = self.total_num_crates.filter(|total| *total > 0)?;
+        let first_on_page = self.first_page.saturating_sub(1) * self.page_size + 1;
+        let position = self.selected_with_page_context().max(first_on_page);
+        Some((position.min(total), total))
+    }
+
     pub fn page_number_status(&self) -> String {
-        let max_page_size = (self.total_num_crates.unwrap_or_default() / self.page_size) + 1;
-        format!("Page: {}/{}", self.page, max_page_size)
+        match self.last_page() {
+            Some(last_page) => format!("Page: {}/{last_page}", self.page),
+            None => String::new(),
+        }
     }
 
     pub fn enter_normal_mode(&mut self) {
@@ -389,6 +1127,14 @@ impl SearchPage {
         self.input = self.input.clone().with_value(self.search.clone());
     }
 
+    /// Shows the crate info pane, e.g. to pick features in it.
+    pub fn show_crate_info(&mut self) {
+        if !self.search_mode.should_show_crate_info() {
+            self.search_mode = SearchMode::ResultsShowCrate;
+            self.request_crate_details();
+        }
+    }
+
     pub fn toggle_show_crate_info(&mut self) {
         self.search_mode.toggle_show_crate_info();
         if self.search_mode.should_show_crate_info() {
@@ -422,34 +1168,174 @@ impl SearchPage {
         };
     }
 
+    /// Sets the sort order in its natural direction.
+    pub fn set_sort(&mut self, sort: crates_io_api::Sort) {
+        self.sort_ascending = is_ascending_by_default(&sort);
+        self.sort = sort;
+    }
+
+    /// Sets the sort order picked by the user, which is kept for later
+    /// searches.
+    pub fn choose_sort(&mut self, sort: crates_io_api::Sort) {
+        self.set_sort(sort);
+        self.sort_chosen = true;
+    }
+
+    /// Goes back to `default_sort` for a typed query, unless the user picked
+    /// the sort order.
+    fn reset_sort(&mut self) {
+        if !self.sort_chosen {
+            self.set_sort(config::get().default_sort.into());
+        }
+    }
+
     pub fn toggle_sort_by(&mut self, reload: bool, forward: bool) -> Result<()> {
         if forward {
             self.toggle_sort_by_forward()
         } else {
             self.toggle_sort_by_backward()
         };
+        self.sort_ascending = is_ascending_by_default(&self.sort);
+        self.sort_chosen = true;
         if reload {
             self.tx.send(Action::ReloadData)?;
         }
         Ok(())
     }
 
+    /// Flips the sort direction of the current results.
+    pub fn toggle_sort_direction(&mut self) {
+        self.sort_ascending = !self.sort_ascending;
+        self.sort_chosen = true;
+        self.update_search_table_results();
+    }
+
     fn is_focused(&self) -> bool {
         self.mode.is_picker()
     }
 }
 
-pub struct SearchPageWidget;
+/// Returns whether crates.io returns results for `sort` in ascending order.
+fn is_ascending_by_default(sort: &crates_io_api::Sort) -> bool {
+    matches!(sort, crates_io_api::Sort::Alphabetical)
+}
+
+/// Applies readline editing keys that `tui_input` doesn't map the same way:
+/// `Ctrl-U` kills to the start of the line rather than the whole line, and
+/// `Alt-B`/`Alt-F` move by word, as terminals report `Alt` rather than `Meta`.
+///
+/// Returns `None` if `key` isn't a readline key, otherwise whether the value
+/// changed.
+fn handle_readline_key(input: &mut Input, key: KeyEvent) -> Option<bool> {
+    use InputRequest::*;
+    let requests = match (key.code, key.modifiers) {
+        (KeyCode::Char('a'), KeyModifiers::CONTROL) => vec![GoToStart],
+        (KeyCode::Char('e'), KeyModifiers::CONTROL) => vec![GoToEnd],
+        (KeyCode::Char('w'), KeyModifiers::CONTROL) => vec![DeletePrevWord],
+        (KeyCode::Char('u'), KeyModifiers::CONTROL) => vec![DeletePrevChar; input.cursor()],
+        (KeyCode::Char('k'), KeyModifiers::CONTROL) => vec![DeleteTillEnd],
+        (KeyCode::Char('b'), KeyModifiers::ALT) => vec![GoToPrevWord],
+        (KeyCode::Char('f'), KeyModifiers::ALT) => vec![GoToNextWord],
+        _ => return None,
+    };
+    let mut changed = false;
+    for request in requests {
+        changed |= input.handle(request).is_some_and(|state| state.value);
+    }
+    Some(changed)
+}
+
+/// Fetches the docs.rs build status of a crate version into `docs_status`,
+/// unless it is already known.
+async fn update_docs_status(
+    docs_status: Arc<Mutex<Option<(String, DocsStatus)>>>,
+    tx: UnboundedSender<Action>,
+    crate_name: String,
+    version: String,
+) {
+    if docs_status
+        .lock()
+        .unwrap()
+        .as_ref()
+        .is_some_and(|(name, _)| *name == crate_name)
+    {
+        return;
+    }
+    match crates_io_api_helper::request_docs_status(&crate_name, &version).await {
+        Ok(status) => {
+            *docs_status.lock().unwrap() = Some((crate_name, status));
+            // redraw with the new status
+            let _ = tx.send(Action::Refresh);
+        }
+        Err(err) => warn!("Unable to fetch the docs.rs status of {crate_name}: {err}"),
+    }
+}
+
+/// Fetches the owners of a crate into `crate_owners`, unless they are already
+/// known.
+async fn update_crate_owners(
+    crate_owners: CrateOwners,
+    tx: UnboundedSender<Action>,
+    crate_name: String,
+) {
+    if crate_owners
+        .lock()
+        .unwrap()
+        .as_ref()
+        .is_some_and(|(name, _)| *name == crate_name)
+    {
+        return;
+    }
+    match crates_io_api_helper::request_crate_owners(&crate_name).await {
+        Ok(owners) => {
+            *crate_owners.lock().unwrap() = Some((crate_name, owners));
+            // redraw with the owners
+            let _ = tx.send(Action::Refresh);
+        }
+        Err(err) => warn!("Unable to fetch the owners of {crate_name}: {err}"),
+    }
+}
+
+pub struct SearchPageWidget<'a> {
+    bookmarks: &'a Bookmarks,
+    local_deps: &'a BTreeSet<String>,
+}
+
+impl<'a> SearchPageWidget<'a> {
+    pub fn new(bookmarks: &'a Bookmarks, local_deps: &'a BTreeSet<String>) -> Self {
+        Self {
+            bookmarks,
+            local_deps,
+        }
+    }
 
-impl SearchPageWidget {
     fn render_crate_info(&self, area: Rect, buf: &mut Buffer, state: &mut SearchPage) {
         if let Some(ci) = state.crate_response.lock().unwrap().clone() {
-            CrateInfoTableWidget::new(ci).render(area, buf, &mut state.crate_info);
+            let docs_status = state
+                .docs_status
+                .lock()
+                .unwrap()
+                .as_ref()
+                .filter(|(name, _)| *name == ci.crate_data.name)
+                .map(|(_, status)| *status);
+            let owners = state
+                .crate_owners
+                .lock()
+                .unwrap()
+                .as_ref()
+                .filter(|(name, _)| *name == ci.crate_data.name)
+                .map(|(_, owners)| owners.clone());
+            CrateInfoTableWidget::new(ci)
+                .docs_status(docs_status)
+                .owners(owners)
+                .loading(state.details_loading.load(Ordering::SeqCst))
+                .selecting_features(state.mode.is_feature_select())
+                .render(area, buf, &mut state.crate_info);
         }
     }
 }
 
-impl StatefulWidget for SearchPageWidget {
+impl StatefulWidget for SearchPageWidget<'_> {
     type State = SearchPage;
 
     fn render(
@@ -467,11 +1353,10 @@ impl StatefulWidget for SearchPageWidget {
             area
         };
 
-        SearchResultsWidget::new(!state.is_prompt() && state.is_focused()).render(
-            area,
-            buf,
-            &mut state.results,
-        );
+        SearchResultsWidget::new(!state.is_prompt() && state.is_focused())
+            .bookmarks(self.bookmarks)
+            .local_deps(self.local_deps)
+            .render(area, buf, &mut state.results);
 
         Line::from(state.page_number_status())
             .left_aligned()
@@ -492,3 +1377,119 @@ impl StatefulWidget for SearchPageWidget {
         );
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn press(input: &mut Input, code: KeyCode, modifiers: KeyModifiers) {
+        handle_readline_key(input, KeyEvent::new(code, modifiers));
+    }
+
+    #[test]
+    fn readline_keys_edit_the_input() {
+        use KeyCode::Char;
+        use KeyModifiers as M;
+
+        let mut input = Input::new("serde json derive".into());
+        press(&mut input, Char('b'), M::ALT);
+        assert_eq!(input.cursor(), 11);
+        press(&mut input, Char('k'), M::CONTROL);
+        assert_eq!(input.value(), "serde json ");
+        press(&mut input, Char('a'), M::CONTROL);
+        assert_eq!(input.cursor(), 0);
+        press(&mut input, Char('f'), M::ALT);
+        assert_eq!(input.cursor(), 6);
+        press(&mut input, Char('u'), M::CONTROL);
+        assert_eq!((input.value(), input.cursor()), ("json ", 0));
+        press(&mut input, Char('e'), M::CONTROL);
+        assert_eq!(input.cursor(), 5);
+        press(&mut input, Char('w'), M::CONTROL);
+        assert_eq!(input.value(), "");
+    }
+
+    #[test]
+    fn up_and_down_walk_through_submitted_queries() {
+        let mut page = search_page(None);
+        page.search_mode = SearchMode::Search;
+        page.query_history = vec!["serde".into(), "tokio".into()];
+        page.input = Input::new("rat".into());
+        let key = |code| KeyEvent::new(code, KeyModifiers::NONE);
+
+        assert!(page.handle_key(key(KeyCode::Up)));
+        assert_eq!(page.input.value(), "tokio");
+        assert!(page.handle_key(key(KeyCode::Up)));
+        assert!(page.handle_key(key(KeyCode::Up)));
+        assert_eq!(page.input.value(), "serde");
+        assert!(page.handle_key(key(KeyCode::Down)));
+        assert_eq!(page.input.value(), "tokio");
+        assert!(page.handle_key(key(KeyCode::Down)));
+        assert_eq!(page.input.value(), "rat");
+        // past the newest entry Down scrolls the results again
+        assert!(!page.handle_key(key(KeyCode::Down)));
+    }
+
+    /// A search page with 25 crates per page and `total_num_crates` results.
+    fn search_page(total_num_crates: Option<u64>) -> SearchPage {
+        config::init_for_tests();
+        let (tx, _) = tokio::sync::mpsc::unbounded_channel();
+        let mut page = SearchPage::new(tx, Default::default());
+        page.page_size = 25;
+        page.total_num_crates = total_num_crates;
+        page
+    }
+
+    #[test]
+    fn typed_searches_go_back_to_the_default_sort() {
+        let mut page = search_page(None);
+        // e.g. after selecting a category
+        page.set_sort(crates_io_api::Sort::Downloads);
+        page.submit_query();
+        assert_eq!(page.sort, crates_io_api::Sort::Relevance);
+
+        page.toggle_sort_by(false, true).unwrap();
+        page.submit_query();
+        assert_eq!(page.sort, crates_io_api::Sort::Downloads);
+    }
+
+    #[test]
+    fn zero_results_show_no_results_and_disable_paging() {
+        let mut page = search_page(Some(0));
+        assert_eq!(page.results_status(), "No results");
+        assert_eq!(page.page_number_status(), "");
+        assert_eq!(page.last_page(), None);
+        page.increment_page();
+        assert_eq!(page.page, 1);
+    }
+
+    #[test]
+    fn paging_ignores_the_local_filter() {
+        // the filter matches nothing on page 3, so `results` is empty
+        let mut page = search_page(Some(75));
+        page.page = 3;
+        assert_eq!(page.results_status(), "No results");
+        assert_eq!(page.page_number_status(), "Page: 3/3");
+    }
+
+    #[test]
+    fn last_page_holds_the_last_results() {
+        assert_eq!(search_page(Some(25)).last_page(), Some(1));
+        assert_eq!(search_page(Some(26)).last_page(), Some(2));
+        assert_eq!(search_page(Some(50)).last_page(), Some(2));
+        assert_eq!(search_page(Some(0)).last_page(), None);
+    }
+
+    #[test]
+    fn next_page_exists_only_past_the_page_size() {
+        assert!(!search_page(Some(25)).has_next_page());
+        assert!(search_page(Some(26)).has_next_page());
+    }
+
+    #[test]
+    fn other_keys_are_left_to_tui_input() {
+        let mut input = Input::new("serde".into());
+        let key = KeyEvent::new(KeyCode::Char('x'), KeyModifiers::NONE);
+        assert_eq!(handle_readline_key(&mut input, key), None);
+        assert_eq!(input.value(), "serde");
+    }
+}
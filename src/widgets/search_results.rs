@@ -1,16 +1,48 @@
+use std::collections::BTreeSet;
+
 use crates_io_api::Crate;
 use itertools::Itertools;
-use num_format::{Locale, ToFormattedString};
 use ratatui::{prelude::*, widgets::*};
-use unicode_width::UnicodeWidthStr;
+use serde::{Deserialize, Serialize};
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+
+use crate::{bookmarks::Bookmarks, config, format};
 
-use crate::config;
+/// A column that can be shown in the search results table.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SearchColumn {
+    Name,
+    Description,
+    Downloads,
+    Updated,
+    RecentDownloads,
+}
+
+impl SearchColumn {
+    fn header(&self) -> &'static str {
+        match self {
+            SearchColumn::Name => "Name",
+            SearchColumn::Description => "Description",
+            SearchColumn::Downloads => "Downloads",
+            SearchColumn::Updated => "Last Updated",
+            SearchColumn::RecentDownloads => "Recent Downloads",
+        }
+    }
+}
 
 #[derive(Debug, Default)]
 pub struct SearchResults {
     pub crates: Vec<crates_io_api::Crate>,
     pub table_state: TableState,
     pub scrollbar_state: ScrollbarState,
+
+    /// The names of the crates marked for batch copying, kept while filtering.
+    marked: BTreeSet<String>,
+
+    /// The screen area of each visible row from the last render, used to map
+    /// mouse clicks to crates.
+    row_areas: Vec<(usize, Rect)>,
 }
 
 impl SearchResults {
@@ -21,6 +53,23 @@ impl SearchResults {
             .map(|krate| krate.name.clone())
     }
 
+    /// Marks the selected crate, or unmarks it if it is already marked.
+    pub fn toggle_mark(&mut self) {
+        if let Some(name) = self.selected_crate_name()
+            && !self.marked.remove(&name)
+        {
+            self.marked.insert(name);
+        }
+    }
+
+    pub fn marked(&self) -> &BTreeSet<String> {
+        &self.marked
+    }
+
+    pub fn clear_marks(&mut self) {
+        self.marked.clear();
+    }
+
     pub fn selected(&self) -> Option<usize> {
         self.table_state.selected()
     }
@@ -33,22 +82,52 @@ impl SearchResults {
         self.table_state.select(index)
     }
 
-    pub fn scroll_next(&mut self) {
-        let wrap_index = self.crates.len().max(1);
-        let next = self
-            .table_state
-            .selected()
-            .map_or(0, |i| (i + 1) % wrap_index);
+    /// Returns the number of rows that fit in the table at the last render.
+    pub fn page_size(&self) -> usize {
+        self.row_areas.len().max(1)
+    }
+
+    /// Returns the index of the crate rendered at the given screen position.
+    pub fn index_at(&self, position: Position) -> Option<usize> {
+        self.row_areas
+            .iter()
+            .find(|(_, area)| area.contains(position))
+            .map(|(index, _)| *index)
+    }
+
+    /// Selects the crate rendered at the given screen position and returns its
+    /// index.
+    pub fn select_at(&mut self, position: Position) -> Option<usize> {
+        let index = self.index_at(position)?;
+        self.scroll_to(index);
+        Some(index)
+    }
+
+    /// Moves the selection down by `count` rows, stopping at the last row.
+    /// Wraps around to the first row when already on the last row.
+    pub fn scroll_next(&mut self, count: usize) {
+        let last = self.crates.len().saturating_sub(1);
+        let next = self.table_state.selected().map_or(0, |i| {
+            if i >= last {
+                0
+            } else {
+                i.saturating_add(count).min(last)
+            }
+        });
         self.scroll_to(next);
     }
 
-    pub fn scroll_previous(&mut self) {
+    /// Moves the selection up by `count` rows, stopping at the first row.
+    /// Wraps around to the last row when already on the first row.
+    pub fn scroll_previous(&mut self, count: usize) {
         let last = self.crates.len().saturating_sub(1);
-        let wrap_index = self.crates.len().max(1);
-        let previous = self
-            .table_state
-            .selected()
-            .map_or(last, |i| (i + last) % wrap_index);
+        let previous = self.table_state.selected().map_or(last, |i| {
+            if i == 0 {
+                last
+            } else {
+                i.saturating_sub(count)
+            }
+        });
         self.scroll_to(previous);
     }
 
@@ -71,17 +150,43 @@ impl SearchResults {
     }
 }
 
-pub struct SearchResultsWidget {
+pub struct SearchResultsWidget<'a> {
     highlight: bool,
+    bookmarks: Option<&'a Bookmarks>,
+    local_deps: Option<&'a BTreeSet<String>>,
+    columns: Option<&'a [SearchColumn]>,
 }
 
-impl SearchResultsWidget {
+impl<'a> SearchResultsWidget<'a> {
     pub fn new(highlight: bool) -> Self {
-        Self { highlight }
+        Self {
+            highlight,
+            bookmarks: None,
+            local_deps: None,
+            columns: None,
+        }
+    }
+
+    /// Marks crates contained in `bookmarks` with a star.
+    pub fn bookmarks(mut self, bookmarks: &'a Bookmarks) -> Self {
+        self.bookmarks = Some(bookmarks);
+        self
+    }
+
+    /// Marks crates the project in the current directory already depends on.
+    pub fn local_deps(mut self, local_deps: &'a BTreeSet<String>) -> Self {
+        self.local_deps = Some(local_deps);
+        self
+    }
+
+    /// Shows `columns` instead of the configured `search_columns`.
+    pub fn columns(mut self, columns: &'a [SearchColumn]) -> Self {
+        self.columns = Some(columns);
+        self
     }
 }
 
-impl StatefulWidget for SearchResultsWidget {
+impl StatefulWidget for SearchResultsWidget<'_> {
     type State = SearchResults;
 
     fn render(self, area: Rect, buf: &mut Buffer, state: &mut Self::State) {
@@ -102,12 +207,37 @@ impl StatefulWidget for SearchResultsWidget {
             .render(scrollbar_area, buf, &mut state.scrollbar_state);
 
         let highlight_symbol = if self.highlight {
-            " █ "
+            format!(" {} ", config::get().highlight_symbol())
         } else {
-            " \u{2022} "
+            " \u{2022} ".into()
         };
 
-        let column_widths = [Max(20), Fill(1), Max(11)];
+        let config = config::get();
+        let columns = self.columns.unwrap_or(&config.search_columns);
+        // wide enough for the header and the largest expected count
+        let downloads_width = if config::get().compact_downloads {
+            9
+        } else {
+            11
+        };
+        let column_widths = columns
+            .iter()
+            .map(|column| match column {
+                SearchColumn::Name => Max(20),
+                SearchColumn::Description => Fill(1),
+                SearchColumn::Downloads => Max(downloads_width),
+                SearchColumn::Updated => Length(
+                    state
+                        .crates
+                        .iter()
+                        .map(|krate| format::format_timestamp(krate.updated_at).width())
+                        .chain([column.header().width()])
+                        .max()
+                        .unwrap_or_default() as u16,
+                ),
+                SearchColumn::RecentDownloads => Max(column.header().width() as u16),
+            })
+            .collect_vec();
 
         // Emulate the table layout calculations using Layout so we can render the vertical borders
         // in the space between the columns and can wrap the description field based on the actual
@@ -115,72 +245,192 @@ impl StatefulWidget for SearchResultsWidget {
         let highlight_symbol_width = highlight_symbol.width() as u16;
         let [_highlight_column, table_columns] =
             Layout::horizontal([Length(highlight_symbol_width), Fill(1)]).areas(table_area);
-        let column_layout = Layout::horizontal(column_widths).spacing(COLUMN_SPACING);
-        let [_name_column, description_column, _downloads_column] =
-            column_layout.areas(table_columns);
-        let spacers: [Rect; 4] = column_layout.spacers(table_columns);
+        let column_layout = Layout::horizontal(&column_widths).spacing(COLUMN_SPACING);
+        let (column_areas, spacers) = column_layout.split_with_spacers(table_columns);
+        let description_column_width = columns
+            .iter()
+            .position(|column| *column == SearchColumn::Description)
+            .map_or(0, |index| column_areas[index].width as usize);
 
         let vertical_pad = |line| Text::from(vec!["".into(), line, "".into()]);
 
-        let header_cells = ["Name", "Description", "Downloads"]
-            .map(|h| h.bold().into())
-            .map(vertical_pad);
+        let header_cells = columns
+            .iter()
+            .map(|column| vertical_pad(column.header().bold().into()));
         let header = Row::new(header_cells)
             .fg(config::get().color.base05)
             .bg(config::get().color.base00)
             .height(TABLE_HEADER_HEIGHT);
 
-        let description_column_width = description_column.width as usize;
         let selected_index = state.selected().unwrap_or_default();
-        let rows = state
+        let (rows, row_heights): (Vec<_>, Vec<_>) = state
             .crates
             .iter()
             .enumerate()
             .map(|(index, krate)| {
-                row_from_crate(krate, description_column_width, index, selected_index)
+                let markers = RowMarkers {
+                    bookmarked: self
+                        .bookmarks
+                        .is_some_and(|bookmarks| bookmarks.contains(&krate.name)),
+                    marked: state.marked.contains(&krate.name),
+                    in_deps: self
+                        .local_deps
+                        .is_some_and(|local_deps| local_deps.contains(&krate.name)),
+                };
+                row_from_crate(
+                    krate,
+                    columns,
+                    description_column_width,
+                    index,
+                    selected_index,
+                    markers,
+                )
             })
-            .collect_vec();
+            .unzip();
 
         let table = Table::new(rows, column_widths)
             .header(header)
             .column_spacing(COLUMN_SPACING)
             .highlight_symbol(vertical_pad(highlight_symbol.into()))
-            .row_highlight_style(config::get().color.base05)
+            .row_highlight_style(config::get().highlight_style())
             .highlight_spacing(HighlightSpacing::Always);
 
         StatefulWidget::render(table, table_area, buf, &mut state.table_state);
 
-        render_table_borders(state, spacers, buf);
+        state.row_areas = visible_row_areas(
+            table_area,
+            TABLE_HEADER_HEIGHT,
+            state.table_state.offset(),
+            &row_heights,
+        );
+
+        render_table_borders(state, &spacers, buf);
     }
 }
 
-fn row_from_crate(
-    krate: &Crate,
+/// The glyphs shown next to a crate's name.
+struct RowMarkers {
+    bookmarked: bool,
+    marked: bool,
+    in_deps: bool,
+}
+
+fn row_from_crate<'a>(
+    krate: &'a Crate,
+    columns: &[SearchColumn],
     description_column_width: usize,
     index: usize,
     selected_index: usize,
-) -> Row<'_> {
-    let mut description = textwrap::wrap(
-        &krate.description.clone().unwrap_or_default(),
-        description_column_width,
-    )
-    .iter()
-    .map(|s| Line::from(s.to_string()))
-    .collect_vec();
-    description.insert(0, "".into());
-    description.push("".into());
-    let vertical_padded = |line| Text::from(vec!["".into(), line, "".into()]);
-    let crate_name = Line::from(krate.name.clone());
-    let downloads = Line::from(krate.downloads.to_formatted_string(&Locale::en)).right_aligned();
-    let description_height = description.len() as u16;
-    Row::new([
-        vertical_padded(crate_name),
-        Text::from(description),
-        vertical_padded(downloads),
-    ])
-    .height(description_height)
-    .fg(config::get().color.base05)
-    .bg(bg_color(index, selected_index))
+    markers: RowMarkers,
+) -> (Row<'a>, u16) {
+    // in compact mode only the selected row is expanded to the full description
+    let compact = config::get().compact_results && index != selected_index;
+    let description_text = krate.description.as_deref().unwrap_or_default();
+    let description = if compact {
+        vec![Line::from(truncate_with_ellipsis(
+            description_text,
+            description_column_width,
+        ))]
+    } else {
+        let mut description = textwrap::wrap(description_text, description_column_width)
+            .iter()
+            .map(|s| Line::from(s.to_string()))
+            .collect_vec();
+        description.insert(0, "".into());
+        description.push("".into());
+        description
+    };
+    let vertical_padded = |line: Line<'a>| {
+        if compact {
+            Text::from(line)
+        } else {
+            Text::from(vec!["".into(), line, "".into()])
+        }
+    };
+    let mut crate_name = Line::default();
+    if markers.marked {
+        crate_name.push_span("✓ ".fg(config::get().color.base0b));
+    }
+    if markers.bookmarked {
+        crate_name.push_span("★ ".fg(config::get().color.base0a));
+    }
+    crate_name.push_span(krate.name.clone());
+    if markers.in_deps {
+        crate_name.push_span(" ✓ in deps".fg(config::get().color.base03));
+    }
+    let row_height = if compact {
+        1
+    } else if columns.contains(&SearchColumn::Description) {
+        description.len() as u16
+    } else {
+        3
+    };
+    let mut description = Some(description);
+    let cells = columns.iter().map(|column| match column {
+        SearchColumn::Name => vertical_padded(crate_name.clone()),
+        SearchColumn::Description => Text::from(description.take().unwrap_or_default()),
+        SearchColumn::Downloads => {
+            vertical_padded(Line::from(format::format_downloads(krate.downloads)).right_aligned())
+        }
+        SearchColumn::Updated => {
+            vertical_padded(Line::from(format::format_timestamp(krate.updated_at)))
+        }
+        SearchColumn::RecentDownloads => vertical_padded(
+            Line::from(
+                krate
+                    .recent_downloads
+                    .map(format::format_downloads)
+                    .unwrap_or_default(),
+            )
+            .right_aligned(),
+        ),
+    });
+    let row = Row::new(cells.collect_vec())
+        .height(row_height)
+        .fg(config::get().color.base05)
+        .bg(bg_color(index, selected_index));
+    (row, row_height)
+}
+
+/// Shortens `text` to fit in `width` columns, ending it with an ellipsis if
+/// anything was cut off.
+fn truncate_with_ellipsis(text: &str, width: usize) -> String {
+    if text.width() <= width {
+        return text.to_string();
+    }
+    let mut truncated = String::new();
+    let mut truncated_width = 0;
+    for c in text.chars() {
+        let char_width = c.width().unwrap_or_default();
+        if truncated_width + char_width + 1 > width {
+            break;
+        }
+        truncated.push(c);
+        truncated_width += char_width;
+    }
+    truncated.push('…');
+    truncated
+}
+
+/// Computes the screen area of each row that fits in the table starting at
+/// `offset`.
+fn visible_row_areas(
+    table_area: Rect,
+    header_height: u16,
+    offset: usize,
+    row_heights: &[u16],
+) -> Vec<(usize, Rect)> {
+    let mut y = table_area.y.saturating_add(header_height);
+    let mut areas = vec![];
+    for (index, height) in row_heights.iter().enumerate().skip(offset) {
+        if y >= table_area.bottom() {
+            break;
+        }
+        let height = (*height).min(table_area.bottom() - y);
+        areas.push((index, Rect::new(table_area.x, y, table_area.width, height)));
+        y = y.saturating_add(height);
+    }
+    areas
 }
 
 fn bg_color(index: usize, selected_index: usize) -> Color {
@@ -195,7 +445,7 @@ fn bg_color(index: usize, selected_index: usize) -> Color {
     }
 }
 
-fn render_table_borders(state: &mut SearchResults, spacers: [Rect; 4], buf: &mut Buffer) {
+fn render_table_borders(state: &mut SearchResults, spacers: &[Rect], buf: &mut Buffer) {
     // only render margins when there's items in the table
     if !state.crates.is_empty() {
         // don't render margin for the first column
@@ -215,3 +465,99 @@ fn render_table_borders(state: &mut SearchResults, spacers: [Rect; 4], buf: &mut
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use ratatui::{Terminal, backend::TestBackend};
+
+    use super::*;
+    use crate::test_utils;
+
+    fn krate(name: &str, description: &str, downloads: u64) -> Crate {
+        let mut krate = test_utils::krate(name);
+        krate.description = Some(description.into());
+        krate.downloads = downloads;
+        krate
+    }
+
+    /// Renders the results into a 60x14 terminal and returns its lines.
+    fn render(state: &mut SearchResults, highlight: bool) -> Vec<String> {
+        config::init_for_tests();
+        let mut terminal = Terminal::new(TestBackend::new(60, 14)).unwrap();
+        terminal
+            .draw(|frame| {
+                frame.render_stateful_widget(
+                    SearchResultsWidget::new(highlight),
+                    frame.area(),
+                    state,
+                )
+            })
+            .unwrap();
+        let buffer = terminal.backend().buffer();
+        (0..buffer.area.height)
+            .map(|y| {
+                (0..buffer.area.width)
+                    .map(|x| buffer[(x, y)].symbol())
+                    .collect::<String>()
+            })
+            .collect()
+    }
+
+    fn results() -> SearchResults {
+        let mut state = SearchResults {
+            crates: vec![
+                krate(
+                    "ratatui",
+                    "A library that's all about cooking up terminal user interfaces",
+                    1234567,
+                ),
+                krate("tui", "Terminal user interfaces", 42),
+            ],
+            ..Default::default()
+        };
+        state.select(Some(0));
+        state
+    }
+
+    #[test]
+    fn renders_the_header_row() {
+        let lines = render(&mut results(), true);
+        assert_eq!(
+            lines[1],
+            "   Name                   Description           Downloads   "
+        );
+    }
+
+    #[test]
+    fn wraps_descriptions_to_the_column_width() {
+        let lines = render(&mut results(), true);
+        assert_eq!(
+            lines[4..8],
+            [
+                " █ ratatui              │ A library that's    │   1,234,567 ",
+                "                        │ all about cooking   │             ",
+                "                        │ up terminal user    │             ",
+                "                        │ interfaces          │             ",
+            ]
+        );
+        assert_eq!(
+            lines[10..12],
+            [
+                "   tui                  │ Terminal user       │          42 ",
+                "                        │ interfaces          │             ",
+            ]
+        );
+    }
+
+    #[test]
+    fn marks_the_selection_with_the_highlight_symbol() {
+        let mut state = results();
+        state.select(Some(1));
+        let lines = render(&mut state, true);
+        assert!(lines[4].starts_with("   ratatui"));
+        assert!(lines[10].starts_with(" █ tui"));
+
+        let lines = render(&mut state, false);
+        assert!(lines[10].starts_with(" • tui"));
+    }
+}
@@ -1,39 +1,133 @@
 use ratatui::{prelude::*, widgets::*};
 
-use crate::{app::Mode, command::Command, config};
+use crate::{app::Mode, command::Command, config, crates_io_api_helper::RequestStats, format};
 
 pub struct StatusBarWidget {
     text: String,
     mode: Mode,
     sort: crates_io_api::Sort,
+    min_downloads: Option<u64>,
+    quick_filters: Vec<String>,
+    sort_ascending: bool,
+    type_ahead: String,
+    request_stats: Option<RequestStats>,
 }
 
 impl StatusBarWidget {
     pub fn new(mode: Mode, sort: crates_io_api::Sort, text: String) -> Self {
-        Self { text, mode, sort }
+        Self {
+            text,
+            mode,
+            sort,
+            min_downloads: None,
+            quick_filters: vec![],
+            sort_ascending: false,
+            type_ahead: String::new(),
+            request_stats: None,
+        }
+    }
+
+    /// Shows the type-ahead buffer while it is active.
+    pub fn type_ahead(mut self, type_ahead: String) -> Self {
+        self.type_ahead = type_ahead;
+        self
+    }
+
+    /// Shows the number of requests sent to crates.io on the right.
+    pub fn request_stats(mut self, request_stats: Option<RequestStats>) -> Self {
+        self.request_stats = request_stats;
+        self
+    }
+
+    /// Shows an arrow for the sort direction next to the sort name.
+    pub fn sort_ascending(mut self, sort_ascending: bool) -> Self {
+        self.sort_ascending = sort_ascending;
+        self
+    }
+
+    /// Shows the active quick filters next to the query.
+    pub fn quick_filters(mut self, quick_filters: Vec<String>) -> Self {
+        self.quick_filters = quick_filters;
+        self
+    }
+
+    /// Shows the active download threshold next to the query.
+    pub fn min_downloads(mut self, min_downloads: Option<u64>) -> Self {
+        self.min_downloads = min_downloads;
+        self
     }
 }
 
 impl Widget for StatusBarWidget {
     fn render(self, area: Rect, buf: &mut Buffer) {
-        self.status().render(area, buf);
+        let stats = self.request_stats_line();
+        let [status_area, stats_area] = Layout::horizontal([
+            Constraint::Fill(1),
+            Constraint::Length(stats.width() as u16),
+        ])
+        .areas(area);
+        self.status().render(status_area, buf);
+        stats.render(stats_area, buf);
     }
 }
 
 impl StatusBarWidget {
     fn input_text(&self) -> Line<'_> {
         if self.mode.is_picker() {
-            Line::from(vec![
+            let mut spans = vec![
                 self.text.clone().into(),
                 " (".into(),
                 format!("{:?}", self.sort.clone()).fg(config::get().color.base0d),
+                if self.sort_ascending { " ↑" } else { " ↓" }.fg(config::get().color.base0d),
                 ")".into(),
-            ])
+            ];
+            if let Some(min_downloads) = self.min_downloads {
+                spans.extend([
+                    " (≥ ".into(),
+                    format::format_count(min_downloads).fg(config::get().color.base0d),
+                    " downloads)".into(),
+                ]);
+            }
+            for filter in &self.quick_filters {
+                spans.extend([
+                    " [".into(),
+                    filter.clone().fg(config::get().color.base0d),
+                    "]".into(),
+                ]);
+            }
+            if !self.type_ahead.is_empty() {
+                spans.extend([
+                    " jump to: ".into(),
+                    self.type_ahead.clone().fg(config::get().color.base0a),
+                ]);
+            }
+            Line::from(spans)
+        } else if self.mode.is_help() {
+            self.text.clone().into()
+        } else if self.mode.is_summary() {
+            self.text.clone().fg(config::get().color.base03).into()
         } else {
             "".into()
         }
     }
 
+    /// The number of requests sent to crates.io, right-aligned after the
+    /// status, or nothing when the stats aren't shown.
+    fn request_stats_line(&self) -> Line<'static> {
+        let Some(stats) = self.request_stats else {
+            return Line::default();
+        };
+        let age = stats
+            .last_request
+            .map(|last| format!(", last {}s ago", last.elapsed().as_secs()))
+            .unwrap_or_default();
+        Line::from(vec![
+            " │ ".fg(config::get().color.base03),
+            format!("reqs: {}{age}", stats.count).fg(config::get().color.base03),
+        ])
+        .right_aligned()
+    }
+
     fn status(&self) -> Block<'_> {
         let line = if self.mode.is_filter() {
             let help = config::get()
@@ -102,8 +196,61 @@ impl StatusBarWidget {
                 help.bold(),
                 " for help".into(),
             ]
+        } else if self.mode.is_bookmarks() {
+            let toggle_bookmark = config::get()
+                .key_bindings
+                .get_config_for_command(self.mode, Command::ToggleBookmark)
+                .into_iter()
+                .next()
+                .unwrap_or_default();
+            let help = config::get()
+                .key_bindings
+                .get_config_for_command(self.mode, Command::SwitchMode(Mode::Help))
+                .into_iter()
+                .next()
+                .unwrap_or_default();
+            vec![
+                toggle_bookmark.bold(),
+                " to remove bookmark, ".into(),
+                "ESC".bold(),
+                " to return, ".into(),
+                help.bold(),
+                " for help".into(),
+            ]
+        } else if self.mode.is_categories() {
+            let search_category = config::get()
+                .key_bindings
+                .get_config_for_command(self.mode, Command::SearchCategory)
+                .into_iter()
+                .next()
+                .unwrap_or_default();
+            let help = config::get()
+                .key_bindings
+                .get_config_for_command(self.mode, Command::SwitchMode(Mode::Help))
+                .into_iter()
+                .next()
+                .unwrap_or_default();
+            vec![
+                search_category.bold(),
+                " to list crates, ".into(),
+                "ESC".bold(),
+                " to return, ".into(),
+                help.bold(),
+                " for help".into(),
+            ]
         } else if self.mode.is_help() {
-            vec!["ESC".bold(), " to return".into()]
+            let filter = config::get()
+                .key_bindings
+                .get_config_for_command(self.mode, Command::StartHelpFilter)
+                .into_iter()
+                .next()
+                .unwrap_or_default();
+            vec![
+                filter.bold(),
+                " to filter, ".into(),
+                "ESC".bold(),
+                " to return".into(),
+            ]
         } else {
             let search = config::get()
                 .key_bindings
@@ -7,11 +7,17 @@ use std::sync::{
 use itertools::Itertools;
 use ratatui::{layout::Flex, prelude::*, widgets::*};
 use strum::{Display, EnumIs, EnumIter, FromRepr};
-use tokio::sync::mpsc::UnboundedSender;
-
-use crate::{action::Action, config, crates_io_api_helper};
+use tokio::{sync::mpsc::UnboundedSender, task::JoinHandle};
+use tracing::warn;
+
+use crate::{
+    action::Action,
+    cache::CachedSummary,
+    config,
+    crates_io_api_helper::{self, ApiError},
+};
 
-#[derive(Default, Debug, Clone, Copy, EnumIs, FromRepr, Display, EnumIter)]
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, EnumIs, FromRepr, Display, EnumIter)]
 pub enum SummaryMode {
     #[default]
     NewCrates,
@@ -22,8 +28,6 @@ pub enum SummaryMode {
     PopularCategories,
 }
 
-const HIGHLIGHT_SYMBOL: &str = " █ ";
-
 impl SummaryMode {
     /// Get the previous tab, if there is no previous tab return the current tab.
     fn previous(&mut self) {
@@ -39,6 +43,17 @@ impl SummaryMode {
         *self = Self::from_repr(next_index).unwrap_or(*self)
     }
 
+    /// Moves to the panel next to this one in the 3×2 grid the panels are
+    /// rendered in, staying put at the edges.
+    fn move_in_grid(&mut self, columns: isize, rows: isize) {
+        const COLUMNS: isize = 3;
+        const ROWS: isize = 2;
+        let index = *self as isize;
+        let column = (index % COLUMNS + columns).clamp(0, COLUMNS - 1);
+        let row = (index / COLUMNS + rows).clamp(0, ROWS - 1);
+        *self = Self::from_repr((row * COLUMNS + column) as usize).unwrap_or(*self)
+    }
+
     fn url_prefix(&self) -> String {
         match self {
             SummaryMode::NewCrates => "https://crates.io/crates/",
@@ -52,17 +67,35 @@ impl SummaryMode {
     }
 }
 
-#[derive(Debug, Clone)]
+/// An entry of one of the summary lists.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SummaryItem {
+    Crate(String),
+    Keyword(String),
+    Category(String),
+}
+
+#[derive(Debug)]
 pub struct Summary {
     pub state: [ListState; 6],
+    pub scrollbar_state: [ScrollbarState; 6],
     pub last_selection: [usize; 6],
     pub mode: SummaryMode,
     pub summary_data: Option<crates_io_api::Summary>,
 
+    /// When `summary_data` was fetched from crates.io.
+    pub fetched_at: Option<chrono::DateTime<chrono::Utc>>,
+
+    /// The number of items that fit in a list at the last render.
+    pub page_size: usize,
+
     /// A thread-safe shared container holding the detailed information about
     /// the currently selected crate; this can be `None` if no crate is
     /// selected.
-    pub data: Arc<Mutex<Option<crates_io_api::Summary>>>,
+    pub data: Arc<Mutex<Option<CachedSummary>>>,
+
+    /// The in-flight summary request, aborted on quit.
+    request_task_handle: Option<JoinHandle<()>>,
 
     /// Sender end of an asynchronous channel for dispatching actions from
     /// various parts of the app to be handled by the event loop.
@@ -79,10 +112,14 @@ impl Summary {
             tx,
             loading_status,
             state: Default::default(),
+            scrollbar_state: Default::default(),
             last_selection: Default::default(),
             mode: Default::default(),
             summary_data: Default::default(),
+            fetched_at: None,
             data: Default::default(),
+            request_task_handle: None,
+            page_size: 1,
         }
     }
     pub fn mode(&self) -> SummaryMode {
@@ -91,23 +128,38 @@ impl Summary {
 
     pub fn url(&self) -> Option<String> {
         let prefix = self.mode.url_prefix();
-        if let Some(ref summary) = self.summary_data {
-            let state = self.get_state(self.mode);
-            let i = state.selected().unwrap_or_default().saturating_sub(1); // starting index for list is 1 because we render empty line as the 0th element
-            tracing::debug!("i = {i}");
-            let suffix = match self.mode {
-                SummaryMode::NewCrates => summary.new_crates[i].name.clone(),
-                SummaryMode::MostDownloaded => summary.most_downloaded[i].name.clone(),
-                SummaryMode::JustUpdated => summary.just_updated[i].name.clone(),
-                SummaryMode::MostRecentlyDownloaded => {
-                    summary.most_recently_downloaded[i].name.clone()
-                }
-                SummaryMode::PopularKeywords => summary.popular_keywords[i].id.clone(),
-                SummaryMode::PopularCategories => summary.popular_categories[i].slug.clone(),
-            };
-            Some(format!("{prefix}{suffix}"))
-        } else {
-            None
+        let suffix = match self.selected_item()? {
+            SummaryItem::Crate(name) => name,
+            SummaryItem::Keyword(id) => id,
+            SummaryItem::Category(slug) => slug,
+        };
+        Some(format!("{prefix}{suffix}"))
+    }
+
+    /// Returns the entry selected in the current list.
+    pub fn selected_item(&self) -> Option<SummaryItem> {
+        let summary = self.summary_data.as_ref()?;
+        let state = self.get_state(self.mode);
+        let i = state.selected().unwrap_or_default().saturating_sub(1); // starting index for list is 1 because we render empty line as the 0th element
+        tracing::debug!("i = {i}");
+        let crate_name = |crates: &[crates_io_api::Crate]| {
+            crates
+                .get(i)
+                .map(|krate| SummaryItem::Crate(krate.name.clone()))
+        };
+        match self.mode {
+            SummaryMode::NewCrates => crate_name(&summary.new_crates),
+            SummaryMode::MostDownloaded => crate_name(&summary.most_downloaded),
+            SummaryMode::JustUpdated => crate_name(&summary.just_updated),
+            SummaryMode::MostRecentlyDownloaded => crate_name(&summary.most_recently_downloaded),
+            SummaryMode::PopularKeywords => summary
+                .popular_keywords
+                .get(i)
+                .map(|keyword| SummaryItem::Keyword(keyword.id.clone())),
+            SummaryMode::PopularCategories => summary
+                .popular_categories
+                .get(i)
+                .map(|category| SummaryItem::Category(category.slug.clone())),
         }
     }
 
@@ -142,46 +194,119 @@ impl Summary {
     }
 
     pub fn next_mode(&mut self) {
-        self.save_state();
-        let old_state = self.get_state_mut(self.mode);
-        *old_state.selected_mut() = None;
-        self.mode.next();
-        let i = self.last_selection[self.mode as usize];
-        let new_state = self.get_state_mut(self.mode);
-        *new_state.selected_mut() = Some(i);
+        self.change_mode(SummaryMode::next);
     }
 
     pub fn previous_mode(&mut self) {
+        self.change_mode(SummaryMode::previous);
+    }
+
+    pub fn move_left(&mut self) {
+        self.change_mode(|mode| mode.move_in_grid(-1, 0));
+    }
+
+    pub fn move_right(&mut self) {
+        self.change_mode(|mode| mode.move_in_grid(1, 0));
+    }
+
+    pub fn move_up(&mut self) {
+        self.change_mode(|mode| mode.move_in_grid(0, -1));
+    }
+
+    pub fn move_down(&mut self) {
+        self.change_mode(|mode| mode.move_in_grid(0, 1));
+    }
+
+    /// Switches to another panel, restoring the selection it had when it was
+    /// last left.
+    fn change_mode(&mut self, change: impl FnOnce(&mut SummaryMode)) {
         self.save_state();
         let old_state = self.get_state_mut(self.mode);
         *old_state.selected_mut() = None;
-        self.mode.previous();
+        change(&mut self.mode);
         let i = self.last_selection[self.mode as usize];
         let new_state = self.get_state_mut(self.mode);
         *new_state.selected_mut() = Some(i);
     }
 
-    pub fn request(&self) -> Result<()> {
+    /// Loads the summary from the cache if it is fresh, otherwise fetches it
+    /// from crates.io. Only the crates.io summary is cached.
+    pub fn request(&mut self) -> Result<()> {
+        if config::get().registry_url.is_none()
+            && let Some(cached) = CachedSummary::load().filter(CachedSummary::is_fresh)
+        {
+            *self.data.lock().unwrap() = Some(cached);
+            self.tx.send(Action::UpdateSummary)?;
+            self.tx.send(Action::ScrollDown)?;
+            return Ok(());
+        }
+        self.refresh();
+        Ok(())
+    }
+
+    /// Fetches the summary from crates.io and updates the cache.
+    pub fn refresh(&mut self) {
+        self.abort_request();
         let tx = self.tx.clone();
         let loading_status = self.loading_status.clone();
         let summary = self.data.clone();
-        tokio::spawn(async move {
+        self.request_task_handle = Some(tokio::spawn(async move {
             loading_status.store(true, Ordering::SeqCst);
-            if let Err(error_message) = crates_io_api_helper::request_summary(summary).await {
-                let _ = tx.send(Action::ShowErrorPopup(error_message));
+            match crates_io_api_helper::request_summary().await {
+                Ok(summary_data) => {
+                    let cached = CachedSummary::new(summary_data);
+                    if config::get().registry_url.is_none()
+                        && let Err(err) = cached.save()
+                    {
+                        warn!("Unable to write summary cache: {err}");
+                    }
+                    *summary.lock().unwrap() = Some(cached);
+                }
+                // registries mirroring crates.io may not have a summary
+                Err(ApiError::NotFound(_)) => {
+                    let _ = tx.send(Action::HideSummary);
+                }
+                Err(err) => {
+                    crates_io_api_helper::report_error(
+                        &tx,
+                        "loading the summary",
+                        &err,
+                        Some(Action::RefreshSummary),
+                    );
+                }
             }
             loading_status.store(false, Ordering::SeqCst);
             let _ = tx.send(Action::UpdateSummary);
             let _ = tx.send(Action::ScrollDown);
-        });
-        Ok(())
+        }));
+    }
+
+    pub fn abort_request(&mut self) {
+        if let Some(handle) = self.request_task_handle.take() {
+            handle.abort();
+        }
     }
 
     pub fn update(&mut self) {
-        if let Some(summary) = self.data.lock().unwrap().clone() {
-            self.summary_data = Some(summary);
+        let cached = self.data.lock().unwrap().clone();
+        self.fetched_at = cached.as_ref().map(|cached| cached.fetched_at);
+        self.summary_data = cached.map(|cached| cached.summary);
+    }
+
+    /// Describes how old the summary is for the status bar.
+    pub fn age_description(&self) -> String {
+        let Some(fetched_at) = self.fetched_at else {
+            return String::new();
+        };
+        let age = chrono::Utc::now() - fetched_at;
+        if age.num_minutes() < 1 {
+            "updated just now".into()
+        } else if age.num_hours() < 1 {
+            format!("updated {} min ago", age.num_minutes())
+        } else if age.num_days() < 1 {
+            format!("updated {} h ago", age.num_hours())
         } else {
-            self.summary_data = None;
+            format!("updated {} d ago", age.num_days())
         }
     }
 }
@@ -191,6 +316,17 @@ impl Summary {
         Borders::NONE
     }
 
+    fn list(&self, mode: SummaryMode) -> List<'static> {
+        match mode {
+            SummaryMode::NewCrates => self.new_crates(),
+            SummaryMode::MostDownloaded => self.most_downloaded(),
+            SummaryMode::JustUpdated => self.just_updated(),
+            SummaryMode::MostRecentlyDownloaded => self.most_recently_downloaded(),
+            SummaryMode::PopularKeywords => self.popular_keywords(),
+            SummaryMode::PopularCategories => self.popular_categories(),
+        }
+    }
+
     fn new_crates(&self) -> List<'static> {
         let selected = self.mode.is_new_crates();
         let borders = self.borders(selected);
@@ -347,13 +483,20 @@ fn list_builder<'a>(
                 .title_style(title_style)
                 .title_alignment(Alignment::Left),
         )
-        .highlight_symbol(HIGHLIGHT_SYMBOL)
-        .highlight_style(config::get().color.base05)
+        .highlight_symbol(Line::from(format!(
+            " {} ",
+            config::get().highlight_symbol()
+        )))
+        .highlight_style(config::get().highlight_style())
         .highlight_spacing(HighlightSpacing::Always)
 }
 
 pub struct SummaryWidget;
 
+/// Below this size the 3×2 grid is collapsed into the selected list alone.
+const GRID_MIN_WIDTH: u16 = 90;
+const GRID_MIN_HEIGHT: u16 = 24;
+
 impl SummaryWidget {
     fn render_list(
         &self,
@@ -363,10 +506,33 @@ impl SummaryWidget {
         mode: SummaryMode,
         state: &mut Summary,
     ) {
-        *(state.get_state_mut(mode).selected_mut()) = state
+        use Constraint::*;
+
+        let [list_area, scrollbar_area] = Layout::horizontal([Fill(1), Length(1)]).areas(area);
+        // the block title takes up the first line of the list
+        let [_, scrollbar_area] = Layout::vertical([Length(1), Fill(1)]).areas(scrollbar_area);
+        state.page_size = (area.height as usize).saturating_sub(1).max(1);
+
+        let content_length = list.len();
+        let selected = state
             .selected(mode)
-            .map(|i| i.min(list.len().saturating_sub(1)));
-        StatefulWidget::render(list, area, buf, state.get_state_mut(mode));
+            .map(|i| i.min(content_length.saturating_sub(1)));
+        *(state.get_state_mut(mode).selected_mut()) = selected;
+        StatefulWidget::render(list, list_area, buf, state.get_state_mut(mode));
+
+        let scrollbar_state = &mut state.scrollbar_state[mode as usize];
+        *scrollbar_state = scrollbar_state
+            .content_length(content_length)
+            .position(selected.unwrap_or_default());
+        if state.mode == mode {
+            Scrollbar::default()
+                .track_symbol(Some(" "))
+                .thumb_symbol("▐")
+                .begin_symbol(None)
+                .end_symbol(None)
+                .track_style(config::get().color.base06)
+                .render(scrollbar_area, buf, scrollbar_state);
+        }
     }
 }
 
@@ -378,6 +544,14 @@ impl StatefulWidget for &SummaryWidget {
         }
         use Constraint::*;
 
+        if area.width < GRID_MIN_WIDTH || area.height < GRID_MIN_HEIGHT {
+            let [_, area, _] = Layout::horizontal([Length(1), Fill(1), Length(1)]).areas(area);
+            let mode = state.mode;
+            let list = state.list(mode);
+            self.render_list(area, buf, list, mode, state);
+            return;
+        }
+
         let [_, area] = Layout::vertical([Min(0), Percentage(90)]).areas(area);
 
         let [_, area, _] = Layout::horizontal([Min(0), Percentage(85), Min(0)]).areas(area);
@@ -444,3 +618,77 @@ impl StatefulWidget for &SummaryWidget {
         );
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use strum::IntoEnumIterator;
+
+    use super::*;
+    use crate::test_utils::crate_json;
+
+    fn summary_data() -> crates_io_api::Summary {
+        serde_json::from_value(json!({
+            "just_updated": [crate_json("just-updated")],
+            "most_downloaded": [crate_json("most-downloaded")],
+            "new_crates": [crate_json("new-crate")],
+            "most_recently_downloaded": [crate_json("most-recently-downloaded")],
+            "num_crates": 4,
+            "num_downloads": 0,
+            "popular_categories": [{
+                "category": "Category",
+                "crates_cnt": 1,
+                "created_at": "2024-01-01T00:00:00Z",
+                "description": "",
+                "id": "category",
+                "slug": "popular-category",
+            }],
+            "popular_keywords": [{
+                "id": "popular-keyword",
+                "keyword": "keyword",
+                "crates_cnt": 1,
+                "created_at": "2024-01-01T00:00:00Z",
+            }],
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn url_uses_the_list_for_each_mode() {
+        let (tx, _rx) = tokio::sync::mpsc::unbounded_channel();
+        let mut summary = Summary::new(tx, Default::default());
+        summary.summary_data = Some(summary_data());
+
+        let expected = [
+            "https://crates.io/crates/new-crate",
+            "https://crates.io/crates/most-downloaded",
+            "https://crates.io/crates/just-updated",
+            "https://crates.io/crates/most-recently-downloaded",
+            "https://crates.io/keywords/popular-keyword",
+            "https://crates.io/categories/popular-category",
+        ];
+        for (mode, expected) in SummaryMode::iter().zip(expected) {
+            summary.mode = mode;
+            summary.get_state_mut(mode).select(Some(1));
+            assert_eq!(summary.url().as_deref(), Some(expected), "{mode}");
+        }
+    }
+
+    #[test]
+    fn move_in_grid_follows_the_rendered_layout() {
+        let moved = |mut mode: SummaryMode, columns, rows| {
+            mode.move_in_grid(columns, rows);
+            mode.to_string()
+        };
+        assert_eq!(moved(SummaryMode::NewCrates, 1, 0), "MostDownloaded");
+        assert_eq!(moved(SummaryMode::NewCrates, -1, 0), "NewCrates");
+        assert_eq!(
+            moved(SummaryMode::NewCrates, 0, 1),
+            "MostRecentlyDownloaded"
+        );
+        assert_eq!(moved(SummaryMode::JustUpdated, 1, 0), "JustUpdated");
+        assert_eq!(moved(SummaryMode::JustUpdated, 0, 1), "PopularCategories");
+        assert_eq!(moved(SummaryMode::PopularKeywords, 0, -1), "MostDownloaded");
+        assert_eq!(moved(SummaryMode::PopularKeywords, 0, 1), "PopularKeywords");
+    }
+}
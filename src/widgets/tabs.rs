@@ -1,13 +1,16 @@
 use ratatui::{prelude::*, widgets::*};
-use strum::{Display, EnumIter, FromRepr};
+use strum::{Display, EnumIter, FromRepr, IntoEnumIterator};
 
-use crate::config;
+use crate::{app::Mode, config};
 
-#[derive(Debug, Default, Clone, Copy, Display, FromRepr, EnumIter)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Display, FromRepr, EnumIter)]
 pub enum SelectedTab {
     #[default]
     Summary,
     Search,
+    Bookmarks,
+    Categories,
+    Trending,
     None,
 }
 
@@ -16,11 +19,57 @@ impl SelectedTab {
         *self = selected_tab
     }
 
+    /// Get the next tab, wrapping around to the first tab after the last one.
+    ///
+    /// `SelectedTab::None` is never returned; stepping forward from it selects
+    /// the first tab.
+    pub fn next(self) -> Self {
+        match self {
+            SelectedTab::None => Self::from_repr(0).unwrap_or(self),
+            _ => Self::from_repr((self as usize + 1) % Self::count()).unwrap_or(self),
+        }
+    }
+
+    /// Get the previous tab, wrapping around to the last tab before the first
+    /// one.
+    ///
+    /// `SelectedTab::None` is never returned; stepping backward from it selects
+    /// the last tab.
+    pub fn previous(self) -> Self {
+        let count = Self::count();
+        match self {
+            SelectedTab::None => Self::from_repr(count - 1).unwrap_or(self),
+            _ => Self::from_repr((self as usize + count - 1) % count).unwrap_or(self),
+        }
+    }
+
+    /// The mode the app switches to when this tab is selected.
+    pub fn mode(self) -> Mode {
+        match self {
+            SelectedTab::Summary | SelectedTab::None => Mode::Summary,
+            SelectedTab::Search => Mode::Search,
+            SelectedTab::Bookmarks => Mode::Bookmarks,
+            SelectedTab::Categories => Mode::Categories,
+            SelectedTab::Trending => Mode::Trending,
+        }
+    }
+
+    /// The number of tabs that can be navigated to, excluding
+    /// `SelectedTab::None`.
+    fn count() -> usize {
+        Self::iter().filter(|tab| *tab != SelectedTab::None).count()
+    }
+
     pub fn highlight_style() -> Style {
-        Style::default()
+        let style = Style::default()
             .fg(config::get().color.base00)
             .bg(config::get().color.base0a)
-            .bold()
+            .bold();
+        if config::get().no_color {
+            style.reversed()
+        } else {
+            style
+        }
     }
 }
 
@@ -29,6 +78,9 @@ impl Widget for &SelectedTab {
         match self {
             SelectedTab::Summary => self.render_tab_summary(area, buf),
             SelectedTab::Search => self.render_tab_search(area, buf),
+            SelectedTab::Bookmarks => self.render_tab_bookmarks(area, buf),
+            SelectedTab::Categories => self.render_tab_categories(area, buf),
+            SelectedTab::Trending => self.render_tab_trending(area, buf),
             SelectedTab::None => (),
         }
     }
@@ -57,6 +109,24 @@ impl SelectedTab {
             .render(area, buf)
     }
 
+    fn render_tab_bookmarks(&self, area: Rect, buf: &mut Buffer) {
+        Paragraph::new("Bookmarks")
+            .block(self.block())
+            .render(area, buf)
+    }
+
+    fn render_tab_categories(&self, area: Rect, buf: &mut Buffer) {
+        Paragraph::new("Categories")
+            .block(self.block())
+            .render(area, buf)
+    }
+
+    fn render_tab_trending(&self, area: Rect, buf: &mut Buffer) {
+        Paragraph::new("Trending")
+            .block(self.block())
+            .render(area, buf)
+    }
+
     fn block(&self) -> Block<'static> {
         Block::default()
             .borders(Borders::ALL)
@@ -65,3 +135,28 @@ impl SelectedTab {
             .border_style(config::get().color.base03)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn next_wraps_and_skips_none() {
+        assert_eq!(SelectedTab::Summary.next(), SelectedTab::Search);
+        assert_eq!(SelectedTab::Search.next(), SelectedTab::Bookmarks);
+        assert_eq!(SelectedTab::Bookmarks.next(), SelectedTab::Categories);
+        assert_eq!(SelectedTab::Categories.next(), SelectedTab::Trending);
+        assert_eq!(SelectedTab::Trending.next(), SelectedTab::Summary);
+        assert_eq!(SelectedTab::None.next(), SelectedTab::Summary);
+    }
+
+    #[test]
+    fn previous_wraps_and_skips_none() {
+        assert_eq!(SelectedTab::Summary.previous(), SelectedTab::Trending);
+        assert_eq!(SelectedTab::Trending.previous(), SelectedTab::Categories);
+        assert_eq!(SelectedTab::Categories.previous(), SelectedTab::Bookmarks);
+        assert_eq!(SelectedTab::Bookmarks.previous(), SelectedTab::Search);
+        assert_eq!(SelectedTab::Search.previous(), SelectedTab::Summary);
+        assert_eq!(SelectedTab::None.previous(), SelectedTab::Trending);
+    }
+}
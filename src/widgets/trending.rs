@@ -0,0 +1,151 @@
+use std::sync::{
+    Arc, Mutex,
+    atomic::{AtomicBool, Ordering},
+};
+
+use ratatui::{layout::Flex, prelude::*};
+use tokio::sync::mpsc::UnboundedSender;
+
+use crate::{
+    action::Action, app::Mode, bookmarks::Bookmarks, command::Command, config, crates_io_api_helper,
+};
+
+use super::search_results::{SearchColumn, SearchResults, SearchResultsWidget};
+
+/// The columns of the trending table, leading with the downloads it is
+/// sorted by.
+const TRENDING_COLUMNS: &[SearchColumn] = &[
+    SearchColumn::Name,
+    SearchColumn::Description,
+    SearchColumn::RecentDownloads,
+    SearchColumn::Downloads,
+];
+
+/// The crates downloaded the most recently, i.e. a search sorted by recent
+/// downloads that doesn't disturb the search page.
+#[derive(Debug)]
+pub struct Trending {
+    /// A table component listing the trending crates.
+    pub results: SearchResults,
+
+    /// A thread-safe, shared vector holding the trending crates fetched from
+    /// crates.io.
+    pub crates: Arc<Mutex<Vec<crates_io_api::Crate>>>,
+
+    /// Why the last fetch failed, shown in place of the table until a fetch
+    /// succeeds.
+    pub error: Arc<Mutex<Option<String>>>,
+
+    /// Sender end of an asynchronous channel for dispatching actions from
+    /// various parts of the app to be handled by the event loop.
+    tx: UnboundedSender<Action>,
+
+    /// A thread-safe indicator of whether data is currently being loaded,
+    /// allowing different parts of the app to know if it's in a loading state.
+    loading_status: Arc<AtomicBool>,
+}
+
+impl Trending {
+    pub fn new(tx: UnboundedSender<Action>, loading_status: Arc<AtomicBool>) -> Self {
+        Self {
+            results: Default::default(),
+            crates: Default::default(),
+            error: Default::default(),
+            tx,
+            loading_status,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.results.crates.is_empty()
+    }
+
+    /// Spawns an asynchronous task to fetch the trending crates from
+    /// crates.io.
+    pub fn request(&self) {
+        let tx = self.tx.clone();
+        let crates = self.crates.clone();
+        let loading_status = self.loading_status.clone();
+        let error = self.error.clone();
+        *error.lock().unwrap() = None;
+        let page_size = config::get().page_size;
+        tokio::spawn(async move {
+            loading_status.store(true, Ordering::SeqCst);
+            if let Err(err) = crates_io_api_helper::request_trending(page_size, crates).await {
+                *error.lock().unwrap() = Some(err.user_message("loading trending crates"));
+                crates_io_api_helper::report_error(
+                    &tx,
+                    "loading trending crates",
+                    &err,
+                    Some(Action::RefreshTrending),
+                );
+            }
+            loading_status.store(false, Ordering::SeqCst);
+            let _ = tx.send(Action::UpdateTrending);
+        });
+    }
+
+    /// Updates the table with the fetched crates, keeping the selection.
+    pub fn update(&mut self) {
+        self.results.crates = self.crates.lock().unwrap().clone();
+        self.results.content_length(self.results.crates.len());
+        if self.results.crates.is_empty() {
+            self.results.select(None);
+        } else {
+            let selected = self
+                .results
+                .selected()
+                .map_or(0, |i| i.min(self.results.crates.len() - 1));
+            self.results.select(Some(selected));
+        }
+    }
+
+    pub fn scroll_up(&mut self, count: usize) {
+        self.results.scroll_previous(count);
+    }
+
+    pub fn scroll_down(&mut self, count: usize) {
+        self.results.scroll_next(count);
+    }
+}
+
+pub struct TrendingWidget<'a> {
+    bookmarks: &'a Bookmarks,
+}
+
+impl<'a> TrendingWidget<'a> {
+    pub fn new(bookmarks: &'a Bookmarks) -> Self {
+        Self { bookmarks }
+    }
+}
+
+impl StatefulWidget for TrendingWidget<'_> {
+    type State = Trending;
+
+    fn render(self, area: Rect, buf: &mut Buffer, state: &mut Self::State) {
+        if state.is_empty() {
+            let [center] = Layout::vertical([Constraint::Length(1)])
+                .flex(Flex::Center)
+                .areas(area);
+            let line = match state.error.lock().unwrap().as_ref() {
+                Some(error) => {
+                    let retry = config::get()
+                        .key_bindings
+                        .get_config_for_command(Mode::Trending, Command::RefreshTrending)
+                        .into_iter()
+                        .next()
+                        .unwrap_or_default();
+                    Line::from(format!("{error} Press {retry} to retry."))
+                        .fg(config::get().color.base08)
+                }
+                None => Line::from("Loading trending crates…").fg(config::get().color.base03),
+            };
+            line.centered().render(center, buf);
+            return;
+        }
+        SearchResultsWidget::new(true)
+            .bookmarks(self.bookmarks)
+            .columns(TRENDING_COLUMNS)
+            .render(area, buf, &mut state.results);
+    }
+}
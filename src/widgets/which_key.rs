@@ -0,0 +1,63 @@
+use itertools::Itertools;
+use ratatui::{layout::Flex, prelude::*, widgets::*};
+
+use crate::{command::Command, config, serde_helper::keybindings::key_event_to_string};
+
+use crossterm::event::KeyEvent;
+
+/// Lists the keys that complete a pending key sequence, like vim's
+/// which-key, so that multi-key bindings can be discovered.
+pub struct WhichKeyWidget {
+    pending: Vec<KeyEvent>,
+    completions: Vec<(Vec<KeyEvent>, Command)>,
+}
+
+impl WhichKeyWidget {
+    pub fn new(pending: Vec<KeyEvent>, completions: Vec<(Vec<KeyEvent>, Command)>) -> Self {
+        Self {
+            pending,
+            completions,
+        }
+    }
+}
+
+impl Widget for WhichKeyWidget {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        use Constraint::*;
+
+        let color = config::get().color;
+        let rows = self
+            .completions
+            .into_iter()
+            .map(|(keys, command)| {
+                let keys = keys.iter().map(key_event_to_string).join("");
+                (keys, command.to_string())
+            })
+            .sorted()
+            .map(|(keys, command)| {
+                Line::from(vec![
+                    format!(" {keys:>4} ").bold().fg(color.base0d),
+                    format!("{command} ").fg(color.base05),
+                ])
+            })
+            .collect_vec();
+        let width = rows.iter().map(Line::width).max().unwrap_or_default() as u16 + 2;
+        let height = rows.len() as u16 + 2;
+        let [area] = Layout::horizontal([Length(width)])
+            .flex(Flex::End)
+            .areas(area);
+        let [area] = Layout::vertical([Length(height)])
+            .flex(Flex::End)
+            .areas(area);
+        let title = self.pending.iter().map(key_event_to_string).join("");
+        Clear.render(area, buf);
+        Paragraph::new(rows)
+            .block(
+                Block::bordered()
+                    .title(format!(" {title} "))
+                    .border_style(color.base03),
+            )
+            .bg(color.base01)
+            .render(area, buf);
+    }
+}